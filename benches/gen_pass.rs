@@ -0,0 +1,80 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use xkpasswd::prelude::*;
+use xkpasswd::settings::Settings;
+
+const DICT_EN: &[u8] = include_bytes!("../src/assets/dict_en.txt");
+const BATCH_SIZE: usize = 1000;
+
+fn bench_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("load");
+
+    group.bench_function("parsed index", |b| {
+        b.iter(|| Xkpasswd::new(DictIndex::from_bytes(black_box(DICT_EN))))
+    });
+    group.bench_function("static index", |b| {
+        b.iter(|| Xkpasswd::for_language(black_box(Language::English)))
+    });
+    group.bench_function("static index, all languages", |b| {
+        b.iter(|| {
+            Xkpasswd::for_languages(black_box(&[
+                Language::English,
+                Language::French,
+                Language::German,
+                Language::Portuguese,
+                Language::Spanish,
+            ]))
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_gen_pass(c: &mut Criterion) {
+    let settings = Settings::default();
+    let parsed_index = Xkpasswd::new(DictIndex::from_bytes(DICT_EN));
+    let static_index = Xkpasswd::for_language(Language::English);
+
+    let mut group = c.benchmark_group("gen_pass");
+
+    group.bench_function("parsed index", |b| {
+        b.iter(|| parsed_index.gen_pass(black_box(&settings)).unwrap())
+    });
+    group.bench_function("static index", |b| {
+        b.iter(|| static_index.gen_pass(black_box(&settings)).unwrap())
+    });
+
+    group.finish();
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let settings = Settings::default();
+    let mut group = c.benchmark_group("batch");
+
+    group.bench_function("parsed index", |b| {
+        b.iter_batched(
+            || Xkpasswd::new(DictIndex::from_bytes(DICT_EN)),
+            |pass| {
+                for _ in 0..BATCH_SIZE {
+                    black_box(pass.gen_pass(&settings).unwrap());
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("static index", |b| {
+        b.iter_batched(
+            || Xkpasswd::for_language(Language::English),
+            |pass| {
+                for _ in 0..BATCH_SIZE {
+                    black_box(pass.gen_pass(&settings).unwrap());
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_load, bench_gen_pass, bench_batch);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const ASSETS_DIR: &str = "src/assets";
+const LANGUAGES: [&str; 5] = ["de", "en", "es", "fr", "pt"];
+
+// Converts embedded dictionaries into static arrays of words sorted by length,
+// along with per-length offsets, so that loading them costs nothing at runtime.
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+
+    for lang in LANGUAGES {
+        let dict_path = format!("{}/dict_{}.txt", ASSETS_DIR, lang);
+        println!("cargo:rerun-if-changed={}", dict_path);
+
+        let dict_str = fs::read_to_string(&dict_path)
+            .unwrap_or_else(|err| panic!("failed to read dictionary {}: {}", dict_path, err));
+        let index = build_index(&dict_str);
+        fs::write(Path::new(&out_dir).join(format!("dict_{}.rs", lang)), index).unwrap();
+    }
+}
+
+fn build_index(dict_str: &str) -> String {
+    let mut grouped: BTreeMap<u8, Vec<&str>> = BTreeMap::new();
+
+    for line in dict_str.trim().lines() {
+        let mut comps = line.trim().split(':');
+
+        let len = match comps.next().and_then(|len_str| len_str.parse::<u8>().ok()) {
+            Some(len) => len,
+            None => continue,
+        };
+
+        let words = comps.next().unwrap_or("").split(',');
+        grouped
+            .entry(len)
+            .or_default()
+            .extend(words.filter(|word| !word.is_empty()));
+    }
+
+    let max_len = grouped.keys().last().copied().unwrap_or(0) as usize;
+
+    let mut words: Vec<&str> = vec![];
+    let mut offsets: Vec<usize> = vec![0; max_len + 2];
+
+    for len in 0..=max_len {
+        if let Some(group) = grouped.get(&(len as u8)) {
+            words.extend(group);
+        }
+        offsets[len + 1] = words.len();
+    }
+
+    format!(
+        "DictIndex::from_static(&{:?}, &{:?})",
+        words.as_slice(),
+        offsets.as_slice()
+    )
+}
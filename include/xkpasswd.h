@@ -0,0 +1,163 @@
+// Generated by cbindgen from src/ffi/mod.rs with `make ffi-header`, do not edit.
+
+#ifndef XKPASSWD_H
+#define XKPASSWD_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define XK_TRANSFORM_LOWERCASE 1
+
+#define XK_TRANSFORM_TITLECASE 2
+
+#define XK_TRANSFORM_UPPERCASE 4
+
+#define XK_TRANSFORM_INVERSED_TITLECASE 8
+
+#define XK_TRANSFORM_ALTERCASE_LOWER_FIRST 64
+
+#define XK_TRANSFORM_ALTERCASE_UPPER_FIRST 128
+
+// Result of fallible functions, details are given by `xkpasswd_last_error`.
+typedef enum XkStatus {
+  XK_STATUS_OK = 0,
+  XK_STATUS_NULL_POINTER = 1,
+  XK_STATUS_INVALID_UTF8 = 2,
+  XK_STATUS_INVALID_SETTINGS = 3,
+  XK_STATUS_GENERATION_FAILED = 4,
+} XkStatus;
+
+typedef enum XkPreset {
+  XK_PRESET_DEFAULT = 0,
+  XK_PRESET_APPLE_ID = 1,
+  XK_PRESET_WINDOWS_NTLM_V1 = 2,
+  XK_PRESET_SECURITY_QUESTIONS = 3,
+  XK_PRESET_WEB16 = 4,
+  XK_PRESET_WEB32 = 5,
+  XK_PRESET_WIFI = 6,
+  XK_PRESET_XKCD = 7,
+} XkPreset;
+
+typedef enum XkLanguage {
+  XK_LANGUAGE_ENGLISH = 0,
+  XK_LANGUAGE_FRENCH = 1,
+  XK_LANGUAGE_GERMAN = 2,
+  XK_LANGUAGE_PORTUGUESE = 3,
+  XK_LANGUAGE_SPANISH = 4,
+} XkLanguage;
+
+// Password generator for a language, freed with `xkpasswd_generator_free`.
+typedef struct XkGenerator XkGenerator;
+
+// Password settings, freed with `xkpasswd_settings_free`.
+typedef struct XkSettings XkSettings;
+
+// Entropy of generated passwords, in bits, along with the time to break them.
+typedef struct XkEntropy {
+  size_t blind_min;
+  size_t blind_max;
+  size_t seen;
+  size_t guess_years;
+  uint8_t guess_months;
+  uint8_t guess_days;
+} XkEntropy;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message of the last error on the calling thread, NULL if none.
+// Owned by the library and valid until the next failing call on the same thread.
+const char *xkpasswd_last_error(void);
+
+// Default settings.
+struct XkSettings *xkpasswd_settings_new(void);
+
+// Settings of an `XkPreset`, NULL if the preset is unknown.
+struct XkSettings *xkpasswd_settings_from_preset(uint32_t preset);
+
+// # Safety
+// `settings` must come from `xkpasswd_settings_new` or `xkpasswd_settings_from_preset`, or be NULL.
+void xkpasswd_settings_free(struct XkSettings *settings);
+
+// # Safety
+// `settings` must be valid settings.
+enum XkStatus xkpasswd_settings_set_words_count(struct XkSettings *settings, uint8_t words_count);
+
+// Word lengths, with 0 to keep the current bound.
+//
+// # Safety
+// `settings` must be valid settings.
+enum XkStatus xkpasswd_settings_set_word_lengths(struct XkSettings *settings,
+                                                 uint8_t min_length,
+                                                 uint8_t max_length);
+
+// Combination of `XK_TRANSFORM_*` flags.
+//
+// # Safety
+// `settings` must be valid settings.
+enum XkStatus xkpasswd_settings_set_word_transforms(struct XkSettings *settings,
+                                                    uint8_t transforms);
+
+// # Safety
+// `settings` must be valid settings and `separators` a NUL-terminated UTF-8 string.
+enum XkStatus xkpasswd_settings_set_separators(struct XkSettings *settings, const char *separators);
+
+// # Safety
+// `settings` must be valid settings.
+enum XkStatus xkpasswd_settings_set_padding_digits(struct XkSettings *settings,
+                                                   uint8_t prefix,
+                                                   uint8_t suffix);
+
+// # Safety
+// `settings` must be valid settings and `symbols` a NUL-terminated UTF-8 string.
+enum XkStatus xkpasswd_settings_set_padding_symbols(struct XkSettings *settings,
+                                                    const char *symbols);
+
+// # Safety
+// `settings` must be valid settings.
+enum XkStatus xkpasswd_settings_set_padding_symbol_lengths(struct XkSettings *settings,
+                                                           uint8_t prefix,
+                                                           uint8_t suffix);
+
+// Symbols before & after words are padded with fixed lengths.
+//
+// # Safety
+// `settings` must be valid settings.
+enum XkStatus xkpasswd_settings_set_fixed_padding(struct XkSettings *settings);
+
+// Passwords are padded or trimmed to fit a length.
+//
+// # Safety
+// `settings` must be valid settings.
+enum XkStatus xkpasswd_settings_set_adaptive_padding(struct XkSettings *settings, size_t length);
+
+// Generator for an `XkLanguage`, NULL if the language is unknown or not bundled.
+struct XkGenerator *xkpasswd_generator_new(uint32_t language);
+
+// # Safety
+// `generator` must come from `xkpasswd_generator_new`, or be NULL.
+void xkpasswd_generator_free(struct XkGenerator *generator);
+
+// Generate a password, to be freed with `xkpasswd_string_free`.
+// `entropy` may be NULL if not needed.
+//
+// # Safety
+// `generator` & `settings` must be valid, `passwd` must point to writable memory.
+enum XkStatus xkpasswd_gen_pass(const struct XkGenerator *generator,
+                                const struct XkSettings *settings,
+                                char **passwd,
+                                struct XkEntropy *entropy);
+
+// Free a password, wiping it with the `zeroize` feature.
+//
+// # Safety
+// `passwd` must come from `xkpasswd_gen_pass`, or be NULL.
+void xkpasswd_string_free(char *passwd);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* XKPASSWD_H */
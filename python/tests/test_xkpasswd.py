@@ -0,0 +1,149 @@
+import pytest
+
+from xkpasswd import (
+    Entropy,
+    Language,
+    Preset,
+    Settings,
+    WordTransform,
+    Xkpasswd,
+    XkpasswdError,
+)
+
+
+def test_gen_pass():
+    generator = Xkpasswd()
+    passwd, entropy = generator.gen_pass()
+
+    assert isinstance(passwd, str)
+    assert isinstance(entropy, Entropy)
+    assert passwd
+    assert entropy.blind_min <= entropy.blind_max
+    assert 3 == len(entropy.guess_time)
+    assert "guesses/sec" in str(entropy)
+
+
+def test_gen_pass_with_settings():
+    generator = Xkpasswd(Language.ENGLISH)
+    settings = Settings(
+        words_count=5,
+        word_lengths=(4, 6),
+        word_transforms=WordTransform.UPPERCASE,
+        separators="-",
+        padding_digits=(0, 2),
+        padding_symbols="!",
+        padding_symbol_lengths=(1, 0),
+    )
+
+    passwd, _ = generator.gen_pass(settings)
+    assert passwd.startswith("!")
+
+    *words, digits = passwd[1:].split("-")
+    assert 2 == len(digits) and digits.isdigit()
+    assert 5 == len(words)
+    for word in words:
+        assert word.isupper()
+        assert 4 <= len(word) <= 6
+
+
+def test_gen_pass_failed():
+    generator = Xkpasswd(Language.ENGLISH)
+    settings = Settings(included_words=["fooz"])
+
+    with pytest.raises(XkpasswdError, match="0 word"):
+        generator.gen_pass(settings)
+
+
+def test_languages():
+    generator = Xkpasswd(Language.GERMAN, Language.FRENCH)
+    assert generator.word_lengths()
+
+    passwd, _ = generator.gen_pass(Settings(Preset.XKCD))
+    assert 4 == len(passwd.split("-"))
+
+    with pytest.raises(TypeError):
+        Xkpasswd("english")
+
+
+def test_presets():
+    settings = Settings(Preset.WIFI)
+    assert 63 == settings.adaptive_padding
+    assert settings == Settings(Preset.WIFI)
+    assert settings != Settings(Preset.WEB32)
+    assert settings != Settings()
+
+    settings = Settings(Preset.XKCD, words_count=6)
+    assert 6 == settings.words_count
+    assert "-" == settings.separators
+
+
+def test_settings():
+    settings = Settings()
+    assert 3 == settings.words_count
+    assert WordTransform.LOWERCASE | WordTransform.UPPERCASE == settings.word_transforms
+    assert settings.adaptive_padding is None
+
+    updated = settings.replace(
+        words_count=4,
+        padding_digits=(1, None),
+        padding_symbols="#",
+        adaptive_padding=24,
+        excluded_words=["correct", "horse"],
+    )
+    assert 4 == updated.words_count
+    assert (1, 2) == updated.padding_digits
+    assert "#" == updated.padding_symbols
+    assert (0, 0) == updated.padding_symbol_lengths
+    assert 24 == updated.adaptive_padding
+    assert "excluding 2 word(s)" in str(updated)
+
+    # settings are immutable
+    assert 3 == settings.words_count
+    assert updated.replace(adaptive_padding=None).adaptive_padding is None
+
+    # symbol lengths reset adaptive padding, unless given along with it
+    fixed = updated.replace(padding_symbol_lengths=(2, 2))
+    assert (2, 2) == fixed.padding_symbol_lengths
+    assert fixed.adaptive_padding is None
+    assert 24 == settings.replace(adaptive_padding=24, padding_symbol_lengths=(2, 2)).adaptive_padding
+
+    # options are applied in the same order whatever the order they are given in
+    for options in (
+        {"word_lengths": (3, 5), "available_word_lengths": [3, 4, 5]},
+        {"available_word_lengths": [3, 4, 5], "word_lengths": (3, 5)},
+    ):
+        assert "from 3 to 5 chars" in str(Settings(**options))
+
+    readable = settings.replace(readable=True)
+    assert readable.readable
+    assert not settings.readable
+
+
+def test_settings_errors():
+    with pytest.raises(XkpasswdError, match="only positive integer"):
+        Settings(words_count=0)
+
+    with pytest.raises(ValueError, match="min word length must be 3 or higher"):
+        Settings().replace(word_lengths=(2, 6))
+
+    with pytest.raises(XkpasswdError):
+        Settings(available_word_lengths=[])
+
+    with pytest.raises(TypeError, match="unexpected settings option 'word_count'"):
+        Settings(word_count=4)
+
+    with pytest.raises(TypeError):
+        Settings(separators=1)
+
+
+def test_word_transforms():
+    flags = WordTransform.TITLECASE | WordTransform.INVERSED_TITLECASE
+    assert 0b00001010 == flags
+    assert 0b00001011 == WordTransform.LOWERCASE | flags
+
+    settings = Settings(word_transforms=WordTransform.ALTERCASE_LOWER_FIRST)
+    assert 0b01000000 == settings.word_transforms
+    assert WordTransform.ALTERCASE_LOWER_FIRST == WordTransform.ALTERCASE_LOWER_FIRST
+
+    with pytest.raises(XkpasswdError):
+        Settings(word_transforms=0)
@@ -0,0 +1,21 @@
+"""XKCD-style password generator, with Python bindings to the Rust crate."""
+
+from ._xkpasswd import (
+    Entropy,
+    Language,
+    Preset,
+    Settings,
+    WordTransform,
+    Xkpasswd,
+    XkpasswdError,
+)
+
+__all__ = [
+    "Entropy",
+    "Language",
+    "Preset",
+    "Settings",
+    "WordTransform",
+    "Xkpasswd",
+    "XkpasswdError",
+]
@@ -0,0 +1,95 @@
+from typing import Optional, Sequence, Tuple, Union
+
+class XkpasswdError(ValueError): ...
+
+class Preset:
+    DEFAULT: Preset
+    APPLE_ID: Preset
+    WINDOWS_NTLM_V1: Preset
+    SECURITY_QUESTIONS: Preset
+    WEB16: Preset
+    WEB32: Preset
+    WIFI: Preset
+    XKCD: Preset
+
+class Language:
+    ENGLISH: Language
+    FRENCH: Language
+    GERMAN: Language
+    PORTUGUESE: Language
+    SPANISH: Language
+
+class WordTransform:
+    LOWERCASE: WordTransform
+    TITLECASE: WordTransform
+    UPPERCASE: WordTransform
+    INVERSED_TITLECASE: WordTransform
+    ALTERCASE_LOWER_FIRST: WordTransform
+    ALTERCASE_UPPER_FIRST: WordTransform
+    def __index__(self) -> int: ...
+    def __or__(self, other: Union[int, WordTransform]) -> int: ...
+    def __ror__(self, other: Union[int, WordTransform]) -> int: ...
+
+class Entropy:
+    blind_min: int
+    blind_max: int
+    seen: int
+    guess_time: Tuple[int, int, int]
+    """`(years, months, days)` for computers to break passwords."""
+    description: str
+
+class Settings:
+    def __init__(
+        self,
+        preset: Optional[Preset] = None,
+        *,
+        words_count: int = ...,
+        word_lengths: Tuple[Optional[int], Optional[int]] = ...,
+        available_word_lengths: Sequence[int] = ...,
+        word_transforms: Union[int, WordTransform] = ...,
+        separators: str = ...,
+        padding_digits: Tuple[Optional[int], Optional[int]] = ...,
+        padding_symbols: str = ...,
+        padding_symbol_lengths: Tuple[Optional[int], Optional[int]] = ...,
+        adaptive_padding: Optional[int] = ...,
+        excluded_words: Sequence[str] = ...,
+        included_words: Sequence[str] = ...,
+        readable: bool = ...,
+    ) -> None: ...
+    def replace(
+        self,
+        *,
+        words_count: int = ...,
+        word_lengths: Tuple[Optional[int], Optional[int]] = ...,
+        available_word_lengths: Sequence[int] = ...,
+        word_transforms: Union[int, WordTransform] = ...,
+        separators: str = ...,
+        padding_digits: Tuple[Optional[int], Optional[int]] = ...,
+        padding_symbols: str = ...,
+        padding_symbol_lengths: Tuple[Optional[int], Optional[int]] = ...,
+        adaptive_padding: Optional[int] = ...,
+        excluded_words: Sequence[str] = ...,
+        included_words: Sequence[str] = ...,
+        readable: bool = ...,
+    ) -> Settings: ...
+    @property
+    def words_count(self) -> int: ...
+    @property
+    def word_transforms(self) -> int: ...
+    @property
+    def separators(self) -> str: ...
+    @property
+    def padding_digits(self) -> Tuple[int, int]: ...
+    @property
+    def padding_symbols(self) -> str: ...
+    @property
+    def padding_symbol_lengths(self) -> Tuple[int, int]: ...
+    @property
+    def adaptive_padding(self) -> Optional[int]: ...
+    @property
+    def readable(self) -> bool: ...
+
+class Xkpasswd:
+    def __init__(self, *languages: Language) -> None: ...
+    def word_lengths(self) -> list[int]: ...
+    def gen_pass(self, settings: Optional[Settings] = None) -> Tuple[str, Entropy]: ...
@@ -1,6 +1,7 @@
 #!/usr/bin/env python3.9
 
 from typing import List
+from word_filters import read_deny_list, remove_accents
 import re
 
 LANGUAGES = ["de", "en", "es", "fr", "pt"]
@@ -9,7 +10,7 @@ IN_FILE_SUFFIX = "50k.txt"
 OUT_FILE_PREFIX = "raw_dict"
 OUT_FILE_SUFFIX = ".txt"
 
-MIN_WORD_LENGTH = 4
+MIN_WORD_LENGTH = 3
 WHITELISTED_PATTERN_EN = r"[^a-zA-Z]"
 WHITELISTED_PATTERN_OTHERS = (
     r"[^a-zA-Zà-úÀ-Úä-üÄ-Üàáâãäåèéêëìíîïòóôõöùúûüßæçñœ]"  # noqa: E501
@@ -38,7 +39,12 @@ def read_file(lang: str) -> List[str]:
         lines = [line.split(" ")[0] for line in pre_filtered]
         print(f"Read {len(lines)} lines")
 
-        words = list(filter(_post_filter, lines[0:15000]))
+        denied_words = read_deny_list(lang)
+        words = [
+            word
+            for word in filter(_post_filter, lines[0:15000])
+            if remove_accents(word) not in denied_words
+        ]
         words.sort()
         return words
 
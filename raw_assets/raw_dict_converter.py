@@ -1,11 +1,11 @@
 #!/usr/bin/env python3.9
 
 from typing import Dict, Iterable, Set
-import re
+from word_filters import read_deny_list, remove_accents
 
 LANGUAGES = ["de", "en", "es", "fr", "pt"]
-MIN_WORD_LENGTH = 4
-MAX_WORD_LENGTH = 10
+MIN_WORD_LENGTH = 3
+MAX_WORD_LENGTH = 12
 MAX_WORDS_PER_LENGTH = 1500
 
 
@@ -18,33 +18,18 @@ def read_dict_file(lang: str) -> Iterable[str]:
         return filter(lambda line: not line.startswith("#"), lines)
 
 
-def remove_accents(original: str) -> str:
-    word = original.strip().lower()
-
-    # Accents
-    word = re.sub(r"[àáâãäå]", "a", word)
-    word = re.sub(r"[èéêë]", "e", word)
-    word = re.sub(r"[ìíîï]", "i", word)
-    word = re.sub(r"[òóôõö]", "o", word)
-    word = re.sub(r"[ùúûü]", "u", word)
-
-    # Special characters
-    word = re.sub(r"[ß]", "ss", word)
-    word = re.sub(r"[æ]", "ae", word)
-    word = re.sub(r"[ç]", "c", word)
-    word = re.sub(r"[ñ]", "n", word)
-    word = re.sub(r"[œ]", "ce", word)
-
-    return word
-
-
-def group_words_by_length(all_words: Iterable[str]) -> Dict[int, Set[str]]:
+def group_words_by_length(
+    all_words: Iterable[str], denied_words: Set[str]
+) -> Dict[int, Set[str]]:
     grouped: Dict[int, Set[str]] = dict()
 
     for line in all_words:
         word = remove_accents(line)
         length = len(word)
 
+        if word in denied_words:
+            continue
+
         if length < MIN_WORD_LENGTH or length > MAX_WORD_LENGTH:
             continue
 
@@ -64,7 +49,7 @@ if __name__ == "__main__":
         print(f"\nReading raw file for '{lang}'")
 
         all_words = read_dict_file(lang)
-        grouped_words = group_words_by_length(all_words)
+        grouped_words = group_words_by_length(all_words, read_deny_list(lang))
 
         with open(f"dict_{lang}.txt", "w") as file:
             for length, words_set in grouped_words.items():
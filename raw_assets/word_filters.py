@@ -0,0 +1,38 @@
+from typing import Set
+import re
+
+DENY_LIST_DIR = "../src/assets"
+
+
+def remove_accents(original: str) -> str:
+    word = original.strip().lower()
+
+    # Accents
+    word = re.sub(r"[àáâãäå]", "a", word)
+    word = re.sub(r"[èéêë]", "e", word)
+    word = re.sub(r"[ìíîï]", "i", word)
+    word = re.sub(r"[òóôõö]", "o", word)
+    word = re.sub(r"[ùúûü]", "u", word)
+
+    # Special characters
+    word = re.sub(r"[ß]", "ss", word)
+    word = re.sub(r"[æ]", "ae", word)
+    word = re.sub(r"[ç]", "c", word)
+    word = re.sub(r"[ñ]", "n", word)
+    word = re.sub(r"[œ]", "ce", word)
+
+    return word
+
+
+def read_deny_list(lang: str) -> Set[str]:
+    file_name = f"{DENY_LIST_DIR}/deny_{lang}.txt"
+
+    with open(file_name, "r") as file:
+        lines = [line.strip() for line in file.readlines()]
+        words = {
+            remove_accents(line)
+            for line in lines
+            if line and not line.startswith("#")
+        }
+        print(f"Read {len(words)} denied words from {file_name}")
+        return words
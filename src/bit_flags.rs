@@ -1,10 +1,12 @@
-use std::fmt;
-use std::ops::*;
+use crate::compat::*;
+use core::fmt;
+use core::ops::*;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 pub type FieldSize = u8;
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WordTransform {
@@ -98,6 +100,34 @@ impl BitFlags for FieldSize {
 }
 
 impl WordTransform {
+    pub const ALL: [WordTransform; 6] = [
+        WordTransform::Lowercase,
+        WordTransform::Titlecase,
+        WordTransform::Uppercase,
+        WordTransform::InversedTitlecase,
+        WordTransform::AltercaseLowerFirst,
+        WordTransform::AltercaseUpperFirst,
+    ];
+
+    /// Name used in the CLI & config files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lowercase => "lowercase",
+            Self::Titlecase => "titlecase",
+            Self::Uppercase => "uppercase",
+            Self::InversedTitlecase => "inversed-titlecase",
+            Self::AltercaseLowerFirst => "altercase-lower-first",
+            Self::AltercaseUpperFirst => "altercase-upper-first",
+        }
+    }
+
+    /// Case-insensitive lookup of [`WordTransform::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|transform| transform.name().eq_ignore_ascii_case(name))
+    }
+
     pub fn to_strings(transforms: &[WordTransform]) -> Vec<String> {
         transforms
             .iter()
@@ -132,6 +162,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_names() {
+        for transform in WordTransform::ALL {
+            assert_eq!(Some(transform), WordTransform::from_name(transform.name()));
+        }
+
+        assert_eq!(
+            Some(WordTransform::InversedTitlecase),
+            WordTransform::from_name("Inversed-TitleCase")
+        );
+        assert_eq!(None, WordTransform::from_name("camelcase"));
+    }
+
     #[test]
     fn test_from_flag() {
         assert_eq!(0b00000001, FieldSize::from_flag(WordTransform::Lowercase));
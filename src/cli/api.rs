@@ -0,0 +1,468 @@
+//! JSON API shared by `xkpasswd serve` & `xkpasswd --stdio`.
+//!
+//! Settings of requests use the same fields as config files, validated the same way as
+//! command line options. Requests without settings use the settings of the command line.
+
+use super::*;
+use crate::settings::Settings;
+
+use serde_json::{json, Map, Value};
+
+const MAX_COUNT: u64 = 100;
+const SETTINGS_PARAMS: [&str; 3] = ["settings", "preset", "language"];
+
+// config file fields accepted in requests, file paths & languages excluded
+const SETTINGS_FIELDS: [(&str, FieldKind); 17] = [
+    ("preset", FieldKind::Text),
+    ("words_count", FieldKind::Byte),
+    ("word_min", FieldKind::Byte),
+    ("word_max", FieldKind::Byte),
+    ("transforms", FieldKind::TextList),
+    ("separators", FieldKind::Text),
+    ("digits_before", FieldKind::Byte),
+    ("digits_after", FieldKind::Byte),
+    ("symbols", FieldKind::Text),
+    ("symbols_before", FieldKind::Byte),
+    ("symbols_after", FieldKind::Byte),
+    ("padding", FieldKind::Text),
+    ("adaptive_length", FieldKind::Length),
+    ("exclude_words", FieldKind::TextList),
+    ("include_words", FieldKind::TextList),
+    ("readable", FieldKind::Bool),
+    ("keyboard", FieldKind::Text),
+];
+
+#[derive(Clone, Copy, Debug)]
+enum FieldKind {
+    Bool,
+    Byte,
+    Length,
+    Text,
+    TextList,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiError {
+    /// Malformed request, or settings rejected by the builder.
+    InvalidParams(String),
+    /// Valid settings, which can't generate any password with the dictionaries.
+    Failed(String),
+}
+
+impl ApiError {
+    pub fn message(&self) -> &str {
+        match self {
+            ApiError::InvalidParams(message) | ApiError::Failed(message) => message,
+        }
+    }
+}
+
+pub struct PassService {
+    settings: Settings,
+    // layout of the first language, for generating without any keyboard
+    keyboard: Keyboard,
+    pass_generator: Xkpasswd,
+    // preloaded once for all requests
+    lang_generators: Vec<(Language, Xkpasswd)>,
+}
+
+impl PassService {
+    pub fn new(cli: &Cli) -> Result<Self, String> {
+        let pass_generator = Xkpasswd::for_languages(&cli.languages());
+        let settings = cli.build_settings(&pass_generator.source().word_lengths())?;
+
+        let lang_generators = Language::ALL
+            .iter()
+            .filter(|language| language.is_bundled())
+            .map(|&language| (language, Xkpasswd::for_language(language)))
+            .collect();
+
+        Ok(PassService {
+            settings,
+            keyboard: cli.keyboard(),
+            pass_generator,
+            lang_generators,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Passwords with their entropy, from optional `settings`, `preset`, `language` & `count`.
+    pub fn generate(&self, params: &Value) -> Result<Value, ApiError> {
+        let params = params_object(params, &[&SETTINGS_PARAMS[..], &["count"]].concat())?;
+
+        let count = match params.get("count") {
+            None => 1,
+            Some(count) => count
+                .as_u64()
+                .filter(|count| (1..=MAX_COUNT).contains(count))
+                .ok_or_else(|| {
+                    invalid_params(format!("count must be an integer from 1 to {}", MAX_COUNT))
+                })?,
+        };
+
+        let (pass_generator, settings) = self.generation_settings(params)?;
+
+        let passwords = (0..count)
+            .map(|_| {
+                let (passwd, entropy) = pass_generator.gen_pass(&settings)?;
+                Ok(json!({
+                    "passwd": passwd.as_str(),
+                    "entropy": entropy_value(&entropy),
+                }))
+            })
+            .collect::<Result<Vec<Value>, String>>()
+            .map_err(|err| ApiError::Failed(format!("unable to generate password: {}", err)))?;
+
+        Ok(json!({ "passwords": passwords }))
+    }
+
+    /// Entropy of the requested settings, without generating any password.
+    pub fn entropy(&self, params: &Value) -> Result<Value, ApiError> {
+        let params = params_object(params, &SETTINGS_PARAMS)?;
+        let (pass_generator, settings) = self.generation_settings(params)?;
+
+        pass_generator
+            .calc_entropy(&settings)
+            .map(|entropy| entropy_value(&entropy))
+            .map_err(|err| ApiError::Failed(format!("unable to calculate entropy: {}", err)))
+    }
+
+    /// Human readable summary of the settings passwords are generated with,
+    /// along with the code of the requested settings.
+    pub fn describe(&self, params: &Value) -> Result<Value, ApiError> {
+        let params = params_object(params, &SETTINGS_PARAMS)?;
+        let (_, settings, keyboard) = self.request_settings(params)?;
+
+        Ok(json!({
+            "description": settings.with_language_keyboard(keyboard).to_string(),
+            "code": settings.to_code(),
+        }))
+    }
+
+    pub fn presets(&self) -> Value {
+        Value::Array(
+            Preset::ALL
+                .iter()
+                .map(|preset| {
+                    let description = preset
+                        .to_possible_value()
+                        .and_then(|value| value.get_help().map(ToString::to_string))
+                        .unwrap_or_default();
+
+                    json!({
+                        "name": preset.name(),
+                        "description": description,
+                        "code": Settings::from_preset(*preset).to_code(),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn languages(&self) -> Value {
+        Value::Array(
+            self.lang_generators
+                .iter()
+                .map(|(language, _)| {
+                    json!({
+                        "code": possible_value_name(language),
+                        "name": format!("{:?}", language),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    // settings along with the layout of the requested language, if any
+    fn request_settings(
+        &self,
+        params: &Map<String, Value>,
+    ) -> Result<(&Xkpasswd, Settings, Keyboard), ApiError> {
+        let (keyboard, pass_generator) = match params.get("language") {
+            None => (self.keyboard, &self.pass_generator),
+            Some(language) => {
+                let (language, pass_generator) = self.lang_generator(language)?;
+                (Keyboard::for_language(language), pass_generator)
+            }
+        };
+
+        let settings = match (params.get("settings"), params.get("preset")) {
+            (None, None) => self.settings.clone(),
+            (settings, preset) => fresh_settings(settings, preset, pass_generator)?,
+        };
+
+        Ok((pass_generator, settings, keyboard))
+    }
+
+    // same as `Cli::generation_settings`, preset symbols restricted to the layout of the language
+    fn generation_settings(
+        &self,
+        params: &Map<String, Value>,
+    ) -> Result<(&Xkpasswd, Settings), ApiError> {
+        let (pass_generator, settings, keyboard) = self.request_settings(params)?;
+        Ok((pass_generator, settings.with_language_keyboard(keyboard)))
+    }
+
+    fn lang_generator(&self, language: &Value) -> Result<(Language, &Xkpasswd), ApiError> {
+        let name = language
+            .as_str()
+            .ok_or_else(|| invalid_params("language must be a string"))?;
+        let language = Language::from_str(name, true)
+            .map_err(|_| invalid_params(format!("unknown language '{}'", name)))?;
+
+        self.lang_generators
+            .iter()
+            .find(|(item, _)| *item == language)
+            .map(|(language, pass_generator)| (*language, pass_generator))
+            .ok_or_else(|| invalid_params(format!("{:?} dictionary is not bundled", language)))
+    }
+}
+
+fn params_object<'a>(
+    params: &'a Value,
+    fields: &[&str],
+) -> Result<&'a Map<String, Value>, ApiError> {
+    let params = params
+        .as_object()
+        .ok_or_else(|| invalid_params("expect a JSON object"))?;
+
+    match params
+        .keys()
+        .find(|field| !fields.contains(&field.as_str()))
+    {
+        Some(field) => Err(invalid_params(format!("unknown field '{}'", field))),
+        None => Ok(params),
+    }
+}
+
+// fresh settings, so that requests don't depend on the command line options
+fn fresh_settings(
+    settings: Option<&Value>,
+    preset: Option<&Value>,
+    pass_generator: &Xkpasswd,
+) -> Result<Settings, ApiError> {
+    let mut config = match settings {
+        Some(settings) => settings_config(settings)?,
+        None => toml::Table::new(),
+    };
+
+    if let Some(preset) = preset {
+        let preset = preset
+            .as_str()
+            .ok_or_else(|| invalid_params("preset must be a string"))?;
+        config.insert(
+            "preset".to_string(),
+            toml::Value::String(preset.to_string()),
+        );
+    }
+
+    let mut cli = Cli::parse_from(["xkpasswd"]);
+    cli.parse_config(&toml::Value::Table(config))
+        .map_err(|err| match err {
+            ConfigParseError::InvalidConfig(field, err) => {
+                invalid_params(format!("invalid settings at '{}': {}", field, err))
+            }
+            ConfigParseError::InvalidFile(err) => {
+                invalid_params(format!("invalid settings: {}", err))
+            }
+            ConfigParseError::Ignore => invalid_params("invalid settings"),
+        })?;
+
+    cli.build_settings(&pass_generator.source().word_lengths())
+        .map_err(|err| invalid_params(format!("invalid settings: {}", err)))
+}
+
+fn settings_config(settings: &Value) -> Result<toml::Table, ApiError> {
+    let settings = settings
+        .as_object()
+        .ok_or_else(|| invalid_params("settings must be a JSON object"))?;
+
+    settings
+        .iter()
+        .map(|(field, value)| {
+            let (_, kind) = SETTINGS_FIELDS
+                .iter()
+                .find(|(name, _)| name == field)
+                .ok_or_else(|| invalid_params(format!("unknown settings field '{}'", field)))?;
+
+            let config_value = match (kind, value) {
+                (FieldKind::Bool, Value::Bool(value)) => Some(toml::Value::Boolean(*value)),
+                (FieldKind::Byte, Value::Number(number)) => number
+                    .as_u64()
+                    .filter(|&number| number <= u8::MAX as u64)
+                    .map(|number| toml::Value::Integer(number as i64)),
+                (FieldKind::Length, Value::Number(number)) => number
+                    .as_u64()
+                    .filter(|&number| number <= PaddingStrategy::MAX_ADAPTIVE_LENGTH as u64)
+                    .map(|number| toml::Value::Integer(number as i64)),
+                (FieldKind::Text, Value::String(text)) => Some(toml::Value::String(text.clone())),
+                (FieldKind::TextList, Value::Array(values)) => values
+                    .iter()
+                    .map(|value| {
+                        value
+                            .as_str()
+                            .map(|text| toml::Value::String(text.to_string()))
+                    })
+                    .collect::<Option<Vec<toml::Value>>>()
+                    .map(toml::Value::Array),
+                _ => None,
+            };
+
+            let expected = match kind {
+                FieldKind::Bool => "a boolean",
+                FieldKind::Byte => "an integer from 0 to 255",
+                FieldKind::Length => "an integer from 0 to 255",
+                FieldKind::Text => "a string",
+                FieldKind::TextList => "an array of strings",
+            };
+
+            config_value
+                .map(|value| (field.clone(), value))
+                .ok_or_else(|| {
+                    invalid_params(format!("settings field '{}' must be {}", field, expected))
+                })
+        })
+        .collect()
+}
+
+fn entropy_value(entropy: &Entropy) -> Value {
+    let guess_time = &entropy.guess_time;
+
+    json!({
+        "blind_min": entropy.blind_min,
+        "blind_max": entropy.blind_max,
+        "seen": entropy.seen,
+        "guess_time": {
+            "years": guess_time.years,
+            "months": guess_time.months,
+            "days": guess_time.days,
+        },
+        "description": entropy.to_string(),
+    })
+}
+
+fn possible_value_name<T: ValueEnum>(value: &T) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+fn invalid_params(message: impl Into<String>) -> ApiError {
+    ApiError::InvalidParams(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(args: &[&str]) -> PassService {
+        PassService::new(&Cli::parse_from([&["xkpasswd"], args].concat())).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let service = service(&[]);
+
+        let value = service
+            .generate(&json!({"settings": {"padding": "adaptive", "adaptive_length": 255}}))
+            .unwrap();
+        assert_eq!(255, value["passwords"][0]["passwd"].as_str().unwrap().len());
+
+        // passwords are allocated to the adaptive length
+        for length in [256, 100000000000u64] {
+            assert_eq!(
+                Err(invalid_params(
+                    "settings field 'adaptive_length' must be an integer from 0 to 255"
+                )),
+                service.generate(
+                    &json!({"settings": {"padding": "adaptive", "adaptive_length": length}})
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_entropy() {
+        let service = service(&["-P", "xkcd"]);
+
+        let value = service.entropy(&json!({})).unwrap();
+        let (_, expected) = Xkpasswd::default().gen_pass(service.settings()).unwrap();
+        assert_eq!(entropy_value(&expected), value);
+
+        let value = service
+            .entropy(&json!({"settings": {"words_count": 8}, "language": "de"}))
+            .unwrap();
+        assert!(value["seen"].as_u64().unwrap() > expected.seen as u64);
+
+        // symbols of presets are restricted to the layout of the language by default
+        let value = service
+            .entropy(&json!({"preset": "wifi", "language": "fr"}))
+            .unwrap();
+        let settings = Settings::from_preset(Preset::Wifi).with_language_keyboard(Keyboard::Fr);
+        let expected = Xkpasswd::for_language(Language::French)
+            .calc_entropy(&settings)
+            .unwrap();
+        assert_eq!(entropy_value(&expected), value);
+
+        assert_eq!(
+            Err(ApiError::Failed(
+                "unable to calculate entropy: only 0 word(s) left after filtering, 3 required"
+                    .to_string()
+            )),
+            service.entropy(&json!({"settings": {"include_words": ["foo"]}}))
+        );
+        assert_eq!(
+            Err(invalid_params("unknown field 'count'")),
+            service.entropy(&json!({"count": 2}))
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let service = service(&[]);
+
+        let value = service.describe(&json!({"preset": "wifi"})).unwrap();
+        let settings = Settings::from_preset(Preset::Wifi);
+        assert_eq!(settings.to_string(), value["description"]);
+        assert_eq!(settings.to_code(), value["code"]);
+
+        // the layout of the language only applies to generated passwords, not codes
+        let value = service
+            .describe(&json!({"preset": "wifi", "language": "fr"}))
+            .unwrap();
+        assert_eq!(settings.to_code(), value["code"]);
+        assert_eq!(
+            settings.with_language_keyboard(Keyboard::Fr).to_string(),
+            value["description"]
+        );
+        assert_ne!(settings.to_string(), value["description"]);
+        let value = service
+            .describe(&json!({"settings": {"keyboard": "ios"}, "language": "fr"}))
+            .unwrap();
+        assert!(value["code"].as_str().unwrap().ends_with("-kios"));
+
+        let value = service.describe(&json!({})).unwrap();
+        assert_eq!(service.settings().to_string(), value["description"]);
+
+        let value = service
+            .describe(&json!({"settings": {"readable": true}}))
+            .unwrap();
+        assert!(value["code"].as_str().unwrap().ends_with("-r"));
+        assert_eq!(
+            Err(invalid_params(
+                "settings field 'readable' must be a boolean"
+            )),
+            service.describe(&json!({"settings": {"readable": 1}}))
+        );
+
+        assert_eq!(
+            Err(invalid_params("expect a JSON object")),
+            service.describe(&json!([]))
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Copy passwords to the clipboard, cleared after a timeout.
+//!
+//! Local providers (Wayland, X11, macOS) are tried first,
+//! OSC 52 escape sequences are used as a fallback, e.g. in SSH sessions.
+
+use std::env;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+pub const DEFAULT_CLIPBOARD_TIMEOUT: u64 = 45;
+
+/// How the password was put on the clipboard.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Copied {
+    /// With a local provider, e.g. `xclip`.
+    Local(&'static str),
+    /// With an OSC 52 escape sequence, which terminals may ignore without telling.
+    Osc52,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct LocalProvider {
+    name: &'static str,
+    copy: &'static [&'static str],
+    paste: &'static [&'static str],
+    // command clearing the clipboard, copying an empty string if `None`
+    clear: Option<&'static [&'static str]>,
+}
+
+const WL_CLIPBOARD: LocalProvider = LocalProvider {
+    name: "wl-copy",
+    copy: &["wl-copy"],
+    paste: &["wl-paste", "--no-newline"],
+    clear: Some(&["wl-copy", "--clear"]),
+};
+
+const XCLIP: LocalProvider = LocalProvider {
+    name: "xclip",
+    copy: &["xclip", "-selection", "clipboard"],
+    paste: &["xclip", "-selection", "clipboard", "-o"],
+    clear: None,
+};
+
+const XSEL: LocalProvider = LocalProvider {
+    name: "xsel",
+    copy: &["xsel", "--clipboard", "--input"],
+    paste: &["xsel", "--clipboard", "--output"],
+    clear: Some(&["xsel", "--clipboard", "--delete"]),
+};
+
+const PBCOPY: LocalProvider = LocalProvider {
+    name: "pbcopy",
+    copy: &["pbcopy"],
+    paste: &["pbpaste"],
+    clear: None,
+};
+
+/// Put a password on the clipboard, cleared after `timeout` seconds unless 0.
+pub fn copy(passwd: &str, timeout: u64) -> Result<Copied, String> {
+    for provider in local_providers(
+        env::var_os("WAYLAND_DISPLAY").is_some(),
+        env::var_os("DISPLAY").is_some(),
+        cfg!(target_os = "macos"),
+    ) {
+        match provider.copy(passwd) {
+            Ok(_) => {
+                if timeout > 0 {
+                    provider.schedule_clear(passwd, timeout)?;
+                }
+                return Ok(Copied::Local(provider.name));
+            }
+            Err(err) => log::debug!("clipboard provider {} unavailable: {}", provider.name, err),
+        }
+    }
+
+    copy_osc52(passwd, timeout)
+        .map(|_| Copied::Osc52)
+        .map_err(|err| format!("no clipboard provider available: {}", err))
+}
+
+fn local_providers(wayland: bool, x11: bool, macos: bool) -> Vec<&'static LocalProvider> {
+    let mut providers = vec![];
+
+    if macos {
+        providers.push(&PBCOPY);
+    }
+
+    if wayland {
+        providers.push(&WL_CLIPBOARD);
+    }
+
+    if x11 {
+        providers.extend([&XCLIP, &XSEL]);
+    }
+
+    providers
+}
+
+impl LocalProvider {
+    fn copy(&self, passwd: &str) -> io::Result<()> {
+        // providers may keep running to serve the selection, so don't wait on stdout
+        let mut child = Command::new(self.copy[0])
+            .args(&self.copy[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(passwd.as_bytes())?;
+
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(io::Error::other(format!(
+                "{} exited with {}",
+                self.name, status
+            )));
+        }
+
+        Ok(())
+    }
+
+    // the password is given on stdin, so that it's not in the environment or arguments
+    // of the background shell, readable by other processes
+    fn schedule_clear(&self, passwd: &str, timeout: u64) -> Result<(), String> {
+        let mut child = Command::new("sh")
+            .args(["-c", &self.clear_script(timeout)])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| format!("unable to schedule clipboard clearing: {}", err))?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(passwd.as_bytes())
+            .map_err(|err| format!("unable to schedule clipboard clearing: {}", err))
+    }
+
+    // shell script clearing the clipboard in the background, only if still holding the password
+    fn clear_script(&self, timeout: u64) -> String {
+        let clear = match self.clear {
+            Some(clear) => clear.join(" "),
+            None => format!("printf '' | {}", self.copy.join(" ")),
+        };
+
+        format!(
+            r#"secret=$(cat); sleep {timeout}; [ "$({paste})" = "$secret" ] && {clear}"#,
+            timeout = timeout,
+            paste = self.paste.join(" "),
+            clear = clear,
+        )
+    }
+}
+
+fn copy_osc52(passwd: &str, timeout: u64) -> io::Result<()> {
+    let mut tty = OpenOptions::new().write(true).open("/dev/tty")?;
+    write!(tty, "{}", osc52_sequence(Some(passwd)))?;
+    tty.flush()?;
+
+    if timeout > 0 {
+        // the terminal can't be read back, so clearing is unconditional
+        Command::new("sh")
+            .args([
+                "-c",
+                &format!(r"sleep {}; printf '\033]52;c;!\007' > /dev/tty", timeout),
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+    }
+
+    Ok(())
+}
+
+/// Escape sequence setting the clipboard, or clearing it with an invalid payload.
+fn osc52_sequence(text: Option<&str>) -> String {
+    let payload = match text {
+        Some(text) => base64_encode(text.as_bytes()),
+        None => "!".to_string(),
+    };
+
+    format!("\x1b]52;c;{}\x07", payload)
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for position in 0..4 {
+            if position <= chunk.len() {
+                let index = (triple >> (18 - 6 * position)) & 0x3f;
+                encoded.push(ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        let table = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+            ("hôtel-42", "aMO0dGVsLTQy"),
+        ];
+
+        for (data, expected) in table {
+            assert_eq!(expected, base64_encode(data.as_bytes()), "{}", data);
+        }
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!("\x1b]52;c;Zm9vYmFy\x07", osc52_sequence(Some("foobar")));
+        assert_eq!("\x1b]52;c;!\x07", osc52_sequence(None));
+    }
+
+    #[test]
+    fn test_local_providers() {
+        let table = [
+            ((false, false, false), vec![]),
+            ((true, false, false), vec![&WL_CLIPBOARD]),
+            ((false, true, false), vec![&XCLIP, &XSEL]),
+            ((true, true, false), vec![&WL_CLIPBOARD, &XCLIP, &XSEL]),
+            ((false, false, true), vec![&PBCOPY]),
+        ];
+
+        for ((wayland, x11, macos), expected) in table {
+            assert_eq!(expected, local_providers(wayland, x11, macos));
+        }
+    }
+
+    #[test]
+    fn test_clear_script() {
+        assert_eq!(
+            r#"secret=$(cat); sleep 45; [ "$(xclip -selection clipboard -o)" = "$secret" ] && printf '' | xclip -selection clipboard"#,
+            XCLIP.clear_script(45)
+        );
+        assert_eq!(
+            r#"secret=$(cat); sleep 5; [ "$(wl-paste --no-newline)" = "$secret" ] && wl-copy --clear"#,
+            WL_CLIPBOARD.clear_script(5)
+        );
+        assert_eq!(
+            r#"secret=$(cat); sleep 5; [ "$(xsel --clipboard --output)" = "$secret" ] && xsel --clipboard --delete"#,
+            XSEL.clear_script(5)
+        );
+    }
+}
@@ -1,14 +1,44 @@
+#[cfg(any(feature = "server", feature = "stdio"))]
+mod api;
+mod clipboard;
+#[cfg(feature = "stdio")]
+mod rpc;
+#[cfg(feature = "server")]
+mod server;
 #[cfg(test)]
 mod tests;
 mod toml_conf;
+#[cfg(feature = "tui")]
+mod tui;
 
 use crate::bit_flags::*;
+use crate::derive::DEFAULT_COUNTER;
+use crate::hash::HashScheme;
 use crate::prelude::*;
+use crate::qr::{WifiQr, WifiSecurity};
+use crate::secret::Passwd;
+use crate::settings::Settings;
+use crate::spell::Speller;
+use clipboard::{Copied, DEFAULT_CLIPBOARD_TIMEOUT};
+#[cfg(feature = "stdio")]
+pub use rpc::RpcSession;
+#[cfg(feature = "server")]
+pub use server::PassServer;
+#[cfg(feature = "server")]
+use server::DEFAULT_PORT;
 use toml_conf::*;
+#[cfg(feature = "tui")]
+pub use tui::Tui;
 
 use clap::builder::PossibleValue;
 use clap::error::ErrorKind;
-use clap::{ArgAction, CommandFactory, Parser, ValueEnum};
+use clap::{ArgAction, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+const MASTER_SECRET_ENV: &str = "XKPASSWD_MASTER_SECRET";
 
 #[derive(Clone, Copy, Debug)]
 pub enum CliPadding {
@@ -16,7 +46,7 @@ pub enum CliPadding {
     Adaptive,
 }
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(version)]
 pub struct Cli {
     #[arg(
@@ -88,73 +118,538 @@ pub struct Cli {
     )]
     adaptive_length: Option<usize>,
 
+    #[arg(
+        long = "exclude",
+        value_delimiter = ',',
+        help = "Words to be excluded from the dictionary, comma-separated or with multiple occurrences"
+    )]
+    excluded_words: Option<Vec<String>>,
+
+    #[arg(
+        long = "exclude-file",
+        help = "Path to a file of words to be excluded from the dictionary, one per line"
+    )]
+    exclude_file: Option<String>,
+
+    #[arg(
+        long = "include",
+        value_delimiter = ',',
+        help = "Only use these words from the dictionary, comma-separated or with multiple occurrences"
+    )]
+    included_words: Option<Vec<String>>,
+
+    #[arg(
+        long = "include-file",
+        help = "Path to a file of the only words to be used from the dictionary, one per line"
+    )]
+    include_file: Option<String>,
+
+    #[arg(
+        long = "readable",
+        help = "Leave out visually ambiguous symbols, digits & words, e.g. |, 0, 1 or corn"
+    )]
+    readable: bool,
+
+    #[arg(
+        long = "keyboard",
+        value_enum,
+        help = "Only use separators & symbols typable on this keyboard, defaults to the layout of the first language"
+    )]
+    keyboard: Option<Keyboard>,
+
+    #[arg(
+        long = "dice",
+        num_args = 0..,
+        value_name = "ROLLS",
+        help = "Pick words with physical dice rolls, e.g. --dice 43126 22514, prompted for if none given"
+    )]
+    dice_rolls: Option<Vec<String>>,
+
+    #[arg(
+        long = "dice-table",
+        help = "Print the dice rolls lookup table of words for the selected languages & word lengths"
+    )]
+    dice_table: bool,
+
     #[arg(short = 'P', long = "preset", value_enum)]
     preset: Option<Preset>,
 
+    #[arg(
+        long = "recipe",
+        value_name = "CODE",
+        conflicts_with = "preset",
+        help = "Base settings from a shared code, e.g. xk1:w4-l4.8-t5-s~2D, see `config dump --code`"
+    )]
+    recipe: Option<String>,
+
     #[arg(short = 'v', long = "verbose", help = "Verbosity: 1 = info, 2+ = debug", action = ArgAction::Count)]
     verbosity: u8,
 
-    #[arg(short = 'z', long = "lang", help = "Language of generated words")]
-    language: Option<Language>,
+    #[arg(
+        short = 'z',
+        long = "lang",
+        value_enum,
+        help = "Language of generated words, can be combined with multiple occurrences"
+    )]
+    languages: Option<Vec<Language>>,
+
+    #[arg(
+        short = 'i',
+        long = "interactive",
+        help = "Explore settings & regenerate passwords interactively, with changes saved to the config file"
+    )]
+    interactive: bool,
+
+    #[arg(
+        long = "stdio",
+        conflicts_with = "interactive",
+        help = "Answer line-delimited JSON-RPC requests on stdin, methods: generate, entropy, describe, presets & languages"
+    )]
+    stdio: bool,
+
+    #[arg(
+        long = "clipboard",
+        help = "Copy the password to the clipboard instead of printing it"
+    )]
+    clipboard: bool,
+
+    #[arg(
+        long = "clipboard-timeout",
+        value_name = "SECONDS",
+        default_value_t = DEFAULT_CLIPBOARD_TIMEOUT,
+        help = "Clear the clipboard after some seconds, 0 to keep the password"
+    )]
+    clipboard_timeout: u64,
+
+    #[arg(
+        long = "print",
+        requires = "clipboard",
+        help = "Print the password as well when copied to the clipboard"
+    )]
+    print: bool,
+
+    #[arg(
+        long = "hash",
+        value_enum,
+        value_name = "SCHEME",
+        help = "Print a hash of the password as well, computed locally with a random salt"
+    )]
+    hash: Option<HashScheme>,
+
+    #[arg(
+        long = "hash-only",
+        requires = "hash",
+        conflicts_with = "clipboard",
+        help = "Print the hash only, without the password"
+    )]
+    hash_only: bool,
+
+    #[arg(
+        long = "chpasswd",
+        conflicts_with_all = ["dice_rolls", "dice_table", "clipboard"],
+        help = "Read usernames from stdin & print `user:password` lines for chpasswd, `user:hash` lines with --hash-only"
+    )]
+    chpasswd: bool,
+
+    #[arg(
+        long = "qr",
+        requires = "ssid",
+        conflicts_with_all = ["chpasswd", "dice_table"],
+        help = "Print a Wi-Fi QR code of the password for phones to join the network, best with -P wifi"
+    )]
+    qr: bool,
+
+    #[arg(
+        long = "qr-file",
+        value_name = "PATH",
+        requires = "ssid",
+        conflicts_with_all = ["chpasswd", "dice_table"],
+        help = "Save the Wi-Fi QR code as a .png or .svg image"
+    )]
+    qr_file: Option<String>,
+
+    #[arg(long = "ssid", help = "Name of the Wi-Fi network of the QR code")]
+    ssid: Option<String>,
+
+    #[arg(
+        long = "wifi-security",
+        value_enum,
+        default_value_t = WifiSecurity::Wpa,
+        help = "Security of the Wi-Fi network of the QR code"
+    )]
+    wifi_security: WifiSecurity,
+
+    #[arg(
+        long = "spell",
+        conflicts_with_all = ["hash_only", "chpasswd", "dice_table"],
+        help = "Spell the password out in the first language, e.g. to read it over the phone"
+    )]
+    spell: bool,
+
+    #[arg(
+        long = "nato",
+        requires = "spell",
+        help = "Spell every word letter by letter with the NATO phonetic alphabet"
+    )]
+    nato: bool,
 
     #[arg(short = 'c', long = "config", help = "Path to .toml config file")]
     config_file: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum CliCommand {
+    /// Derive a stable password for a site from a master secret
+    ///
+    /// The master secret is read from $XKPASSWD_MASTER_SECRET, piped through stdin or prompted for.
+    Derive(DeriveArgs),
+
+    /// Inspect the effective settings, from the config file & command line options
+    Config(ConfigArgs),
+
+    /// Serve passwords over a local HTTP API, on localhost or a Unix socket
+    ///
+    /// POST /generate with optional `settings` (fields of the config file), `preset`,
+    /// `language` & `count`; POST /entropy & /describe with the same fields but `count`;
+    /// GET /presets & /languages. Requests without settings use the settings from
+    /// the config file & command line options.
+    #[cfg(feature = "server")]
+    Serve(ServeArgs),
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct DeriveArgs {
+    #[arg(
+        long = "site",
+        help = "Site to derive the password for, case-insensitive"
+    )]
+    pub site: String,
+
+    #[arg(
+        long = "counter",
+        default_value_t = DEFAULT_COUNTER,
+        help = "Bump to rotate the derived password of a site"
+    )]
+    pub counter: u32,
+}
+
+#[cfg(feature = "server")]
+#[derive(Args, Clone, Debug)]
+pub struct ServeArgs {
+    #[arg(
+        long = "port",
+        default_value_t = DEFAULT_PORT,
+        help = "Port to listen on, bound to localhost only, 0 for any free port"
+    )]
+    pub port: u16,
+
+    #[arg(
+        long = "socket",
+        value_name = "PATH",
+        conflicts_with = "port",
+        help = "Path of a Unix socket to listen on instead"
+    )]
+    pub socket: Option<String>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ConfigCommand {
+    /// Print the effective settings as a config file, or as a shareable code
+    Dump(DumpArgs),
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct DumpArgs {
+    #[arg(
+        long = "code",
+        help = "Print a compact code instead, to be shared & used with --recipe"
+    )]
+    pub code: bool,
 }
 
 impl Cli {
     pub fn init() -> Self {
-        let cli = Self::parse();
+        let mut cli = Self::parse();
         cli.init_logger();
+        cli.load_config_file();
         cli
     }
 
-    pub fn language(&self) -> Language {
-        match self.language {
-            Some(language) => language,
-            None => Language::English,
+    pub fn languages(&self) -> Vec<Language> {
+        match &self.languages {
+            Some(languages) if !languages.is_empty() => languages.clone(),
+            _ => vec![Language::English],
         }
     }
 
-    pub fn parse_settings<B: Builder + Randomizer>(&mut self) -> B {
-        let parse_result = match self.parse_config_file() {
-            Ok(_) => Ok(()),
-            Err(err) => match err {
-                ConfigParseError::Ignore => Ok(()),
-                ConfigParseError::InvalidFile(err) => {
-                    Err(format!("Error parsing config file: {}", err))
+    /// The chosen keyboard, or the usual layout of the first language.
+    pub fn keyboard(&self) -> Keyboard {
+        self.keyboard
+            .unwrap_or_else(|| Keyboard::for_language(self.languages()[0]))
+    }
+
+    /// Settings to generate passwords with, preset symbols being restricted to [`Cli::keyboard`]
+    /// without any keyboard, unlike derived passwords & dumped settings which don't depend on
+    /// the languages.
+    pub fn generation_settings(&self, settings: &Settings) -> Settings {
+        settings.with_language_keyboard(self.keyboard())
+    }
+
+    /// Warns about the given separators & padding symbols left out by the chosen keyboard.
+    pub fn warn_untypable_symbols(&self, settings: &Settings) {
+        let Some(keyboard) = settings.keyboard() else {
+            return;
+        };
+
+        for (name, symbols) in [
+            ("separators", &self.separators),
+            ("padding symbols", &self.padding_symbols),
+        ] {
+            let untypable: String = symbols
+                .iter()
+                .flat_map(|symbols| symbols.chars())
+                .filter(|&symbol| !keyboard.can_type(symbol))
+                .collect();
+
+            if !untypable.is_empty() {
+                eprintln!(
+                    "Warning: {} '{}' left out, not typable on {} keyboards",
+                    name,
+                    untypable,
+                    keyboard.name()
+                );
+            }
+        }
+    }
+
+    pub fn dice_table(&self) -> bool {
+        self.dice_table
+    }
+
+    pub fn interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn stdio(&self) -> bool {
+        self.stdio
+    }
+
+    pub fn uses_dice(&self) -> bool {
+        self.dice_rolls.is_some()
+    }
+
+    pub fn dice_rolls(&self, dice_per_word: u32) -> Vec<String> {
+        match &self.dice_rolls {
+            Some(rolls) if !rolls.is_empty() => rolls.clone(),
+            _ => prompt_dice_rolls(dice_per_word).unwrap_or_else(|err| {
+                Self::exit_with_error(format!("Unable to read dice rolls: {}", err))
+            }),
+        }
+    }
+
+    pub fn subcommand(&self) -> Option<&CliCommand> {
+        self.command.as_ref()
+    }
+
+    /// Whether to read usernames from stdin & print lines for `chpasswd` with --chpasswd.
+    pub fn chpasswd(&self) -> bool {
+        self.chpasswd
+    }
+
+    /// Print the password, or copy it to the clipboard with --clipboard.
+    pub fn output_passwd(&self, passwd: &str) {
+        // checked first, not to output passwords that the QR code can't hold
+        let wifi_qr = self.wifi_qr(passwd).unwrap_or_else(|err| {
+            Self::exit_with_error(format!("Unable to generate QR code: {}", err))
+        });
+
+        if !self.hash_only {
+            self.output_plain_passwd(passwd);
+        }
+
+        if let Some(scheme) = self.hash {
+            let hash = scheme.hash(passwd).unwrap_or_else(|err| {
+                Self::exit_with_error(format!("Unable to hash password: {}", err))
+            });
+            println!("{}", hash);
+        }
+
+        if let Some(speller) = self.speller() {
+            for group in speller.spell(passwd) {
+                println!("{}", group);
+            }
+        }
+
+        if let Some(wifi_qr) = wifi_qr {
+            if self.qr {
+                println!("{}", wifi_qr.to_unicode());
+            }
+
+            if let Some(path) = &self.qr_file {
+                if let Err(err) = save_qr_file(&wifi_qr, path) {
+                    Self::exit_with_error(format!("Unable to save QR code: {}", err));
                 }
-                ConfigParseError::InvalidConfig(field, err) => {
-                    Err(format!("Error parsing config file at '{}': {}", field, err))
+            }
+        }
+    }
+
+    /// Print a `user:password` line for each username read from the input,
+    /// or `user:hash` with --hash-only, as expected by `chpasswd` (`-e` for hashes).
+    pub fn output_chpasswd<R: BufRead, W: Write>(
+        &self,
+        input: R,
+        mut output: W,
+        mut gen_pass: impl FnMut() -> Result<Passwd, String>,
+    ) -> Result<(), String> {
+        if self.command.is_some() {
+            return Err("--chpasswd can't be combined with subcommands".to_string());
+        }
+
+        if self.hash.is_some() && !self.hash_only {
+            return Err(
+                "--chpasswd prints either passwords or hashes, add --hash-only for hashes"
+                    .to_string(),
+            );
+        }
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line.map_err(|err| format!("unable to read usernames: {}", err))?;
+            let user = line.trim();
+            if user.is_empty() || user.starts_with('#') {
+                continue;
+            }
+
+            if user.contains(':') || user.contains(char::is_whitespace) {
+                return Err(format!("invalid username '{}' at line {}", user, index + 1));
+            }
+
+            let passwd = gen_pass()?;
+            match self.hash {
+                Some(scheme) => writeln!(output, "{}:{}", user, scheme.hash(&passwd)?),
+                None => writeln!(output, "{}:{}", user, passwd.as_str()),
+            }
+            .map_err(|err| format!("unable to write: {}", err))?;
+        }
+
+        Ok(())
+    }
+
+    // mixed-language passwords are spelled in the first language
+    fn speller(&self) -> Option<Speller> {
+        self.spell
+            .then(|| Speller::new(self.languages()[0]).with_nato(self.nato))
+    }
+
+    fn wifi_qr(&self, passwd: &str) -> Result<Option<WifiQr>, String> {
+        if let Some(path) = &self.qr_file {
+            qr_file_extension(path)?;
+        } else if !self.qr {
+            return Ok(None);
+        }
+
+        let ssid = self.ssid.as_deref().unwrap_or_default();
+        WifiQr::new(ssid, self.wifi_security, passwd).map(Some)
+    }
+
+    fn output_plain_passwd(&self, passwd: &str) {
+        if self.clipboard {
+            let copied = clipboard::copy(passwd, self.clipboard_timeout).unwrap_or_else(|err| {
+                Self::exit_with_error(format!("Unable to copy to clipboard: {}", err))
+            });
+
+            // terminals silently ignore OSC 52 sequences they don't support or allow
+            let message = match copied {
+                Copied::Local(provider) => format!("Copied to clipboard with {}", provider),
+                Copied::Osc52 => {
+                    "Sent to the terminal with OSC 52, copied if the terminal supports it"
+                        .to_string()
                 }
-            },
-        };
+            };
 
-        let result = match parse_result {
-            Err(message) => Err(message),
-            Ok(_) => match self.build_settings::<B>() {
-                Err(err) => Err(format!("Invalid settings: {}", err)),
-                Ok(settings) => Ok(settings),
-            },
-        };
+            match self.clipboard_timeout {
+                0 => eprintln!("{}", message),
+                timeout => eprintln!("{}, clearing in {} seconds", message, timeout),
+            }
 
-        match result {
-            Ok(settings) => settings,
-            Err(message) => {
-                Self::command()
-                    .error(ErrorKind::InvalidValue, message)
-                    .exit();
+            if !self.print {
+                return;
             }
         }
+
+        println!("{}", passwd);
+    }
+
+    /// Print the effective settings for `config dump`, as a config file or a code.
+    pub fn dump_config(&self, settings: &Settings, args: &DumpArgs) {
+        if args.code {
+            println!("{}", settings.to_code());
+            return;
+        }
+
+        let config = dump_config(settings, &self.languages())
+            .unwrap_or_else(|err| Self::exit_with_error(format!("Unable to dump config: {}", err)));
+        print!("{}", config);
+    }
+
+    pub fn parse_settings<B: Builder + Randomizer>(&self, word_lengths: &[u8]) -> B {
+        match self.build_settings::<B>(word_lengths) {
+            Ok(settings) => settings,
+            Err(err) => Self::exit_with_error(format!("Invalid settings: {}", err)),
+        }
+    }
+
+    fn load_config_file(&mut self) {
+        let message = match self.parse_config_file() {
+            Ok(_) | Err(ConfigParseError::Ignore) => return,
+            Err(ConfigParseError::InvalidFile(err)) => {
+                format!("Error parsing config file: {}", err)
+            }
+            Err(ConfigParseError::InvalidConfig(field, err)) => {
+                format!("Error parsing config file at '{}': {}", field, err)
+            }
+        };
+
+        Self::exit_with_error(message);
+    }
+
+    pub fn read_master_secret() -> String {
+        let secret = match env::var(MASTER_SECRET_ENV) {
+            Ok(secret) => Ok(secret),
+            Err(_) if io::stdin().is_terminal() => rpassword::prompt_password("Master secret: "),
+            Err(_) => read_first_line(io::stdin().lock()),
+        };
+
+        secret.unwrap_or_else(|err| {
+            Self::exit_with_error(format!("Unable to read master secret: {}", err))
+        })
     }
 
-    fn build_settings<B: Builder + Randomizer>(&self) -> Result<B, String> {
-        let mut settings = if let Some(preset) = self.preset {
+    pub fn exit_with_error(message: String) -> ! {
+        Self::command()
+            .error(ErrorKind::InvalidValue, message)
+            .exit();
+    }
+
+    fn build_settings<B: Builder + Randomizer>(&self, word_lengths: &[u8]) -> Result<B, String> {
+        let mut settings = if let Some(recipe) = &self.recipe {
+            B::from_code(recipe)?
+        } else if let Some(preset) = self.preset {
             B::from_preset(preset)
         } else {
             B::default()
         };
 
         settings = settings
+            .with_available_word_lengths(word_lengths)?
             .with_word_lengths(self.word_length_min, self.word_length_max)?
             .with_padding_digits(self.padding_digits_before, self.padding_digits_after)
             .with_padding_symbol_lengths(self.padding_symbols_before, self.padding_symbols_after);
@@ -196,6 +691,26 @@ impl Cli {
             }
         }
 
+        let excluded_words = collect_words(&self.excluded_words, &self.exclude_file)?;
+        if !excluded_words.is_empty() {
+            let words: Vec<&str> = excluded_words.iter().map(String::as_str).collect();
+            settings = settings.with_excluded_words(&words);
+        }
+
+        let included_words = collect_words(&self.included_words, &self.include_file)?;
+        if !included_words.is_empty() {
+            let words: Vec<&str> = included_words.iter().map(String::as_str).collect();
+            settings = settings.with_included_words(&words);
+        }
+
+        if self.readable {
+            settings = settings.with_readable(true);
+        }
+
+        if self.keyboard.is_some() {
+            settings = settings.with_keyboard(self.keyboard);
+        }
+
         Ok(settings)
     }
 
@@ -216,105 +731,86 @@ impl Cli {
     }
 }
 
-impl ValueEnum for CliPadding {
-    fn value_variants<'a>() -> &'a [Self] {
-        &[Self::Fixed, Self::Adaptive]
+fn collect_words(
+    inline_words: &Option<Vec<String>>,
+    words_file: &Option<String>,
+) -> Result<Vec<String>, String> {
+    let mut words: Vec<String> = inline_words.clone().unwrap_or_default();
+
+    if let Some(path) = words_file {
+        let data = fs::read_to_string(path)
+            .map_err(|err| format!("unable to read words file '{}': {}", path, err))?;
+
+        words.extend(
+            data.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from),
+        );
     }
 
-    fn to_possible_value(&self) -> Option<PossibleValue> {
-        Some(match self {
-            Self::Fixed => PossibleValue::new("fixed")
-                .help("Fixed numbers of symbols to be padded before & after words"),
-            Self::Adaptive => PossibleValue::new("adaptive").help(
-                r#"Pad or trim the final output to fit a length. Requires --adaptive-length.
-Notes: setting this will disable --symbols-before and --symbols-after options"#,
-            ),
-        })
-    }
+    Ok(words)
 }
 
-impl ValueEnum for Preset {
-    fn value_variants<'a>() -> &'a [Self] {
-        &[
-            Self::Default,
-            Self::AppleID,
-            Self::WindowsNtlmV1,
-            Self::SecurityQuestions,
-            Self::Web16,
-            Self::Web32,
-            Self::Wifi,
-            Self::Xkcd,
-        ]
-    }
+fn qr_file_extension(path: &str) -> Result<String, String> {
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase)
+        .filter(|extension| extension == "png" || extension == "svg")
+        .ok_or_else(|| format!("unsupported image '{}', expect a .png or .svg file", path))
+}
 
-    fn to_possible_value(&self) -> Option<PossibleValue> {
-        Some(match self {
-            Self::Default => PossibleValue::new("default").help("Some sensible default values"),
-            Self::AppleID => PossibleValue::new("apple-id").help("Apple ID passwords"),
-            Self::WindowsNtlmV1 => PossibleValue::new("ntlm").help("Windows NTLM v1"),
-            Self::SecurityQuestions => PossibleValue::new("secq").help("Security questions"),
-            Self::Web16 => {
-                PossibleValue::new("web16").help("Maxium 16 characters for older websites")
-            }
-            Self::Web32 => {
-                PossibleValue::new("web32").help("Maximum 32 characters for modern websites")
-            }
-            Self::Wifi => PossibleValue::new("wifi").help("Fixed 63 characters for Wifi WPA2 keys"),
-            Self::Xkcd => {
-                PossibleValue::new("xkcd").help("As described in the original XKCD comic")
-            }
-        })
-    }
+fn save_qr_file(wifi_qr: &WifiQr, path: &str) -> Result<(), String> {
+    let data = match qr_file_extension(path)?.as_str() {
+        "png" => wifi_qr.to_png()?,
+        _ => wifi_qr.to_svg().into_bytes(),
+    };
+
+    fs::write(path, data).map_err(|err| format!("unable to write '{}': {}", path, err))
 }
 
-impl ValueEnum for WordTransform {
-    fn value_variants<'a>() -> &'a [Self] {
-        &[
-            Self::Lowercase,
-            Self::Titlecase,
-            Self::Uppercase,
-            Self::InversedTitlecase,
-            Self::AltercaseLowerFirst,
-            Self::AltercaseUpperFirst,
-        ]
-    }
+fn prompt_dice_rolls(dice_per_word: u32) -> io::Result<Vec<String>> {
+    let interactive = io::stdin().is_terminal();
+    let mut rolls: Vec<String> = vec![];
+
+    loop {
+        if interactive {
+            eprint!(
+                "Roll {} dice for word #{} (empty to finish): ",
+                dice_per_word,
+                rolls.len() + 1
+            );
+        }
 
-    fn to_possible_value(&self) -> Option<PossibleValue> {
-        Some(match self {
-            Self::Lowercase => PossibleValue::new("lowercase").help(self.to_string()),
-            Self::Titlecase => PossibleValue::new("titlecase").help(self.to_string()),
-            Self::Uppercase => PossibleValue::new("uppercase").help(self.to_string()),
-            Self::InversedTitlecase => {
-                PossibleValue::new("inversed-titlecase").help(self.to_string())
-            }
-            Self::AltercaseLowerFirst => {
-                PossibleValue::new("altercase-lower-first").help(self.to_string())
-            }
-            Self::AltercaseUpperFirst => {
-                PossibleValue::new("altercase-upper-first").help(self.to_string())
-            }
-        })
+        let roll = read_first_line(io::stdin().lock())?;
+        if roll.trim().is_empty() {
+            return Ok(rolls);
+        }
+
+        rolls.push(roll);
     }
 }
 
-impl ValueEnum for Language {
+fn read_first_line<R: BufRead>(mut reader: R) -> io::Result<String> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+impl ValueEnum for CliPadding {
     fn value_variants<'a>() -> &'a [Self] {
-        &[
-            Self::English,
-            Self::French,
-            Self::German,
-            Self::Portuguese,
-            Self::Spanish,
-        ]
+        &[Self::Fixed, Self::Adaptive]
     }
 
     fn to_possible_value(&self) -> Option<PossibleValue> {
         Some(match self {
-            Self::English => PossibleValue::new("en").help("English"),
-            Self::French => PossibleValue::new("fr").help("French"),
-            Self::German => PossibleValue::new("de").help("German"),
-            Self::Portuguese => PossibleValue::new("pt").help("Portuguese"),
-            Self::Spanish => PossibleValue::new("es").help("Spanish"),
+            Self::Fixed => PossibleValue::new("fixed")
+                .help("Fixed numbers of symbols to be padded before & after words"),
+            Self::Adaptive => PossibleValue::new("adaptive").help(
+                r#"Pad or trim the final output to fit a length. Requires --adaptive-length.
+Notes: setting this will disable --symbols-before and --symbols-after options"#,
+            ),
         })
     }
 }
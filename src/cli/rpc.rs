@@ -0,0 +1,206 @@
+//! Line-delimited JSON-RPC 2.0 of `xkpasswd --stdio`, one request per line on stdin
+//! & one response per line on stdout, for editors & scripts keeping a process around.
+//!
+//! Methods map to [`PassService`], with the same params as the HTTP API.
+
+use super::api::{ApiError, PassService};
+use super::*;
+
+use serde_json::{json, Value};
+use std::io::Write;
+
+// error codes of the JSON-RPC 2.0 specification
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+// implementation-defined server error
+const GENERATION_FAILED: i64 = -32000;
+
+pub struct RpcSession {
+    service: PassService,
+}
+
+impl RpcSession {
+    pub fn new(cli: &Cli) -> Result<Self, String> {
+        Ok(RpcSession {
+            service: PassService::new(cli)?,
+        })
+    }
+
+    /// Answer requests until the input is closed.
+    pub fn run<R: BufRead, W: Write>(&self, input: R, mut output: W) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(response) = self.handle(&line) {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // notifications, i.e. requests without id, get no response
+    fn handle(&self, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                return Some(error_response(
+                    Value::Null,
+                    PARSE_ERROR,
+                    format!("invalid JSON: {}", err),
+                ))
+            }
+        };
+
+        let id = request.get("id").cloned();
+        let response_id = id.clone().unwrap_or(Value::Null);
+
+        let method = match (request.get("jsonrpc"), request.get("method")) {
+            (Some(version), Some(Value::String(method))) if version == "2.0" => method,
+            _ => {
+                return Some(error_response(
+                    response_id,
+                    INVALID_REQUEST,
+                    "expect a JSON-RPC 2.0 request",
+                ))
+            }
+        };
+
+        let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+        log::info!("{} {}", method, params);
+
+        let result = match method.as_str() {
+            "generate" => self.service.generate(&params),
+            "entropy" => self.service.entropy(&params),
+            "describe" => self.service.describe(&params),
+            "presets" => Ok(self.service.presets()),
+            "languages" => Ok(self.service.languages()),
+            _ => {
+                return id.map(|id| {
+                    error_response(id, METHOD_NOT_FOUND, format!("unknown method '{}'", method))
+                })
+            }
+        };
+
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(err @ ApiError::InvalidParams(_)) => {
+                error_response(id, INVALID_PARAMS, err.message())
+            }
+            Err(err @ ApiError::Failed(_)) => error_response(id, GENERATION_FAILED, err.message()),
+        })
+    }
+}
+
+fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message.into() },
+        "id": id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(args: &[&str]) -> RpcSession {
+        RpcSession::new(&Cli::parse_from([&["xkpasswd"], args].concat())).unwrap()
+    }
+
+    #[test]
+    fn test_handle() {
+        let session = session(&["-P", "xkcd"]);
+
+        let response = session
+            .handle(r#"{"jsonrpc": "2.0", "method": "generate", "params": {"count": 2}, "id": 1}"#)
+            .unwrap();
+        assert_eq!(1, response["id"]);
+        assert_eq!(2, response["result"]["passwords"].as_array().unwrap().len());
+
+        let response = session
+            .handle(r#"{"jsonrpc": "2.0", "method": "describe", "id": "a"}"#)
+            .unwrap();
+        assert_eq!("a", response["id"]);
+        assert_eq!(
+            session.service.settings().to_string(),
+            response["result"]["description"]
+        );
+
+        // notifications
+        assert_eq!(
+            None,
+            session.handle(r#"{"jsonrpc": "2.0", "method": "generate"}"#)
+        );
+        assert_eq!(
+            None,
+            session.handle(r#"{"jsonrpc": "2.0", "method": "foo"}"#)
+        );
+    }
+
+    #[test]
+    fn test_handle_errors() {
+        let session = session(&[]);
+        let table = [
+            ("{", Value::Null, PARSE_ERROR),
+            (r#"[1, 2]"#, Value::Null, INVALID_REQUEST),
+            (
+                r#"{"method": "generate", "id": 1}"#,
+                json!(1),
+                INVALID_REQUEST,
+            ),
+            (
+                r#"{"jsonrpc": "2.0", "method": "foo", "id": 2}"#,
+                json!(2),
+                METHOD_NOT_FOUND,
+            ),
+            (
+                r#"{"jsonrpc": "2.0", "method": "entropy", "params": [], "id": 3}"#,
+                json!(3),
+                INVALID_PARAMS,
+            ),
+            (
+                r#"{"jsonrpc": "2.0", "method": "generate", "params": {"settings": {"include_words": ["foo"]}}, "id": 4}"#,
+                json!(4),
+                GENERATION_FAILED,
+            ),
+        ];
+
+        for (line, expected_id, expected_code) in table {
+            let response = session.handle(line).unwrap();
+            assert_eq!(expected_id, response["id"], "{}", line);
+            assert_eq!(expected_code, response["error"]["code"], "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let session = session(&[]);
+        let input = concat!(
+            r#"{"jsonrpc": "2.0", "method": "presets", "id": 1}"#,
+            "\n\n",
+            r#"{"jsonrpc": "2.0", "method": "entropy", "params": {"preset": "wifi"}, "id": 2}"#,
+            "\n",
+        );
+
+        let mut output = vec![];
+        session.run(input.as_bytes(), &mut output).unwrap();
+
+        let responses: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(2, responses.len());
+        assert_eq!(session.service.presets(), responses[0]["result"]);
+        assert_eq!(2, responses[1]["id"]);
+        assert!(responses[1]["result"]["seen"].as_u64().unwrap() > 0);
+    }
+}
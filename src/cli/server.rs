@@ -0,0 +1,237 @@
+//! Local HTTP API of `xkpasswd serve`, for tools that would otherwise shell out per password.
+//!
+//! Routes map to the methods of [`PassService`], with JSON bodies of the same fields.
+
+use super::api::{ApiError, PassService};
+use super::*;
+
+use serde_json::{json, Value};
+use std::io::Read;
+use std::net::Ipv4Addr;
+use tiny_http::{Header, Method, Response, Server};
+
+pub const DEFAULT_PORT: u16 = 8787;
+const MAX_BODY_SIZE: u64 = 64 * 1024;
+
+pub struct PassServer {
+    service: PassService,
+}
+
+impl PassServer {
+    pub fn new(cli: &Cli) -> Result<Self, String> {
+        Ok(PassServer {
+            service: PassService::new(cli)?,
+        })
+    }
+
+    /// Serve requests until the process is killed.
+    pub fn serve(&self, args: &ServeArgs) -> Result<(), String> {
+        let server = match &args.socket {
+            #[cfg(unix)]
+            Some(path) => Server::http_unix(std::path::Path::new(path)),
+            #[cfg(not(unix))]
+            Some(_) => return Err("Unix sockets are not supported on this platform".to_string()),
+            None => Server::http((Ipv4Addr::LOCALHOST, args.port)),
+        }
+        .map_err(|err| format!("unable to listen: {}", err))?;
+
+        match server.server_addr().to_ip() {
+            Some(addr) => eprintln!("Listening on http://{}", addr),
+            None => eprintln!("Listening on {}", server.server_addr()),
+        }
+
+        let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let (status, value) = match request
+                .as_reader()
+                .take(MAX_BODY_SIZE)
+                .read_to_string(&mut body)
+            {
+                Ok(_) => {
+                    let path = request.url().split('?').next().unwrap_or_default();
+                    self.handle(request.method(), path, &body)
+                }
+                Err(err) => error_value(400, format!("unable to read request: {}", err)),
+            };
+
+            log::info!("{} {} {}", request.method(), request.url(), status);
+
+            let response = Response::from_string(value.to_string())
+                .with_status_code(status)
+                .with_header(content_type.clone());
+            if let Err(err) = request.respond(response) {
+                log::debug!("unable to respond: {}", err);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle(&self, method: &Method, path: &str, body: &str) -> (u16, Value) {
+        let result = match (method, path) {
+            (Method::Post, "/generate" | "/entropy" | "/describe") => {
+                let params = match parse_body(body) {
+                    Ok(params) => params,
+                    Err(err) => return error_value(400, err),
+                };
+
+                match path {
+                    "/generate" => self.service.generate(&params),
+                    "/entropy" => self.service.entropy(&params),
+                    _ => self.service.describe(&params),
+                }
+            }
+            (Method::Get, "/presets") => Ok(self.service.presets()),
+            (Method::Get, "/languages") => Ok(self.service.languages()),
+            (_, "/generate" | "/entropy" | "/describe" | "/presets" | "/languages") => {
+                return error_value(405, "method not allowed")
+            }
+            _ => return error_value(404, "not found"),
+        };
+
+        match result {
+            Ok(value) => (200, value),
+            Err(err @ ApiError::InvalidParams(_)) => error_value(400, err.message()),
+            Err(err @ ApiError::Failed(_)) => error_value(422, err.message()),
+        }
+    }
+}
+
+// an empty body is the same as an empty object
+fn parse_body(body: &str) -> Result<Value, String> {
+    if body.trim().is_empty() {
+        return Ok(json!({}));
+    }
+
+    serde_json::from_str(body).map_err(|err| format!("invalid JSON: {}", err))
+}
+
+fn error_value(status: u16, message: impl Into<String>) -> (u16, Value) {
+    (status, json!({ "error": message.into() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+
+    fn server(args: &[&str]) -> PassServer {
+        let cli = Cli::parse_from([&["xkpasswd"], args, &["serve"]].concat());
+        PassServer::new(&cli).unwrap()
+    }
+
+    fn post(server: &PassServer, body: &str) -> (u16, Value) {
+        server.handle(&Method::Post, "/generate", body)
+    }
+
+    #[test]
+    fn test_generate() {
+        let server = server(&["-w", "2", "-s", "+", "-y", "!", "--digits-after", "0"]);
+        assert_eq!(2, server.service.settings().words_count());
+
+        // server's own settings by default
+        let (status, value) = post(&server, "");
+        assert_eq!(200, status);
+        let passwords = value["passwords"].as_array().unwrap();
+        assert_eq!(1, passwords.len());
+        assert_eq!(
+            2,
+            passwords[0]["passwd"].as_str().unwrap().split('+').count()
+        );
+        assert!(passwords[0]["entropy"]["seen"].as_u64().unwrap() > 0);
+
+        let (status, value) = post(
+            &server,
+            r#"{"preset": "xkcd", "settings": {"words_count": 5}, "language": "de", "count": 3}"#,
+        );
+        assert_eq!(200, status);
+        let passwords = value["passwords"].as_array().unwrap();
+        assert_eq!(3, passwords.len());
+        for password in passwords {
+            assert_eq!(5, password["passwd"].as_str().unwrap().split('-').count());
+        }
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        let server = server(&[]);
+        let table = [
+            ("[]", 400, "expect a JSON object"),
+            ("{", 400, "invalid JSON: "),
+            (r#"{"size": 1}"#, 400, "unknown field 'size'"),
+            (
+                r#"{"count": 0}"#,
+                400,
+                "count must be an integer from 1 to 100",
+            ),
+            (r#"{"language": "xx"}"#, 400, "unknown language 'xx'"),
+            (
+                r#"{"preset": "foo"}"#,
+                400,
+                "invalid settings at 'preset': ",
+            ),
+            (
+                r#"{"settings": {"exclude_file": "/etc/passwd"}}"#,
+                400,
+                "unknown settings field 'exclude_file'",
+            ),
+            (
+                r#"{"settings": {"words_count": 256}}"#,
+                400,
+                "settings field 'words_count' must be an integer from 0 to 255",
+            ),
+            (
+                r#"{"settings": {"words_count": 0}}"#,
+                400,
+                "invalid settings: only positive integer is allowed for words count",
+            ),
+            (
+                r#"{"settings": {"include_words": ["foo"]}}"#,
+                422,
+                "unable to generate password: only 0 word(s) left after filtering, 3 required",
+            ),
+        ];
+
+        for (body, expected_status, expected_error) in table {
+            let (status, value) = post(&server, body);
+            assert_eq!(expected_status, status, "{}", body);
+            assert!(
+                value["error"].as_str().unwrap().starts_with(expected_error),
+                "{} => {}",
+                body,
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_routes() {
+        let server = server(&[]);
+
+        let (status, value) = server.handle(&Method::Get, "/presets", "");
+        assert_eq!(200, status);
+        assert_eq!(Preset::ALL.len(), value.as_array().unwrap().len());
+        assert_eq!("xkcd", value[7]["name"]);
+        assert_eq!(
+            Settings::from_preset(Preset::Xkcd).to_code(),
+            value[7]["code"]
+        );
+
+        let (status, value) = server.handle(&Method::Post, "/entropy", r#"{"preset": "wifi"}"#);
+        assert_eq!(200, status);
+        assert!(value["seen"].as_u64().unwrap() > 0);
+
+        let (status, value) = server.handle(&Method::Post, "/describe", "");
+        assert_eq!(200, status);
+        assert_eq!(server.service.settings().to_string(), value["description"]);
+
+        let (status, value) = server.handle(&Method::Get, "/languages", "");
+        assert_eq!(200, status);
+        assert_eq!(json!({"code": "en", "name": "English"}), value[0]);
+
+        assert_eq!(405, server.handle(&Method::Get, "/generate", "").0);
+        assert_eq!(404, server.handle(&Method::Get, "/", "").0);
+    }
+}
@@ -1,5 +1,8 @@
 use super::*;
 use crate::settings::*;
+use std::io::Write;
+
+const WORD_LENGTHS: &[u8] = &[3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
 
 const DEFAULT_CLI: Cli = Cli {
     words_count: None,
@@ -14,10 +17,34 @@ const DEFAULT_CLI: Cli = Cli {
     padding_symbols_after: None,
     padding: None,
     adaptive_length: None,
+    excluded_words: None,
+    exclude_file: None,
+    included_words: None,
+    include_file: None,
+    readable: false,
+    keyboard: None,
+    dice_rolls: None,
+    dice_table: false,
     preset: None,
+    recipe: None,
     verbosity: 0,
-    language: None,
+    languages: None,
+    interactive: false,
+    stdio: false,
+    clipboard: false,
+    clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+    print: false,
+    hash: None,
+    hash_only: false,
+    chpasswd: false,
+    qr: false,
+    qr_file: None,
+    ssid: None,
+    wifi_security: WifiSecurity::Wpa,
+    spell: false,
+    nato: false,
     config_file: None,
+    command: None,
 };
 
 #[test]
@@ -27,12 +54,12 @@ fn test_build_settings_panic() {
         words_count: Some(0),
         ..DEFAULT_CLI
     };
-    let _: Settings = cli.build_settings().unwrap();
+    let _: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
 }
 
 #[test]
 fn test_build_settings_default() {
-    let settings: Settings = DEFAULT_CLI.build_settings().unwrap();
+    let settings: Settings = DEFAULT_CLI.build_settings(WORD_LENGTHS).unwrap();
     assert_eq!(Settings::default(), settings);
 }
 
@@ -55,7 +82,7 @@ fn test_build_settings_from_preset() {
             ..DEFAULT_CLI
         };
 
-        let settings: Settings = cli.build_settings().unwrap();
+        let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
         assert_eq!(Settings::from_preset(preset), settings);
     }
 }
@@ -95,7 +122,185 @@ fn test_build_settings_custom() {
         .with_padding_strategy(PaddingStrategy::Adaptive(17))
         .unwrap();
 
-    assert_eq!(expected_settings, cli.build_settings::<Settings>().unwrap());
+    assert_eq!(
+        expected_settings,
+        cli.build_settings::<Settings>(WORD_LENGTHS).unwrap()
+    );
+}
+
+#[test]
+fn test_build_settings_word_lengths() {
+    let cli = Cli {
+        word_length_min: Some(2),
+        word_length_max: Some(2),
+        ..DEFAULT_CLI
+    };
+    assert_eq!(
+        Err("min word length must be 3 or higher".to_string()),
+        cli.build_settings::<Settings>(WORD_LENGTHS)
+    );
+
+    let cli = Cli {
+        word_length_min: Some(3),
+        word_length_max: Some(3),
+        ..DEFAULT_CLI
+    };
+    let settings: Settings = cli.build_settings(&[3, 4, 5]).unwrap();
+    let expected_settings = Settings::default()
+        .with_available_word_lengths(&[3, 4, 5])
+        .unwrap()
+        .with_word_lengths(Some(3), Some(3))
+        .unwrap();
+    assert_eq!(expected_settings, settings);
+
+    let cli = Cli {
+        word_length_max: Some(12),
+        ..DEFAULT_CLI
+    };
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(
+        Settings::default()
+            .with_word_lengths(None, Some(12))
+            .unwrap(),
+        settings
+    );
+
+    assert_eq!(
+        Err("no words available in dictionary".to_string()),
+        DEFAULT_CLI.build_settings::<Settings>(&[])
+    );
+}
+
+#[test]
+fn test_build_settings_word_filters() {
+    let mut words_file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(words_file, "# comment\nbaz\n\n  qux  ").unwrap();
+
+    let cli = Cli {
+        excluded_words: Some(vec!["foo".to_string(), "bar".to_string()]),
+        exclude_file: Some(words_file.path().to_str().unwrap().to_string()),
+        included_words: Some(vec!["fooz".to_string()]),
+        readable: true,
+        ..DEFAULT_CLI
+    };
+
+    let expected_settings = Settings::default()
+        .with_excluded_words(&["foo", "bar", "baz", "qux"])
+        .with_included_words(&["fooz"])
+        .with_readable(true);
+    assert_eq!(
+        expected_settings,
+        cli.build_settings::<Settings>(WORD_LENGTHS).unwrap()
+    );
+
+    let cli = Cli {
+        include_file: Some("/nonexistent/words.txt".to_string()),
+        ..DEFAULT_CLI
+    };
+    let result = cli.build_settings::<Settings>(WORD_LENGTHS);
+    assert!(result
+        .unwrap_err()
+        .starts_with("unable to read words file '/nonexistent/words.txt'"));
+
+    let cli =
+        Cli::try_parse_from(["xkpasswd", "--exclude", "foo,bar", "--exclude", "baz"]).unwrap();
+    assert_eq!(
+        Some(vec![
+            "foo".to_string(),
+            "bar".to_string(),
+            "baz".to_string()
+        ]),
+        cli.excluded_words
+    );
+}
+
+#[test]
+fn test_build_settings_keyboard() {
+    // the layout of the first language only applies to preset symbols when generating
+    let cli = Cli::try_parse_from(["xkpasswd", "-z", "fr", "-z", "en"]).unwrap();
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(None, settings.keyboard());
+    assert_eq!(
+        settings.with_language_keyboard(Keyboard::Fr),
+        cli.generation_settings(&settings)
+    );
+    assert_eq!(".-_", cli.generation_settings(&settings).separators());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-z", "fr", "-s", "€^", "-y", "€"]).unwrap();
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(settings, cli.generation_settings(&settings));
+    assert_eq!("€^", cli.generation_settings(&settings).separators());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-z", "fr", "--keyboard", "pt-br"]).unwrap();
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(Some(Keyboard::PtBr), settings.keyboard());
+    assert_eq!(settings, cli.generation_settings(&settings));
+
+    // recipes keep their own keyboard, unless chosen
+    let cli = Cli {
+        recipe: Some("xk1:w4-kios".to_string()),
+        languages: Some(vec![Language::German]),
+        ..DEFAULT_CLI
+    };
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(Some(Keyboard::Ios), settings.keyboard());
+    assert_eq!(settings, cli.generation_settings(&settings));
+
+    let cli = Cli {
+        keyboard: Some(Keyboard::Android),
+        ..cli
+    };
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(Some(Keyboard::Android), settings.keyboard());
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--keyboard", "azerty"]).is_err());
+}
+
+#[test]
+fn test_build_settings_with_recipe() {
+    let recipe = Settings::from_preset(Preset::Wifi)
+        .with_excluded_words(&["foo"])
+        .with_readable(true)
+        .to_code();
+
+    let cli = Cli {
+        recipe: Some(recipe.clone()),
+        ..DEFAULT_CLI
+    };
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(recipe, settings.to_code());
+
+    // other options override the recipe
+    let cli = Cli {
+        recipe: Some(recipe),
+        words_count: Some(3),
+        ..DEFAULT_CLI
+    };
+    let settings: Settings = cli.build_settings(WORD_LENGTHS).unwrap();
+    assert_eq!(3, settings.words_count());
+    assert_eq!(Some(&"foo".to_string()), settings.excluded_words().first());
+
+    let cli = Cli {
+        recipe: Some("xk1:w0".to_string()),
+        ..DEFAULT_CLI
+    };
+    assert_eq!(
+        Err("only positive integer is allowed for words count".to_string()),
+        cli.build_settings::<Settings>(WORD_LENGTHS)
+    );
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--recipe", "xk1:w5", "-P", "xkcd"]).is_err());
+}
+
+#[test]
+fn test_config_dump_args() {
+    let cli = Cli::try_parse_from(["xkpasswd", "-w", "5", "config", "dump", "--code"]).unwrap();
+    assert!(matches!(
+        cli.subcommand(),
+        Some(CliCommand::Config(ConfigArgs {
+            command: ConfigCommand::Dump(DumpArgs { code: true })
+        }))
+    ));
 }
 
 #[test]
@@ -122,15 +327,274 @@ fn test_language_value_enum() {
 }
 
 #[test]
-fn test_cli_language_default() {
-    // Test that language() returns English by default
+fn test_cli_languages_default() {
+    // Test that languages() returns English by default
     let cli = DEFAULT_CLI;
-    assert_eq!(Language::English, cli.language());
+    assert_eq!(vec![Language::English], cli.languages());
+
+    let cli = Cli {
+        languages: Some(vec![]),
+        ..DEFAULT_CLI
+    };
+    assert_eq!(vec![Language::English], cli.languages());
 
-    // Test with explicit language
+    // Test with explicit languages
     let cli = Cli {
-        language: Some(Language::French),
+        languages: Some(vec![Language::French]),
         ..DEFAULT_CLI
     };
-    assert_eq!(Language::French, cli.language());
+    assert_eq!(vec![Language::French], cli.languages());
+
+    let cli = Cli {
+        languages: Some(vec![Language::English, Language::German]),
+        ..DEFAULT_CLI
+    };
+    assert_eq!(vec![Language::English, Language::German], cli.languages());
+}
+
+#[test]
+fn test_cli_parse_languages() {
+    let cli = Cli::try_parse_from(["xkpasswd", "-z", "en", "--lang", "de"]).unwrap();
+    assert_eq!(vec![Language::English, Language::German], cli.languages());
+}
+
+#[test]
+fn test_cli_parse_derive() {
+    let cli =
+        Cli::try_parse_from(["xkpasswd", "-P", "xkcd", "derive", "--site", "foo.com"]).unwrap();
+    assert!(matches!(
+        cli.subcommand(),
+        Some(CliCommand::Derive(DeriveArgs { site, counter: 1 })) if site == "foo.com"
+    ));
+
+    let cli =
+        Cli::try_parse_from(["xkpasswd", "derive", "--site", "foo.com", "--counter", "3"]).unwrap();
+    assert!(matches!(
+        cli.subcommand(),
+        Some(CliCommand::Derive(DeriveArgs { counter: 3, .. }))
+    ));
+
+    assert!(Cli::try_parse_from(["xkpasswd", "derive"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd"])
+        .unwrap()
+        .subcommand()
+        .is_none());
+}
+
+#[test]
+fn test_read_first_line() {
+    let table = [
+        ("secret", "secret"),
+        ("secret\n", "secret"),
+        ("secret\r\nignored\n", "secret"),
+        (" spaced secret \n", " spaced secret "),
+        ("", ""),
+    ];
+
+    for (input, expected) in table {
+        assert_eq!(expected, read_first_line(input.as_bytes()).unwrap());
+    }
+}
+
+#[test]
+fn test_cli_parse_dice() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--dice", "43126", "22514"]).unwrap();
+    assert!(cli.uses_dice());
+    assert!(!cli.dice_table());
+    assert_eq!(vec!["43126", "22514"], cli.dice_rolls(5));
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--dice"]).unwrap();
+    assert!(cli.uses_dice());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--dice-table", "-z", "de"]).unwrap();
+    assert!(!cli.uses_dice());
+    assert!(cli.dice_table());
+}
+
+#[test]
+fn test_cli_parse_clipboard() {
+    let cli = Cli::try_parse_from(["xkpasswd"]).unwrap();
+    assert!(!cli.clipboard);
+    assert_eq!(DEFAULT_CLIPBOARD_TIMEOUT, cli.clipboard_timeout);
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--clipboard"]).unwrap();
+    assert!(cli.clipboard);
+    assert!(!cli.print);
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--clipboard",
+        "--clipboard-timeout",
+        "0",
+        "--print",
+    ])
+    .unwrap();
+    assert!(cli.clipboard);
+    assert!(cli.print);
+    assert_eq!(0, cli.clipboard_timeout);
+
+    // printing is the default without clipboard
+    assert!(Cli::try_parse_from(["xkpasswd", "--print"]).is_err());
+}
+
+#[test]
+fn test_cli_parse_hash() {
+    let cli = Cli::try_parse_from(["xkpasswd", "--hash", "sha512-crypt"]).unwrap();
+    assert_eq!(Some(HashScheme::Sha512Crypt), cli.hash);
+    assert!(!cli.hash_only);
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--hash", "htpasswd", "--hash-only"]).unwrap();
+    assert_eq!(Some(HashScheme::Htpasswd), cli.hash);
+    assert!(cli.hash_only);
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--hash-only"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--hash", "md5"]).is_err());
+    assert!(
+        Cli::try_parse_from(["xkpasswd", "--hash", "bcrypt", "--hash-only", "--clipboard"])
+            .is_err()
+    );
+    assert!(Cli::try_parse_from(["xkpasswd", "--chpasswd", "--dice"]).is_err());
+}
+
+#[test]
+fn test_output_chpasswd() {
+    let input = "root\n\n# service accounts\n  www-data  \n";
+    let mut counter = 0;
+    let mut gen_pass = || {
+        counter += 1;
+        Ok(Passwd::from(format!("passwd-{}", counter)))
+    };
+
+    let mut output = vec![];
+    let cli = Cli::try_parse_from(["xkpasswd", "--chpasswd"]).unwrap();
+    cli.output_chpasswd(input.as_bytes(), &mut output, &mut gen_pass)
+        .unwrap();
+    assert_eq!(
+        "root:passwd-1\nwww-data:passwd-2\n",
+        String::from_utf8(output).unwrap()
+    );
+
+    let mut output = vec![];
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--chpasswd",
+        "--hash",
+        "sha512-crypt",
+        "--hash-only",
+    ])
+    .unwrap();
+    cli.output_chpasswd("root".as_bytes(), &mut output, &mut gen_pass)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let hash = output.trim_end().strip_prefix("root:").unwrap();
+    let salt = hash.split('$').nth(2).unwrap();
+    assert_eq!(crate::hash::sha512_crypt("passwd-3", salt, None), hash);
+
+    let table = [
+        (
+            vec!["--chpasswd", "--hash", "bcrypt"],
+            "root",
+            "--chpasswd prints either passwords or hashes, add --hash-only for hashes",
+        ),
+        (
+            vec!["--chpasswd"],
+            "root\nbad:user",
+            "invalid username 'bad:user' at line 2",
+        ),
+        (
+            vec!["--chpasswd"],
+            "bad user",
+            "invalid username 'bad user' at line 1",
+        ),
+        (
+            vec!["--chpasswd", "derive", "--site", "example.com"],
+            "root",
+            "--chpasswd can't be combined with subcommands",
+        ),
+    ];
+
+    for (args, input, expected) in table {
+        let cli = Cli::try_parse_from([&["xkpasswd"], &args[..]].concat()).unwrap();
+        assert_eq!(
+            Err(expected.to_string()),
+            cli.output_chpasswd(input.as_bytes(), io::sink(), &mut gen_pass),
+            "{:?}",
+            args
+        );
+    }
+}
+
+#[test]
+fn test_cli_parse_qr() {
+    let cli = Cli::try_parse_from(["xkpasswd", "-P", "wifi", "--qr", "--ssid", "Guests"]).unwrap();
+    assert!(cli.qr);
+    assert_eq!(Some("Guests".to_string()), cli.ssid);
+    assert_eq!(WifiSecurity::Wpa, cli.wifi_security);
+
+    let cli = Cli::try_parse_from([
+        "xkpasswd",
+        "--qr-file",
+        "wifi.svg",
+        "--ssid",
+        "Office",
+        "--wifi-security",
+        "wep",
+    ])
+    .unwrap();
+    assert_eq!(Some("wifi.svg".to_string()), cli.qr_file);
+    assert_eq!(WifiSecurity::Wep, cli.wifi_security);
+
+    // the SSID is required
+    assert!(Cli::try_parse_from(["xkpasswd", "--qr"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--qr", "--ssid", "a", "--chpasswd"]).is_err());
+}
+
+#[test]
+fn test_wifi_qr() {
+    let passwd = "correct;horse,battery:staple";
+    let cli = Cli::try_parse_from(["xkpasswd"]).unwrap();
+    assert!(cli.wifi_qr(passwd).unwrap().is_none());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--qr", "--ssid", "Guests"]).unwrap();
+    assert!(cli.wifi_qr(passwd).unwrap().is_some());
+    assert_eq!(
+        Some("WPA passwords must be 8 to 63 printable ASCII characters".to_string()),
+        cli.wifi_qr("short").err()
+    );
+
+    let cli =
+        Cli::try_parse_from(["xkpasswd", "--qr-file", "wifi.jpg", "--ssid", "Guests"]).unwrap();
+    assert_eq!(
+        Some("unsupported image 'wifi.jpg', expect a .png or .svg file".to_string()),
+        cli.wifi_qr(passwd).err()
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let wifi_qr = WifiQr::new("Guests", WifiSecurity::Wpa, passwd).unwrap();
+
+    let svg_path = dir.path().join("wifi.SVG");
+    save_qr_file(&wifi_qr, svg_path.to_str().unwrap()).unwrap();
+    assert_eq!(wifi_qr.to_svg(), fs::read_to_string(&svg_path).unwrap());
+
+    let png_path = dir.path().join("wifi.png");
+    save_qr_file(&wifi_qr, png_path.to_str().unwrap()).unwrap();
+    assert!(fs::read(&png_path).unwrap().starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn test_cli_speller() {
+    let cli = Cli::try_parse_from(["xkpasswd"]).unwrap();
+    assert_eq!(None, cli.speller());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "-z", "de", "-z", "en", "--spell"]).unwrap();
+    assert_eq!(Some(Speller::new(Language::German)), cli.speller());
+
+    let cli = Cli::try_parse_from(["xkpasswd", "--spell", "--nato"]).unwrap();
+    assert_eq!(
+        Some(Speller::new(Language::English).with_nato(true)),
+        cli.speller()
+    );
+
+    assert!(Cli::try_parse_from(["xkpasswd", "--nato"]).is_err());
+    assert!(Cli::try_parse_from(["xkpasswd", "--spell", "--chpasswd"]).is_err());
 }
@@ -1,7 +1,9 @@
 use super::*;
-use crate::bit_flags::*;
+use crate::settings::Settings;
 use clap::ValueEnum;
-use std::fs;
+use std::collections::BTreeSet;
+#[cfg(feature = "tui")]
+use std::path::PathBuf;
 
 const CONFIG_FILE_NAME: &str = "xkpasswd.toml";
 
@@ -14,90 +16,279 @@ pub enum ConfigParseError {
 
 pub trait ConfigParser {
     fn parse_config_file(&mut self) -> Result<(), ConfigParseError>;
+    fn parse_config(&mut self, config: &toml::Value) -> Result<(), ConfigParseError>;
+    #[cfg(feature = "tui")]
+    fn save_config_file(&self) -> Result<String, String>;
 }
 
 impl ConfigParser for Cli {
     fn parse_config_file(&mut self) -> Result<(), ConfigParseError> {
         let config = read_config_file(&self.config_file)?;
+        self.parse_config(&config)
+    }
 
-        parse_number_config(
-            self.words_count.is_some(),
-            &config,
-            "words_count",
-            |value| self.words_count = Some(value as u8),
-        );
+    /// Fields of a parsed config, without overriding the ones set by command line options.
+    fn parse_config(&mut self, config: &toml::Value) -> Result<(), ConfigParseError> {
+        parse_number_config(self.words_count.is_some(), config, "words_count", |value| {
+            self.words_count = Some(value as u8)
+        });
 
         parse_number_config(
             self.word_length_min.is_some(),
-            &config,
+            config,
             "word_min",
             |value| self.word_length_min = Some(value as u8),
         );
 
         parse_number_config(
             self.word_length_max.is_some(),
-            &config,
+            config,
             "word_max",
             |value| self.word_length_max = Some(value as u8),
         );
 
-        parse_transforms(self.word_transforms.is_some(), &config, |transforms| {
-            self.word_transforms = Some(transforms)
-        })?;
+        parse_enum_arr_config(
+            self.word_transforms.is_some(),
+            config,
+            "transforms",
+            |transforms| self.word_transforms = Some(transforms),
+        )?;
 
-        parse_str_config(self.separators.is_some(), &config, "separators", |value| {
+        parse_str_config(self.separators.is_some(), config, "separators", |value| {
             self.separators = Some(value)
         });
 
         parse_number_config(
             self.padding_digits_before.is_some(),
-            &config,
+            config,
             "digits_before",
             |value| self.padding_digits_before = Some(value as u8),
         );
 
         parse_number_config(
             self.padding_digits_after.is_some(),
-            &config,
+            config,
             "digits_after",
             |value| self.padding_digits_after = Some(value as u8),
         );
 
-        parse_str_config(
-            self.padding_symbols.is_some(),
-            &config,
-            "symbols",
-            |value| self.padding_symbols = Some(value),
-        );
+        parse_str_config(self.padding_symbols.is_some(), config, "symbols", |value| {
+            self.padding_symbols = Some(value)
+        });
 
         parse_number_config(
             self.padding_symbols_before.is_some(),
-            &config,
+            config,
             "symbols_before",
             |value| self.padding_symbols_before = Some(value as u8),
         );
 
         parse_number_config(
             self.padding_symbols_after.is_some(),
-            &config,
+            config,
             "symbols_after",
             |value| self.padding_symbols_after = Some(value as u8),
         );
 
-        parse_enum_config(self.padding.is_some(), &config, "padding", |value| {
+        parse_enum_config(self.padding.is_some(), config, "padding", |value| {
             self.padding = Some(value)
         })?;
 
-        parse_enum_config(self.preset.is_some(), &config, "preset", |value| {
-            self.preset = Some(value)
+        parse_number_config(
+            self.adaptive_length.is_some(),
+            config,
+            "adaptive_length",
+            |value| self.adaptive_length = Some(value as usize),
+        );
+
+        parse_str_arr_config(
+            self.excluded_words.is_some(),
+            config,
+            "exclude_words",
+            |values| self.excluded_words = Some(values),
+        )?;
+
+        parse_str_config(
+            self.exclude_file.is_some(),
+            config,
+            "exclude_file",
+            |value| self.exclude_file = Some(value),
+        );
+
+        parse_str_arr_config(
+            self.included_words.is_some(),
+            config,
+            "include_words",
+            |values| self.included_words = Some(values),
+        )?;
+
+        parse_str_config(
+            self.include_file.is_some(),
+            config,
+            "include_file",
+            |value| self.include_file = Some(value),
+        );
+
+        parse_bool_config(self.readable, config, "readable", |value| {
+            self.readable = value
+        });
+
+        parse_enum_config(self.keyboard.is_some(), config, "keyboard", |value| {
+            self.keyboard = Some(value)
         })?;
 
-        parse_enum_config(self.language.is_some(), &config, "lang", |value| {
-            self.language = Some(value)
+        parse_enum_config(self.preset.is_some(), config, "preset", |value| {
+            self.preset = Some(value)
         })?;
 
+        // a single language is also accepted for backward compatibility
+        if config.get_str("lang").is_some() {
+            parse_enum_config(self.languages.is_some(), config, "lang", |value| {
+                self.languages = Some(vec![value])
+            })?;
+        } else {
+            parse_enum_arr_config(self.languages.is_some(), config, "lang", |values| {
+                self.languages = Some(values)
+            })?;
+        }
+
         Ok(())
     }
+
+    #[cfg(feature = "tui")]
+    fn save_config_file(&self) -> Result<String, String> {
+        let config_path = match &self.config_file {
+            Some(config_file) => PathBuf::from(config_file),
+            None => lookup_default_config_path()
+                .map(PathBuf::from)
+                .or_else(|| dirs::config_dir().map(|dir| dir.join(CONFIG_FILE_NAME)))
+                .ok_or("unable to locate config directory")?,
+        };
+
+        // other fields of an existing config, e.g. words filters, are kept
+        let mut config: toml::Table = fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|data| toml::from_str(&data).ok())
+            .unwrap_or_default();
+        config.extend(config_values(self));
+
+        let data = toml::to_string(&config).map_err(|err| err.to_string())?;
+        fs::write(&config_path, data).map_err(|err| err.to_string())?;
+        log::debug!("saved config file to {}", config_path.display());
+
+        Ok(config_path.display().to_string())
+    }
+}
+
+#[cfg(feature = "tui")]
+fn config_values(cli: &Cli) -> toml::Table {
+    let mut config = toml::Table::new();
+    let mut set_value = |field: &str, value: Option<toml::Value>| {
+        if let Some(value) = value {
+            config.insert(field.to_string(), value);
+        }
+    };
+
+    let number = |value: Option<u8>| value.map(|value| toml::Value::Integer(value as i64));
+    let string = |value: &Option<String>| value.clone().map(toml::Value::String);
+
+    set_value("words_count", number(cli.words_count));
+    set_value("word_min", number(cli.word_length_min));
+    set_value("word_max", number(cli.word_length_max));
+    set_value("transforms", cli.word_transforms.as_deref().map(enum_array));
+    set_value("separators", string(&cli.separators));
+    set_value("digits_before", number(cli.padding_digits_before));
+    set_value("digits_after", number(cli.padding_digits_after));
+    set_value("symbols", string(&cli.padding_symbols));
+    set_value("symbols_before", number(cli.padding_symbols_before));
+    set_value("symbols_after", number(cli.padding_symbols_after));
+    set_value("padding", cli.padding.as_ref().map(enum_value));
+    set_value(
+        "adaptive_length",
+        cli.adaptive_length
+            .map(|value| toml::Value::Integer(value as i64)),
+    );
+    set_value("readable", Some(toml::Value::Boolean(cli.readable)));
+    set_value("keyboard", cli.keyboard.as_ref().map(enum_value));
+    set_value("preset", cli.preset.as_ref().map(enum_value));
+    set_value("lang", cli.languages.as_deref().map(enum_array));
+
+    config
+}
+
+/// Effective settings in the config file format, with every field set.
+pub fn dump_config(settings: &Settings, languages: &[Language]) -> Result<String, String> {
+    let mut config = toml::Table::new();
+    let mut set_value = |field: &str, value: toml::Value| {
+        config.insert(field.to_string(), value);
+    };
+
+    let number = |value: u8| toml::Value::Integer(value as i64);
+    let string = |value: &str| toml::Value::String(value.to_string());
+    let words = |words: &BTreeSet<String>| {
+        toml::Value::Array(words.iter().map(|word| string(word)).collect())
+    };
+
+    let word_lengths = settings.word_lengths();
+    let (digits_before, digits_after) = settings.padding_digits();
+    let (symbols_before, symbols_after) = settings.padding_symbol_lengths();
+
+    set_value("words_count", number(settings.words_count()));
+    set_value("word_min", number(word_lengths.start));
+    set_value("word_max", number(word_lengths.end - 1));
+    set_value(
+        "transforms",
+        toml::Value::Array(
+            settings
+                .word_transforms()
+                .to_flags()
+                .iter()
+                .map(|transform| string(transform.name()))
+                .collect(),
+        ),
+    );
+    set_value("separators", string(settings.separators()));
+    set_value("digits_before", number(digits_before));
+    set_value("digits_after", number(digits_after));
+    set_value("symbols", string(settings.padding_symbols()));
+    set_value("symbols_before", number(symbols_before));
+    set_value("symbols_after", number(symbols_after));
+
+    match settings.padding_strategy() {
+        PaddingStrategy::Fixed => set_value("padding", enum_value(&CliPadding::Fixed)),
+        PaddingStrategy::Adaptive(length) => {
+            set_value("padding", enum_value(&CliPadding::Adaptive));
+            set_value("adaptive_length", toml::Value::Integer(length as i64));
+        }
+    }
+
+    if !settings.excluded_words().is_empty() {
+        set_value("exclude_words", words(settings.excluded_words()));
+    }
+
+    if !settings.included_words().is_empty() {
+        set_value("include_words", words(settings.included_words()));
+    }
+
+    set_value("readable", toml::Value::Boolean(settings.readable()));
+    if let Some(keyboard) = settings.keyboard() {
+        set_value("keyboard", enum_value(&keyboard));
+    }
+    set_value("lang", enum_array(languages));
+
+    toml::to_string(&config).map_err(|err| err.to_string())
+}
+
+fn enum_value<T: ValueEnum>(value: &T) -> toml::Value {
+    let name = value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default();
+    toml::Value::String(name)
+}
+
+fn enum_array<T: ValueEnum>(values: &[T]) -> toml::Value {
+    toml::Value::Array(values.iter().map(enum_value).collect())
 }
 
 fn lookup_default_config_path() -> Option<String> {
@@ -175,43 +366,64 @@ fn parse_enum_config<T: ValueEnum, F: FnMut(T)>(
     }
 }
 
-fn parse_transforms<F: FnMut(Vec<WordTransform>)>(
+fn parse_enum_arr_config<T: ValueEnum, F: FnMut(Vec<T>)>(
     ignore: bool,
     config: &toml::Value,
+    field: &str,
     mut callback: F,
 ) -> Result<(), ConfigParseError> {
-    let field = "transforms";
-
     if ignore {
         log::debug!("loading '{}' from command arguments", field);
         return Ok(());
     }
 
-    let raw_transforms = match config.get_str_arr(field) {
+    let raw_values = match config.get_str_arr(field) {
         Err(err) => {
             return match err {
                 ConfigParseError::Ignore => Ok(()),
                 _ => Err(err),
             }
         }
-        Ok(transforms) => transforms,
+        Ok(values) => values,
     };
 
-    let parsed_transforms: Result<Vec<WordTransform>, String> = raw_transforms
+    let parsed_values: Result<Vec<T>, String> = raw_values
         .iter()
-        .map(|transform| WordTransform::from_str(transform, true))
+        .map(|value| T::from_str(value, true))
         .collect();
 
-    match parsed_transforms {
-        Ok(word_transforms) => {
+    match parsed_values {
+        Ok(values) => {
             log::debug!("loading '{}' from config file", field);
-            callback(word_transforms);
+            callback(values);
             Ok(())
         }
-        Err(err) => Err(ConfigParseError::InvalidConfig(
-            "transforms".to_string(),
-            err,
-        )),
+        Err(err) => Err(ConfigParseError::InvalidConfig(field.to_string(), err)),
+    }
+}
+
+fn parse_str_arr_config<F: FnMut(Vec<String>)>(
+    ignore: bool,
+    config: &toml::Value,
+    field: &str,
+    mut callback: F,
+) -> Result<(), ConfigParseError> {
+    if ignore {
+        log::debug!("loading '{}' from command arguments", field);
+        return Ok(());
+    }
+
+    match config.get_str_arr(field) {
+        Ok(values) => {
+            callback(values.into_iter().map(String::from).collect());
+            log::debug!("loading '{}' from config file", field);
+            Ok(())
+        }
+        Err(ConfigParseError::Ignore) => {
+            log::debug!("loading default value for '{}'", field);
+            Ok(())
+        }
+        Err(err) => Err(err),
     }
 }
 
@@ -235,6 +447,26 @@ fn parse_str_config<F: FnMut(String)>(
     }
 }
 
+fn parse_bool_config<F: FnMut(bool)>(
+    ignore: bool,
+    config: &toml::Value,
+    field: &str,
+    mut callback: F,
+) {
+    if ignore {
+        log::debug!("loading '{}' from command arguments", field);
+        return;
+    }
+
+    match config.get_bool(field) {
+        Some(value) => {
+            callback(value);
+            log::debug!("loading '{}' from config file", field);
+        }
+        None => log::debug!("loading default value for '{}'", field),
+    }
+}
+
 fn parse_number_config<F: FnMut(u64)>(
     ignore: bool,
     config: &toml::Value,
@@ -260,18 +492,19 @@ trait Getter {
     fn get_array<'a>(&'a self, field: &'a str) -> Option<&'a Vec<toml::Value>>;
     fn get_str<'a>(&'a self, field: &str) -> Option<&'a str>;
     fn get_number(&self, field: &str) -> Option<u64>;
+    fn get_bool(&self, field: &str) -> Option<bool>;
 }
 
 impl Getter for toml::Value {
     fn get_str_arr<'a>(&'a self, field: &'a str) -> Result<Vec<&'a str>, ConfigParseError> {
         match self.get_array(field) {
-            Some(transforms) => {
-                let result: Result<Vec<&str>, String> = transforms
+            Some(values) => {
+                let result: Result<Vec<&str>, String> = values
                     .iter()
-                    .map(|transform| {
-                        transform.as_str().ok_or(format!(
+                    .map(|value| {
+                        value.as_str().ok_or(format!(
                             "Invalid data type, expect string but got '{}'",
-                            transform
+                            value
                         ))
                     })
                     .collect();
@@ -300,6 +533,10 @@ impl Getter for toml::Value {
             .as_integer()
             .map(|data| data.unsigned_abs())
     }
+
+    fn get_bool(&self, field: &str) -> Option<bool> {
+        self.get(field)?.as_bool()
+    }
 }
 
 #[cfg(test)]
@@ -308,6 +545,42 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_dump_config() {
+        let settings = Settings::from_preset(Preset::Wifi)
+            .with_excluded_words(&["foo"])
+            .with_keyboard(Some(Keyboard::Android));
+        let data = dump_config(&settings, &[Language::English, Language::German]).unwrap();
+        let config: toml::Value = toml::from_str(&data).unwrap();
+
+        assert_eq!(Some(6), config.get_number("words_count"));
+        assert_eq!(Some(4), config.get_number("word_min"));
+        assert_eq!(Some(8), config.get_number("word_max"));
+        assert_eq!(
+            vec!["lowercase", "uppercase"],
+            config.get_str_arr("transforms").unwrap()
+        );
+        assert_eq!(Some("adaptive"), config.get_str("padding"));
+        assert_eq!(Some(63), config.get_number("adaptive_length"));
+        assert_eq!(vec!["foo"], config.get_str_arr("exclude_words").unwrap());
+        assert_eq!(None, config.get_array("include_words"));
+        assert_eq!(Some(false), config.get_bool("readable"));
+        assert_eq!(Some("android"), config.get_str("keyboard"));
+        assert_eq!(vec!["en", "de"], config.get_str_arr("lang").unwrap());
+
+        // dumped config files build the same settings
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", data).unwrap();
+
+        let mut cli = Cli::try_parse_from(["xkpasswd"]).unwrap();
+        cli.config_file = Some(temp_file.path().to_str().unwrap().to_string());
+        cli.parse_config_file().unwrap();
+        assert_eq!(
+            Ok(settings),
+            cli.build_settings(&[3, 4, 5, 6, 7, 8, 9, 10, 11, 12])
+        );
+    }
+
     #[test]
     fn test_parse_preset_config() {
         let table = [
@@ -395,22 +668,30 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_transforms() {
+    fn test_parse_enum_arr_config() {
         let config: toml::Value = toml::from_str(r#"transforms = ["lowercase"]"#).unwrap();
-        let result = parse_transforms(true, &config, |_| panic!("shouldn't be invoked"));
+        let result = parse_enum_arr_config(true, &config, "transforms", |_: Vec<WordTransform>| {
+            panic!("shouldn't be invoked")
+        });
         assert!(matches!(result, Ok(())));
 
-        let result = parse_transforms(false, &config, |value| {
+        let result = parse_enum_arr_config(false, &config, "transforms", |value| {
             assert_eq!(vec![WordTransform::Lowercase], value)
         });
         assert!(matches!(result, Ok(())));
 
         let config: toml::Value = toml::from_str(r#"transforms = "lowercase""#).unwrap();
-        let result = parse_transforms(false, &config, |_| panic!("shouldn't be invoked"));
+        let result =
+            parse_enum_arr_config(false, &config, "transforms", |_: Vec<WordTransform>| {
+                panic!("shouldn't be invoked")
+            });
         assert!(matches!(result, Ok(())));
 
         let config: toml::Value = toml::from_str(r#"transforms = ["lowercase", false]"#).unwrap();
-        let result = parse_transforms(false, &config, |_| panic!("shouldn't be invoked"));
+        let result =
+            parse_enum_arr_config(false, &config, "transforms", |_: Vec<WordTransform>| {
+                panic!("shouldn't be invoked")
+            });
         if let ConfigParseError::InvalidConfig(field, message) = result.err().unwrap() {
             assert_eq!("transforms", field);
             assert_eq!("Invalid data type, expect string but got 'false'", message);
@@ -420,7 +701,10 @@ mod tests {
 
         let config: toml::Value =
             toml::from_str(r#"transforms = ["lowercase", "inversed_titlecase"]"#).unwrap();
-        let result = parse_transforms(false, &config, |_| panic!("shouldn't be invoked"));
+        let result =
+            parse_enum_arr_config(false, &config, "transforms", |_: Vec<WordTransform>| {
+                panic!("shouldn't be invoked")
+            });
         if let ConfigParseError::InvalidConfig(field, message) = result.err().unwrap() {
             assert_eq!("transforms", field);
             assert_eq!("invalid variant: inversed_titlecase", message);
@@ -429,6 +713,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_str_arr_config() {
+        let config: toml::Value = toml::from_str(r#"exclude_words = ["foo", "bar"]"#).unwrap();
+        let result = parse_str_arr_config(true, &config, "exclude_words", |_| {
+            panic!("shouldn't be invoked")
+        });
+        assert!(matches!(result, Ok(())));
+
+        let result =
+            parse_str_arr_config(false, &config, "foo", |_| panic!("shouldn't be invoked"));
+        assert!(matches!(result, Ok(())));
+
+        let result = parse_str_arr_config(false, &config, "exclude_words", |value| {
+            assert_eq!(vec!["foo".to_string(), "bar".to_string()], value)
+        });
+        assert!(matches!(result, Ok(())));
+
+        let config: toml::Value = toml::from_str(r#"exclude_words = ["foo", 1]"#).unwrap();
+        let result = parse_str_arr_config(false, &config, "exclude_words", |_| {
+            panic!("shouldn't be invoked")
+        });
+        assert!(matches!(
+            result,
+            Err(ConfigParseError::InvalidConfig(field, _)) if field == "exclude_words"
+        ));
+    }
+
     #[test]
     fn test_parse_str_config() {
         let config: toml::Value = toml::from_str(r#"separators = "!@#""#).unwrap();
@@ -476,6 +787,12 @@ symbols_after = 2
 preset = "web32"
 lang = "de"
 transforms = ["lowercase", "uppercase"]
+exclude_words = ["foo", "bar"]
+exclude_file = "excluded.txt"
+include_words = ["baz"]
+include_file = "included.txt"
+readable = true
+keyboard = "pt-br"
 "#
         )
         .unwrap();
@@ -494,9 +811,33 @@ transforms = ["lowercase", "uppercase"]
             padding_symbols_after: None,
             padding: None,
             adaptive_length: None,
+            excluded_words: None,
+            exclude_file: None,
+            included_words: None,
+            include_file: None,
+            readable: false,
+            keyboard: None,
+            dice_rolls: None,
+            dice_table: false,
             preset: None,
+            recipe: None,
             verbosity: 0,
-            language: None,
+            languages: None,
+            interactive: false,
+            stdio: false,
+            clipboard: false,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            print: false,
+            hash: None,
+            hash_only: false,
+            chpasswd: false,
+            qr: false,
+            qr_file: None,
+            ssid: None,
+            wifi_security: WifiSecurity::Wpa,
+            spell: false,
+            nato: false,
+            command: None,
         };
 
         let result = cli.parse_config_file();
@@ -513,11 +854,57 @@ transforms = ["lowercase", "uppercase"]
         assert_eq!(Some(1), cli.padding_symbols_before);
         assert_eq!(Some(2), cli.padding_symbols_after);
         assert!(matches!(cli.preset, Some(Preset::Web32)));
-        assert!(matches!(cli.language, Some(Language::German)));
+        assert_eq!(Some(vec![Language::German]), cli.languages);
         assert_eq!(
             Some(vec![WordTransform::Lowercase, WordTransform::Uppercase]),
             cli.word_transforms
         );
+        assert_eq!(
+            Some(vec!["foo".to_string(), "bar".to_string()]),
+            cli.excluded_words
+        );
+        assert_eq!(Some("excluded.txt".to_string()), cli.exclude_file);
+        assert_eq!(Some(vec!["baz".to_string()]), cli.included_words);
+        assert_eq!(Some("included.txt".to_string()), cli.include_file);
+        assert!(cli.readable);
+        assert_eq!(Some(Keyboard::PtBr), cli.keyboard);
+    }
+
+    #[test]
+    fn test_parse_config_file_with_languages() {
+        let table = [
+            (r#"lang = "fr""#, vec![Language::French]),
+            (
+                r#"lang = ["en", "de"]"#,
+                vec![Language::English, Language::German],
+            ),
+        ];
+
+        for (config, expected) in table {
+            let mut temp_file = NamedTempFile::new().unwrap();
+            writeln!(temp_file, "{}", config).unwrap();
+
+            let mut cli =
+                Cli::try_parse_from(["xkpasswd", "--config", temp_file.path().to_str().unwrap()])
+                    .unwrap();
+
+            let result = cli.parse_config_file();
+            assert!(result.is_ok());
+            assert_eq!(Some(expected), cli.languages);
+        }
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, r#"lang = ["en", "xx"]"#).unwrap();
+
+        let mut cli =
+            Cli::try_parse_from(["xkpasswd", "--config", temp_file.path().to_str().unwrap()])
+                .unwrap();
+
+        let result = cli.parse_config_file();
+        assert!(matches!(
+            result,
+            Err(ConfigParseError::InvalidConfig(field, _)) if field == "lang"
+        ));
     }
 
     #[test]
@@ -541,9 +928,33 @@ transforms = ["lowercase", "uppercase"]
             padding_symbols_after: None,
             padding: None,
             adaptive_length: None,
+            excluded_words: None,
+            exclude_file: None,
+            included_words: None,
+            include_file: None,
+            readable: false,
+            keyboard: None,
+            dice_rolls: None,
+            dice_table: false,
             preset: None,
+            recipe: None,
             verbosity: 0,
-            language: None,
+            languages: None,
+            interactive: false,
+            stdio: false,
+            clipboard: false,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            print: false,
+            hash: None,
+            hash_only: false,
+            chpasswd: false,
+            qr: false,
+            qr_file: None,
+            ssid: None,
+            wifi_security: WifiSecurity::Wpa,
+            spell: false,
+            nato: false,
+            command: None,
         };
 
         let result = cli.parse_config_file();
@@ -569,9 +980,33 @@ transforms = ["lowercase", "uppercase"]
             padding_symbols_after: None,
             padding: None,
             adaptive_length: None,
+            excluded_words: None,
+            exclude_file: None,
+            included_words: None,
+            include_file: None,
+            readable: false,
+            keyboard: None,
+            dice_rolls: None,
+            dice_table: false,
             preset: None,
+            recipe: None,
             verbosity: 0,
-            language: None,
+            languages: None,
+            interactive: false,
+            stdio: false,
+            clipboard: false,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            print: false,
+            hash: None,
+            hash_only: false,
+            chpasswd: false,
+            qr: false,
+            qr_file: None,
+            ssid: None,
+            wifi_security: WifiSecurity::Wpa,
+            spell: false,
+            nato: false,
+            command: None,
         };
 
         let result = cli.parse_config_file();
@@ -597,9 +1032,33 @@ transforms = ["lowercase", "uppercase"]
             padding_symbols_after: None,
             padding: None,
             adaptive_length: None,
+            excluded_words: None,
+            exclude_file: None,
+            included_words: None,
+            include_file: None,
+            readable: false,
+            keyboard: None,
+            dice_rolls: None,
+            dice_table: false,
             preset: None,
+            recipe: None,
             verbosity: 0,
-            language: None,
+            languages: None,
+            interactive: false,
+            stdio: false,
+            clipboard: false,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            print: false,
+            hash: None,
+            hash_only: false,
+            chpasswd: false,
+            qr: false,
+            qr_file: None,
+            ssid: None,
+            wifi_security: WifiSecurity::Wpa,
+            spell: false,
+            nato: false,
+            command: None,
         };
 
         let result = cli.parse_config_file();
@@ -626,9 +1085,33 @@ transforms = ["lowercase", "uppercase"]
             padding_symbols_after: None,
             padding: None,
             adaptive_length: None,
+            excluded_words: None,
+            exclude_file: None,
+            included_words: None,
+            include_file: None,
+            readable: false,
+            keyboard: None,
+            dice_rolls: None,
+            dice_table: false,
             preset: None,
+            recipe: None,
             verbosity: 0,
-            language: None,
+            languages: None,
+            interactive: false,
+            stdio: false,
+            clipboard: false,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            print: false,
+            hash: None,
+            hash_only: false,
+            chpasswd: false,
+            qr: false,
+            qr_file: None,
+            ssid: None,
+            wifi_security: WifiSecurity::Wpa,
+            spell: false,
+            nato: false,
+            command: None,
         };
 
         let result = cli.parse_config_file();
@@ -664,9 +1147,33 @@ separators = 123
             padding_symbols_after: None,
             padding: None,
             adaptive_length: None,
+            excluded_words: None,
+            exclude_file: None,
+            included_words: None,
+            include_file: None,
+            readable: false,
+            keyboard: None,
+            dice_rolls: None,
+            dice_table: false,
             preset: None,
+            recipe: None,
             verbosity: 0,
-            language: None,
+            languages: None,
+            interactive: false,
+            stdio: false,
+            clipboard: false,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            print: false,
+            hash: None,
+            hash_only: false,
+            chpasswd: false,
+            qr: false,
+            qr_file: None,
+            ssid: None,
+            wifi_security: WifiSecurity::Wpa,
+            spell: false,
+            nato: false,
+            command: None,
         };
 
         // Should succeed - type mismatches are silently ignored (use defaults)
@@ -697,9 +1204,33 @@ separators = 123
             padding_symbols_after: None,
             padding: None,
             adaptive_length: None,
+            excluded_words: None,
+            exclude_file: None,
+            included_words: None,
+            include_file: None,
+            readable: false,
+            keyboard: None,
+            dice_rolls: None,
+            dice_table: false,
             preset: None,
+            recipe: None,
             verbosity: 0,
-            language: None,
+            languages: None,
+            interactive: false,
+            stdio: false,
+            clipboard: false,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            print: false,
+            hash: None,
+            hash_only: false,
+            chpasswd: false,
+            qr: false,
+            qr_file: None,
+            ssid: None,
+            wifi_security: WifiSecurity::Wpa,
+            spell: false,
+            nato: false,
+            command: None,
         };
 
         let result = cli.parse_config_file();
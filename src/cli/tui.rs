@@ -0,0 +1,671 @@
+use super::*;
+use crate::secret::Passwd;
+use crate::settings::Settings;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::Frame;
+use std::io;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Preset,
+    Languages,
+    Keyboard,
+    WordsCount,
+    WordLengthMin,
+    WordLengthMax,
+    WordTransforms,
+    Separators,
+    DigitsBefore,
+    DigitsAfter,
+    PaddingSymbols,
+    SymbolsBefore,
+    SymbolsAfter,
+    Padding,
+    AdaptiveLength,
+    Readable,
+}
+
+const FIELDS: [Field; 16] = [
+    Field::Preset,
+    Field::Languages,
+    Field::Keyboard,
+    Field::WordsCount,
+    Field::WordLengthMin,
+    Field::WordLengthMax,
+    Field::WordTransforms,
+    Field::Separators,
+    Field::DigitsBefore,
+    Field::DigitsAfter,
+    Field::PaddingSymbols,
+    Field::SymbolsBefore,
+    Field::SymbolsAfter,
+    Field::Padding,
+    Field::AdaptiveLength,
+    Field::Readable,
+];
+
+const TRANSFORMS_OPTIONS: [&[WordTransform]; 8] = [
+    &[WordTransform::Lowercase, WordTransform::Uppercase],
+    &[WordTransform::Lowercase],
+    &[WordTransform::Titlecase],
+    &[WordTransform::Uppercase],
+    &[WordTransform::InversedTitlecase],
+    &[WordTransform::Titlecase, WordTransform::InversedTitlecase],
+    &[WordTransform::AltercaseLowerFirst],
+    &[WordTransform::AltercaseUpperFirst],
+];
+
+const SEPARATORS_OPTIONS: [&str; 7] = [".-_~", "-", ".", "_", " ", "~@$%^&*-_+=:|?/.;", ""];
+const SYMBOLS_OPTIONS: [&str; 5] = ["~@$%^&*-_+=:|?/.;", "!@#$%^&*", "-_=+", "?!", "."];
+const DEFAULT_ADAPTIVE_LENGTH: usize = 32;
+
+/// Interactive mode to explore settings & regenerate passwords.
+///
+/// All the settings are kept as CLI arguments, so that the same validations apply
+/// and they can be saved to the config file as is.
+pub struct Tui {
+    cli: Cli,
+    pass_generator: Xkpasswd,
+    settings: Settings,
+    passwd: Passwd,
+    entropy: Entropy,
+    selected: usize,
+    status: String,
+}
+
+impl Tui {
+    pub fn new(mut cli: Cli) -> Result<Self, String> {
+        let pass_generator = Xkpasswd::for_languages(&cli.languages());
+        let settings = cli.build_settings::<Settings>(&pass_generator.source().word_lengths())?;
+        resolve_cli(&mut cli, &settings);
+
+        let mut tui = Tui {
+            cli,
+            pass_generator,
+            settings,
+            passwd: Passwd::default(),
+            entropy: Entropy::default(),
+            selected: 0,
+            status: String::new(),
+        };
+        tui.regenerate();
+
+        Ok(tui)
+    }
+
+    pub fn run(mut self) -> io::Result<()> {
+        let mut terminal = ratatui::init();
+
+        let result = loop {
+            if let Err(err) = terminal.draw(|frame| self.render(frame)) {
+                break Err(err);
+            }
+
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    if !self.handle_key(key.code) {
+                        break Ok(());
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => break Err(err),
+            }
+        };
+
+        ratatui::restore();
+        result
+    }
+
+    // returns false when quitting
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + FIELDS.len() - 1) % FIELDS.len()
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1) % FIELDS.len()
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('-') => self.adjust(-1),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('+') => self.adjust(1),
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('r') => {
+                self.status.clear();
+                self.regenerate();
+            }
+            KeyCode::Char('s') => {
+                self.status = match self.cli.save_config_file() {
+                    Ok(path) => format!("Saved to {}", path),
+                    Err(err) => format!("Unable to save config file: {}", err),
+                }
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    fn adjust(&mut self, delta: i8) {
+        let mut cli = self.cli.clone();
+        let field = FIELDS[self.selected];
+
+        match field {
+            Field::Preset => {
+                let presets = Preset::value_variants();
+                let index = cli
+                    .preset
+                    .and_then(|preset| presets.iter().position(|&item| item == preset));
+                cli = Cli {
+                    preset: Some(presets[cycle(index, presets.len(), delta)]),
+                    ..Self::reset_cli(&cli)
+                };
+            }
+            Field::Languages => {
+                let languages = Language::value_variants();
+                let index = languages
+                    .iter()
+                    .position(|&item| item == cli.languages()[0]);
+                cli.languages = Some(vec![languages[cycle(index, languages.len(), delta)]]);
+            }
+            Field::Keyboard => {
+                let index = Keyboard::ALL
+                    .iter()
+                    .position(|&item| item == cli.keyboard());
+                cli.keyboard = Some(Keyboard::ALL[cycle(index, Keyboard::ALL.len(), delta)]);
+            }
+            Field::WordsCount => cli.words_count = step(cli.words_count, delta),
+            Field::WordLengthMin => cli.word_length_min = step(cli.word_length_min, delta),
+            Field::WordLengthMax => cli.word_length_max = step(cli.word_length_max, delta),
+            Field::WordTransforms => {
+                let index = TRANSFORMS_OPTIONS
+                    .iter()
+                    .position(|&option| Some(option) == cli.word_transforms.as_deref());
+                let index = cycle(index, TRANSFORMS_OPTIONS.len(), delta);
+                cli.word_transforms = Some(TRANSFORMS_OPTIONS[index].to_vec());
+            }
+            Field::Separators => {
+                cli.separators = next_option(&SEPARATORS_OPTIONS, &cli.separators, delta)
+            }
+            Field::DigitsBefore => {
+                cli.padding_digits_before = step(cli.padding_digits_before, delta)
+            }
+            Field::DigitsAfter => cli.padding_digits_after = step(cli.padding_digits_after, delta),
+            Field::PaddingSymbols => {
+                cli.padding_symbols = next_option(&SYMBOLS_OPTIONS, &cli.padding_symbols, delta)
+            }
+            Field::SymbolsBefore => {
+                cli.padding_symbols_before = step(cli.padding_symbols_before, delta)
+            }
+            Field::SymbolsAfter => {
+                cli.padding_symbols_after = step(cli.padding_symbols_after, delta)
+            }
+            Field::Padding => match cli.padding {
+                Some(CliPadding::Adaptive) => {
+                    cli.padding = Some(CliPadding::Fixed);
+                    cli.adaptive_length = None;
+                }
+                _ => {
+                    cli.padding = Some(CliPadding::Adaptive);
+                    cli.adaptive_length = Some(DEFAULT_ADAPTIVE_LENGTH);
+                }
+            },
+            Field::AdaptiveLength => {
+                if !matches!(cli.padding, Some(CliPadding::Adaptive)) {
+                    self.status = "Adaptive length only applies to adaptive padding".to_string();
+                    return;
+                }
+
+                let length = cli.adaptive_length.unwrap_or(DEFAULT_ADAPTIVE_LENGTH);
+                cli.adaptive_length = Some(length.saturating_add_signed(delta as isize).max(1));
+            }
+            Field::Readable => cli.readable = !cli.readable,
+        }
+
+        self.apply(cli, field == Field::Preset);
+    }
+
+    fn apply(&mut self, mut cli: Cli, reset_fields: bool) {
+        let pass_generator = Xkpasswd::for_languages(&cli.languages());
+
+        match cli.build_settings::<Settings>(&pass_generator.source().word_lengths()) {
+            Ok(settings) => {
+                if reset_fields {
+                    resolve_cli(&mut cli, &settings);
+                }
+
+                self.cli = cli;
+                self.pass_generator = pass_generator;
+                self.settings = settings;
+                self.status.clear();
+                self.regenerate();
+            }
+            Err(err) => self.status = format!("Invalid settings: {}", err),
+        }
+    }
+
+    fn regenerate(&mut self) {
+        let settings = self.cli.generation_settings(&self.settings);
+        match self.pass_generator.gen_pass(&settings) {
+            Ok((passwd, entropy)) => {
+                self.passwd = passwd;
+                self.entropy = entropy;
+            }
+            Err(err) => self.status = format!("Unable to generate password: {}", err),
+        }
+    }
+
+    // keeps everything but the fields tweakable in the interactive mode
+    fn reset_cli(cli: &Cli) -> Cli {
+        Cli {
+            words_count: None,
+            word_length_min: None,
+            word_length_max: None,
+            word_transforms: None,
+            separators: None,
+            padding_digits_before: None,
+            padding_digits_after: None,
+            padding_symbols: None,
+            padding_symbols_before: None,
+            padding_symbols_after: None,
+            padding: None,
+            adaptive_length: None,
+            recipe: None,
+            ..cli.clone()
+        }
+    }
+
+    fn field_value(&self, field: Field) -> String {
+        let cli = &self.cli;
+        let number = |value: Option<u8>| value.map(|value| value.to_string()).unwrap_or_default();
+
+        match field {
+            Field::Preset => cli
+                .preset
+                .as_ref()
+                .map(value_name)
+                .unwrap_or("custom".to_string()),
+            Field::Languages => cli
+                .languages()
+                .iter()
+                .map(value_name)
+                .collect::<Vec<String>>()
+                .join(" + "),
+            Field::Keyboard => value_name(&cli.keyboard()),
+            Field::WordsCount => number(cli.words_count),
+            Field::WordLengthMin => number(cli.word_length_min),
+            Field::WordLengthMax => number(cli.word_length_max),
+            Field::WordTransforms => {
+                WordTransform::to_strings(cli.word_transforms.as_deref().unwrap_or_default())
+                    .join(" & ")
+            }
+            Field::Separators => format!("⟪{}⟫", cli.separators.as_deref().unwrap_or_default()),
+            Field::DigitsBefore => number(cli.padding_digits_before),
+            Field::DigitsAfter => number(cli.padding_digits_after),
+            Field::PaddingSymbols => {
+                format!("⟪{}⟫", cli.padding_symbols.as_deref().unwrap_or_default())
+            }
+            Field::SymbolsBefore => number(cli.padding_symbols_before),
+            Field::SymbolsAfter => number(cli.padding_symbols_after),
+            Field::Padding => cli.padding.as_ref().map(value_name).unwrap_or_default(),
+            Field::AdaptiveLength => cli
+                .adaptive_length
+                .map(|value| value.to_string())
+                .unwrap_or("-".to_string()),
+            Field::Readable => if cli.readable { "on" } else { "off" }.to_string(),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let [passwd_area, entropy_area, settings_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(4),
+            Constraint::Min(FIELDS.len() as u16 + 2),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Paragraph::new(self.passwd.as_str().bold())
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(" Password ")),
+            passwd_area,
+        );
+
+        let entropy = &self.entropy;
+        let blind_entropy = if entropy.blind_min == entropy.blind_max {
+            format!("{} bits", entropy.blind_min)
+        } else {
+            format!("{} - {} bits", entropy.blind_min, entropy.blind_max)
+        };
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(format!(
+                    "Blind: {}, seen: {} bits",
+                    blind_entropy, entropy.seen
+                )),
+                Line::from(format!("Time to break: {}", entropy.guess_time)),
+            ])
+            .block(Block::bordered().title(" Entropy ")),
+            entropy_area,
+        );
+
+        let settings_lines: Vec<Line> = FIELDS
+            .iter()
+            .enumerate()
+            .map(|(index, &field)| {
+                let (marker, style) = if index == self.selected {
+                    ("▶ ", Style::new().add_modifier(Modifier::REVERSED))
+                } else {
+                    ("  ", Style::new())
+                };
+
+                Line::from(vec![
+                    Span::raw(marker),
+                    Span::styled(format!("{:<16}", field_label(field)), style),
+                    Span::raw(" "),
+                    Span::raw(self.field_value(field)),
+                ])
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(settings_lines).block(Block::bordered().title(" Settings ")),
+            settings_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from("↑/↓ select · ←/→ change · space regenerate · s save · q quit".dim()),
+                Line::from(self.status.as_str().italic()),
+            ]),
+            help_area,
+        );
+    }
+}
+
+// fills all tweakable fields with their effective values
+fn resolve_cli(cli: &mut Cli, settings: &Settings) {
+    let word_lengths = settings.word_lengths();
+    let (digits_before, digits_after) = settings.padding_digits();
+    let (symbols_before, symbols_after) = settings.padding_symbol_lengths();
+
+    cli.words_count = Some(settings.words_count());
+    cli.word_length_min = Some(word_lengths.start);
+    cli.word_length_max = Some(word_lengths.end - 1);
+    cli.word_transforms = Some(settings.word_transforms().to_flags());
+    cli.separators = Some(settings.separators().to_string());
+    cli.padding_digits_before = Some(digits_before);
+    cli.padding_digits_after = Some(digits_after);
+    cli.padding_symbols = Some(settings.padding_symbols().to_string());
+    cli.padding_symbols_before = Some(symbols_before);
+    cli.padding_symbols_after = Some(symbols_after);
+
+    match settings.padding_strategy() {
+        PaddingStrategy::Fixed => {
+            cli.padding = Some(CliPadding::Fixed);
+            cli.adaptive_length = None;
+        }
+        PaddingStrategy::Adaptive(length) => {
+            cli.padding = Some(CliPadding::Adaptive);
+            cli.adaptive_length = Some(length);
+        }
+    }
+}
+
+fn field_label(field: Field) -> &'static str {
+    match field {
+        Field::Preset => "Preset",
+        Field::Languages => "Languages",
+        Field::Keyboard => "Keyboard",
+        Field::WordsCount => "Words",
+        Field::WordLengthMin => "Min word length",
+        Field::WordLengthMax => "Max word length",
+        Field::WordTransforms => "Transforms",
+        Field::Separators => "Separators",
+        Field::DigitsBefore => "Digits before",
+        Field::DigitsAfter => "Digits after",
+        Field::PaddingSymbols => "Padding symbols",
+        Field::SymbolsBefore => "Symbols before",
+        Field::SymbolsAfter => "Symbols after",
+        Field::Padding => "Padding",
+        Field::AdaptiveLength => "Adaptive length",
+        Field::Readable => "Readable",
+    }
+}
+
+fn value_name<T: ValueEnum>(value: &T) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+fn cycle(index: Option<usize>, len: usize, delta: i8) -> usize {
+    match index {
+        Some(index) => (index as isize + delta as isize).rem_euclid(len as isize) as usize,
+        None if delta < 0 => len - 1,
+        None => 0,
+    }
+}
+
+fn step(value: Option<u8>, delta: i8) -> Option<u8> {
+    value.map(|value| value.saturating_add_signed(delta))
+}
+
+fn next_option(options: &[&str], current: &Option<String>, delta: i8) -> Option<String> {
+    let index = options
+        .iter()
+        .position(|&option| Some(option) == current.as_deref());
+    Some(options[cycle(index, options.len(), delta)].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use tempfile::NamedTempFile;
+
+    fn new_tui(args: &[&str]) -> Tui {
+        let args = [&["xkpasswd"], args].concat();
+        Tui::new(Cli::try_parse_from(args).unwrap()).unwrap()
+    }
+
+    fn select(tui: &mut Tui, field: Field) {
+        tui.selected = FIELDS.iter().position(|&item| item == field).unwrap();
+    }
+
+    #[test]
+    fn test_tui_new() {
+        let tui = new_tui(&["-w", "4"]);
+
+        assert_eq!(Some(4), tui.cli.words_count);
+        assert_eq!(Some(4), tui.cli.word_length_min);
+        assert_eq!(Some(10), tui.cli.word_length_max);
+        assert_eq!(Some(".-_~".to_string()), tui.cli.separators);
+        assert!(matches!(tui.cli.padding, Some(CliPadding::Fixed)));
+        assert_eq!(4, tui.settings.words_count());
+        assert!(!tui.passwd.is_empty());
+        assert!(tui.entropy.seen > 0);
+
+        assert!(Tui::new(Cli::try_parse_from(["xkpasswd", "-w", "0"]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_tui_adjust_numbers() {
+        let mut tui = new_tui(&[]);
+
+        select(&mut tui, Field::WordsCount);
+        tui.adjust(1);
+        assert_eq!(4, tui.settings.words_count());
+
+        tui.adjust(-1);
+        tui.adjust(-1);
+        tui.adjust(-1);
+        assert_eq!(1, tui.settings.words_count());
+        assert!(tui.status.is_empty());
+
+        // invalid settings are not applied
+        tui.adjust(-1);
+        assert_eq!(Some(1), tui.cli.words_count);
+        assert_eq!(
+            "Invalid settings: only positive integer is allowed for words count",
+            tui.status
+        );
+
+        select(&mut tui, Field::WordLengthMax);
+        tui.adjust(1);
+        assert_eq!(4..12, tui.settings.word_lengths());
+
+        select(&mut tui, Field::DigitsBefore);
+        tui.adjust(1);
+        assert_eq!((1, 2), tui.settings.padding_digits());
+    }
+
+    #[test]
+    fn test_tui_adjust_options() {
+        let mut tui = new_tui(&[]);
+
+        select(&mut tui, Field::WordTransforms);
+        tui.adjust(1);
+        assert_eq!(
+            WordTransform::Lowercase as FieldSize,
+            tui.settings.word_transforms()
+        );
+        tui.adjust(-2);
+        assert_eq!(
+            WordTransform::AltercaseUpperFirst as FieldSize,
+            tui.settings.word_transforms()
+        );
+
+        select(&mut tui, Field::Separators);
+        tui.adjust(1);
+        assert_eq!("-", tui.settings.separators());
+
+        select(&mut tui, Field::AdaptiveLength);
+        tui.adjust(1);
+        assert_eq!(
+            "Adaptive length only applies to adaptive padding",
+            tui.status
+        );
+
+        select(&mut tui, Field::Padding);
+        tui.adjust(1);
+        assert_eq!(
+            PaddingStrategy::Adaptive(DEFAULT_ADAPTIVE_LENGTH),
+            tui.settings.padding_strategy()
+        );
+        assert_eq!(DEFAULT_ADAPTIVE_LENGTH, tui.passwd.len());
+
+        select(&mut tui, Field::AdaptiveLength);
+        tui.adjust(-1);
+        assert_eq!(DEFAULT_ADAPTIVE_LENGTH - 1, tui.passwd.len());
+    }
+
+    #[test]
+    fn test_tui_adjust_preset_and_languages() {
+        let mut tui = new_tui(&["-w", "5", "-z", "de", "-z", "fr"]);
+
+        select(&mut tui, Field::Preset);
+        tui.adjust(1);
+        assert!(matches!(tui.cli.preset, Some(Preset::Default)));
+        // tweaked fields are reset to the preset
+        assert_eq!(Some(3), tui.cli.words_count);
+        assert_eq!(
+            vec![Language::German, Language::French],
+            tui.cli.languages()
+        );
+
+        tui.adjust(-1);
+        assert!(matches!(tui.cli.preset, Some(Preset::Xkcd)));
+        assert_eq!(Some(4), tui.cli.words_count);
+
+        select(&mut tui, Field::Languages);
+        tui.adjust(1);
+        assert_eq!(vec![Language::Portuguese], tui.cli.languages());
+        assert_eq!("pt", tui.field_value(Field::Languages));
+        // the keyboard follows the language until chosen
+        assert_eq!("pt-br", tui.field_value(Field::Keyboard));
+
+        select(&mut tui, Field::Keyboard);
+        tui.adjust(1);
+        assert_eq!(Some(Keyboard::Ios), tui.settings.keyboard());
+        tui.adjust(-2);
+        assert_eq!("es", tui.field_value(Field::Keyboard));
+    }
+
+    #[test]
+    fn test_tui_handle_key() {
+        let mut tui = new_tui(&[]);
+
+        assert!(tui.handle_key(KeyCode::Up));
+        assert_eq!(FIELDS.len() - 1, tui.selected);
+        assert!(tui.handle_key(KeyCode::Down));
+        assert_eq!(0, tui.selected);
+
+        tui.status = "stale".to_string();
+        assert!(tui.handle_key(KeyCode::Char(' ')));
+        assert!(tui.status.is_empty());
+
+        assert!(!tui.handle_key(KeyCode::Char('q')));
+        assert!(!tui.handle_key(KeyCode::Esc));
+    }
+
+    #[test]
+    fn test_tui_save_config() {
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(
+            temp_file.path(),
+            "exclude_words = [\"foo\"]\nwords_count = 8\n",
+        )
+        .unwrap();
+        let config_path = temp_file.path().to_str().unwrap();
+
+        let mut cli =
+            Cli::try_parse_from(["xkpasswd", "-c", config_path, "-P", "xkcd", "-z", "de"]).unwrap();
+        cli.parse_config_file().unwrap();
+
+        let mut tui = Tui::new(cli).unwrap();
+        select(&mut tui, Field::Padding);
+        tui.adjust(1);
+
+        assert!(tui.handle_key(KeyCode::Char('s')));
+        assert_eq!(format!("Saved to {}", config_path), tui.status);
+
+        let mut cli = Cli::try_parse_from(["xkpasswd", "-c", config_path]).unwrap();
+        cli.parse_config_file().unwrap();
+
+        assert_eq!(Some(vec!["foo".to_string()]), cli.excluded_words);
+        assert_eq!(Some(8), cli.words_count);
+        assert!(matches!(cli.preset, Some(Preset::Xkcd)));
+        assert_eq!(vec![Language::German], cli.languages());
+
+        let settings: Settings = cli
+            .build_settings(&[3, 4, 5, 6, 7, 8, 9, 10, 11, 12])
+            .unwrap();
+        assert_eq!(tui.settings, settings);
+    }
+
+    #[test]
+    fn test_tui_render() {
+        let tui = new_tui(&["-P", "web32"]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal.draw(|frame| tui.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(screen.contains(tui.passwd.as_str()));
+        assert!(screen.contains("Time to break"));
+        assert!(screen.contains("web32"));
+        assert!(screen.contains("Adaptive length"));
+    }
+}
@@ -0,0 +1,131 @@
+//! Shims for building the core generator with `#![no_std]` + `alloc`.
+//!
+//! Hash-based collections & float math need `std`, so ordered collections
+//! and plain Rust approximations are used without it.
+
+pub(crate) use alloc::borrow::Cow;
+pub(crate) use alloc::collections::BTreeSet;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec::Vec;
+pub(crate) use alloc::{format, vec};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::{BTreeMap as Map, BTreeSet as Set};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{HashMap as Map, HashSet as Set};
+
+#[cfg(feature = "std")]
+pub(crate) fn log2(value: f64) -> f64 {
+    value.log2()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn log2(value: f64) -> f64 {
+    log2_approx(value)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn round(value: f64) -> f64 {
+    value.round()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn round(value: f64) -> f64 {
+    round_approx(value)
+}
+
+#[cfg(any(test, not(feature = "std")))]
+fn log2_approx(value: f64) -> f64 {
+    const MANTISSA_BITS: u32 = 52;
+
+    if value.is_nan() || value < 0.0 {
+        return f64::NAN;
+    }
+
+    if value == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+
+    if value.is_infinite() {
+        return f64::INFINITY;
+    }
+
+    let bits = value.to_bits();
+    let biased_exponent = (bits >> MANTISSA_BITS) & 0x7ff;
+
+    // subnormal numbers are scaled up first
+    if biased_exponent == 0 {
+        let scale = (1u64 << MANTISSA_BITS) as f64;
+        return log2_approx(value * scale) - MANTISSA_BITS as f64;
+    }
+
+    // value = mantissa * 2^exponent, with mantissa in [1, 2)
+    let exponent = biased_exponent as i64 - 1023;
+    let mantissa = f64::from_bits((bits & ((1 << MANTISSA_BITS) - 1)) | (1023 << MANTISSA_BITS));
+
+    // ln(mantissa) = 2 * atanh(s) = 2 * (s + s^3 / 3 + s^5 / 5 + ...), with s <= 1/3
+    let s = (mantissa - 1.0) / (mantissa + 1.0);
+    let mut term = s;
+    let mut ln = 0.0;
+    for k in 0..24 {
+        ln += term / (2 * k + 1) as f64;
+        term *= s * s;
+    }
+
+    exponent as f64 + 2.0 * ln / core::f64::consts::LN_2
+}
+
+#[cfg(any(test, not(feature = "std")))]
+fn round_approx(value: f64) -> f64 {
+    // large values have no fractional part
+    if !value.is_finite() || value.abs() >= (1u64 << 52) as f64 {
+        return value;
+    }
+
+    // rounds half away from zero, same as f64::round
+    let truncated = value as i64 as f64;
+    match value - truncated {
+        diff if diff >= 0.5 => truncated + 1.0,
+        diff if diff <= -0.5 => truncated - 1.0,
+        _ => truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log2_approx() {
+        for value in (1..100_000).map(|n| n as f64).chain([
+            0.001,
+            0.5,
+            1.5,
+            10f64.powi(30),
+            f64::MAX,
+            f64::MIN_POSITIVE / 8.0,
+        ]) {
+            let expected = value.log2();
+            assert!(
+                (expected - log2_approx(value)).abs() < 1e-9,
+                "log2({}) = {} != {}",
+                value,
+                log2_approx(value),
+                expected
+            );
+        }
+
+        assert_eq!(f64::NEG_INFINITY, log2_approx(0.0));
+        assert_eq!(f64::INFINITY, log2_approx(f64::INFINITY));
+        assert!(log2_approx(-1.0).is_nan());
+    }
+
+    #[test]
+    fn test_round_approx() {
+        for value in [
+            0.0f64, 0.4, 0.5, 0.6, 1.49, 1.5, 2.5, -0.5, -1.4, -2.5, 12.9, 41.3593, 1e20, -1e20,
+        ] {
+            assert_eq!(value.round(), round_approx(value), "{}", value);
+        }
+    }
+}
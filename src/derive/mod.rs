@@ -0,0 +1,62 @@
+//! Deterministic passwords derived from a master secret, so that they never need to be stored.
+//!
+//! The master secret is stretched with Argon2id, salted with the site & counter,
+//! and the output seeds the generator used by [`Xkpasswd::gen_pass_with_rng`].
+//! Derived passwords stay the same as long as the settings & dictionaries do.
+
+#[cfg(test)]
+mod tests;
+
+use crate::prelude::{Entropy, Randomizer, WordSource, Xkpasswd};
+use crate::secret::Passwd;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+pub const DEFAULT_COUNTER: u32 = 1;
+
+// changing anything below changes every derived password
+const SALT_PREFIX: &str = "xkpasswd-v1";
+const KDF_MEMORY_KIB: u32 = 19 * 1024;
+const KDF_ITERATIONS: u32 = 2;
+const KDF_PARALLELISM: u32 = 1;
+
+impl<W: WordSource> Xkpasswd<W> {
+    /// Derive the password for a site from a master secret.
+    ///
+    /// Sites are case-insensitive, bump the counter to rotate a site's password.
+    pub fn derive_pass<S: Randomizer>(
+        &self,
+        settings: &S,
+        master_secret: &str,
+        site: &str,
+        counter: u32,
+    ) -> Result<(Passwd, Entropy), String> {
+        let mut rng = derive_rng(master_secret, site, counter)?;
+        self.gen_pass_with_rng(settings, &mut rng)
+    }
+}
+
+/// Seeded generator for a (master secret, site, counter) tuple.
+pub fn derive_rng(master_secret: &str, site: &str, counter: u32) -> Result<ChaCha20Rng, String> {
+    if master_secret.is_empty() {
+        return Err("master secret must not be empty".to_string());
+    }
+
+    let site = site.trim().to_lowercase();
+    if site.is_empty() {
+        return Err("site must not be empty".to_string());
+    }
+
+    let salt = format!("{}:{}:{}", SALT_PREFIX, site, counter);
+    let params = Params::new(KDF_MEMORY_KIB, KDF_ITERATIONS, KDF_PARALLELISM, Some(32))
+        .map_err(|err| err.to_string())?;
+
+    let mut seed = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(master_secret.as_bytes(), salt.as_bytes(), &mut seed)
+        .map_err(|err| err.to_string())?;
+
+    Ok(ChaCha20Rng::from_seed(seed))
+}
@@ -0,0 +1,96 @@
+use super::*;
+use crate::prelude::{Builder, L10n, Language, Preset};
+use crate::settings::Settings;
+
+const MASTER_SECRET: &str = "correct horse battery staple";
+const SITE: &str = "example.com";
+
+fn derive_default(master_secret: &str, site: &str, counter: u32) -> Result<String, String> {
+    let pass = Xkpasswd::for_language(Language::English);
+    pass.derive_pass(&Settings::default(), master_secret, site, counter)
+        .map(|(passwd, _)| passwd.to_string())
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_derive_pass_presets() {
+    let pass = Xkpasswd::for_language(Language::English);
+    let table = [
+        (Preset::Default, "howard_retiring_PLANNING_70--"),
+        (Preset::AppleID, "@60.freeman.fleas.FLIPPED.78!"),
+        (Preset::WindowsNtlmV1, "6*gEARS*mARKO&"),
+        (
+            Preset::SecurityQuestions,
+            "ledger magnus kramer discount rico danielle?",
+        ),
+        (Preset::Web16, "+less*LARK*road&"),
+        (Preset::Web32, "+62*BLANK*jumbo*FOUL*jewel*43&"),
+        (
+            Preset::Wifi,
+            "6237*KRAMER*DISCOUNT*rico*danielle*MEDIC*BLOODY*4775???????????",
+        ),
+        (Preset::Xkcd, "CRISPY-ledger-MAGNUS-kramer"),
+    ];
+
+    for (preset, expected) in table {
+        let settings = Settings::from_preset(preset);
+        let (passwd, entropy) = pass
+            .derive_pass(&settings, MASTER_SECRET, SITE, DEFAULT_COUNTER)
+            .unwrap();
+
+        assert_eq!(expected, passwd.as_str(), "{:?}", preset);
+        assert_eq!(pass.gen_pass(&settings).unwrap().1, entropy);
+    }
+}
+
+#[cfg(feature = "all_langs")]
+#[test]
+fn test_derive_pass_languages() {
+    let table = [
+        (Language::English, "howard_retiring_PLANNING_70--"),
+        (Language::French, "monroe_bienvenue_RAPPORTE_70--"),
+        (Language::German, "alleine_entspannt_BESTATIGT_70--"),
+        (Language::Portuguese, "montou_autentico_PASTILHA_70--"),
+        (Language::Spanish, "alcalde_demasiado_CIUDADANO_70--"),
+    ];
+
+    for (language, expected) in table {
+        let pass = Xkpasswd::for_language(language);
+        let (passwd, _) = pass
+            .derive_pass(&Settings::default(), MASTER_SECRET, SITE, DEFAULT_COUNTER)
+            .unwrap();
+
+        assert_eq!(expected, passwd.as_str(), "{:?}", language);
+    }
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_derive_pass_inputs() {
+    let expected = derive_default(MASTER_SECRET, SITE, 1).unwrap();
+
+    // sites are case-insensitive
+    assert_eq!(
+        Ok(expected.clone()),
+        derive_default(MASTER_SECRET, " Example.COM ", 1)
+    );
+
+    assert_ne!(Ok(expected.clone()), derive_default(MASTER_SECRET, SITE, 2));
+    assert_ne!(
+        Ok(expected.clone()),
+        derive_default(MASTER_SECRET, "example.org", 1)
+    );
+    assert_ne!(
+        Ok(expected),
+        derive_default("Correct horse battery staple", SITE, 1)
+    );
+
+    assert_eq!(
+        Err("master secret must not be empty".to_string()),
+        derive_default("", SITE, 1)
+    );
+    assert_eq!(
+        Err("site must not be empty".to_string()),
+        derive_default(MASTER_SECRET, "  ", 1)
+    );
+}
@@ -0,0 +1,135 @@
+//! Diceware-style passphrases from physical dice rolls.
+//!
+//! Words are picked from a lookup table of exactly 6^k entries,
+//! so that every roll maps to a single word without any modulo bias.
+
+#[cfg(test)]
+mod tests;
+
+use crate::prelude::{Randomizer, WordSource, Xkpasswd};
+
+use crate::compat::*;
+use core::fmt;
+
+pub const DICE_SIDES: usize = 6;
+pub const MAX_DICE_PER_WORD: u32 = 5;
+
+/// Words indexed by dice rolls, sorted alphabetically for lookups on paper.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiceTable<'a> {
+    dice_per_word: u32,
+    words: Vec<&'a str>,
+}
+
+impl<'a> DiceTable<'a> {
+    /// Build the largest table fitting in a pool, up to [`MAX_DICE_PER_WORD`] dice per word.
+    ///
+    /// Words are spread evenly over the pool, so that all lengths are represented.
+    pub fn new(pool: &[&'a str]) -> Result<Self, String> {
+        let mut seen_words: Set<&str> = Set::new();
+        let distinct_words: Vec<&'a str> = pool
+            .iter()
+            .copied()
+            .filter(|word| seen_words.insert(word))
+            .collect();
+
+        let dice_per_word = (1..=MAX_DICE_PER_WORD)
+            .take_while(|&dice| DICE_SIDES.pow(dice) <= distinct_words.len())
+            .last()
+            .ok_or(format!(
+                "at least {} distinct words are required for dice rolls, only {} available",
+                DICE_SIDES,
+                distinct_words.len()
+            ))?;
+
+        let table_size = DICE_SIDES.pow(dice_per_word);
+        let mut words: Vec<&'a str> = (0..table_size)
+            .map(|idx| distinct_words[idx * distinct_words.len() / table_size])
+            .collect();
+        words.sort_unstable();
+
+        Ok(DiceTable {
+            dice_per_word,
+            words,
+        })
+    }
+
+    pub fn dice_per_word(&self) -> u32 {
+        self.dice_per_word
+    }
+
+    /// Bits of entropy for every word picked with dice.
+    pub fn entropy_per_word(&self) -> f64 {
+        log2(self.words.len() as f64)
+    }
+
+    /// Word for a roll written as digits from 1 to 6, e.g. `"43126"`.
+    pub fn word_for(&self, roll: &str) -> Result<&'a str, String> {
+        let roll = roll.trim();
+        let invalid_roll = || {
+            format!(
+                "invalid dice roll '{}', expected {} dice from 1 to {}",
+                roll, self.dice_per_word, DICE_SIDES
+            )
+        };
+
+        if roll.chars().count() != self.dice_per_word as usize {
+            return Err(invalid_roll());
+        }
+
+        let index = roll.chars().try_fold(0, |acc, die| {
+            match die.to_digit(10).map(|face| face as usize) {
+                Some(face @ 1..=DICE_SIDES) => Ok(acc * DICE_SIDES + face - 1),
+                _ => Err(invalid_roll()),
+            }
+        })?;
+
+        Ok(self.words[index])
+    }
+
+    /// Words for a sequence of rolls.
+    pub fn words_for(&self, rolls: &[&str]) -> Result<Vec<&'a str>, String> {
+        if rolls.is_empty() {
+            return Err("at least one dice roll is required".to_string());
+        }
+
+        rolls.iter().map(|roll| self.word_for(roll)).collect()
+    }
+
+    /// All table entries, as rolls with their words.
+    pub fn entries(&self) -> impl Iterator<Item = (String, &'a str)> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .map(|(index, &word)| (self.roll_for(index), word))
+    }
+
+    fn roll_for(&self, index: usize) -> String {
+        (0..self.dice_per_word)
+            .rev()
+            .map(|position| {
+                let face = index / DICE_SIDES.pow(position) % DICE_SIDES + 1;
+                char::from_digit(face as u32, 10).unwrap()
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for DiceTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (roll, word) in self.entries() {
+            writeln!(f, "{}\t{}", roll, word)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: WordSource> Xkpasswd<W> {
+    /// Lookup table of words matching the settings' lengths & filters.
+    pub fn dice_table<S: Randomizer>(&self, settings: &S) -> Result<DiceTable<'_>, String> {
+        let pool = self.build_pool(settings.word_lengths())?;
+        let all_words = settings.filter_words(pool)?;
+        DiceTable::new(&all_words)
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+use crate::prelude::{Builder, L10n, Language};
+use crate::settings::Settings;
+
+fn numbered_words(count: usize) -> Vec<String> {
+    (0..count).map(|idx| format!("word{:05}", idx)).collect()
+}
+
+#[test]
+fn test_dice_table_new() {
+    let table = [
+        (6, 1),
+        (35, 1),
+        (36, 2),
+        (215, 2),
+        (216, 3),
+        (1296, 4),
+        (9000, 5),
+    ];
+
+    for (pool_size, dice_per_word) in table {
+        let words = numbered_words(pool_size);
+        let pool: Vec<&str> = words.iter().map(String::as_str).collect();
+        let table = DiceTable::new(&pool).unwrap();
+
+        assert_eq!(dice_per_word, table.dice_per_word());
+        assert_eq!(DICE_SIDES.pow(dice_per_word), table.entries().count());
+    }
+
+    assert_eq!(
+        Err("at least 6 distinct words are required for dice rolls, only 5 available".to_string()),
+        DiceTable::new(&["foo", "bar", "baz", "qux", "foo", "fooz", "fooz"])
+    );
+}
+
+#[test]
+fn test_dice_table_spreads_words() {
+    let words = numbered_words(72);
+    let pool: Vec<&str> = words.iter().map(String::as_str).collect();
+    let table = DiceTable::new(&pool).unwrap();
+
+    let expected: Vec<&str> = pool.iter().step_by(2).copied().collect();
+    assert_eq!(expected, table.words);
+}
+
+#[test]
+fn test_dice_table_word_for() {
+    let pool = ["f", "e", "d", "c", "b", "a", "b"];
+    let table = DiceTable::new(&pool).unwrap();
+
+    assert_eq!(Ok("a"), table.word_for("1"));
+    assert_eq!(Ok("f"), table.word_for(" 6 "));
+
+    let words = numbered_words(36);
+    let pool: Vec<&str> = words.iter().map(String::as_str).collect();
+    let table = DiceTable::new(&pool).unwrap();
+
+    assert_eq!(Ok("word00000"), table.word_for("11"));
+    assert_eq!(Ok("word00005"), table.word_for("16"));
+    assert_eq!(Ok("word00006"), table.word_for("21"));
+    assert_eq!(Ok("word00035"), table.word_for("66"));
+    assert_eq!(
+        Ok(vec!["word00006", "word00005"]),
+        table.words_for(&["21", "16"])
+    );
+
+    for roll in ["", "1", "111", "17", "01", "a1"] {
+        assert_eq!(
+            Err(format!(
+                "invalid dice roll '{}', expected 2 dice from 1 to 6",
+                roll
+            )),
+            table.word_for(roll)
+        );
+    }
+
+    assert_eq!(
+        Err("at least one dice roll is required".to_string()),
+        table.words_for(&[])
+    );
+}
+
+#[test]
+fn test_dice_table_entries() {
+    let words = numbered_words(36);
+    let pool: Vec<&str> = words.iter().map(String::as_str).collect();
+    let table = DiceTable::new(&pool).unwrap();
+
+    let entries: Vec<(String, &str)> = table.entries().collect();
+    assert_eq!(("11".to_string(), "word00000"), entries[0]);
+    assert_eq!(("12".to_string(), "word00001"), entries[1]);
+    assert_eq!(("66".to_string(), "word00035"), entries[35]);
+
+    for (roll, word) in entries {
+        assert_eq!(Ok(word), table.word_for(&roll));
+    }
+
+    let display = table.to_string();
+    assert_eq!(36, display.lines().count());
+    assert!(display.starts_with("11\tword00000\n12\tword00001\n"));
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_xkpasswd_dice_table() {
+    let pass = Xkpasswd::for_language(Language::English);
+    let settings = Settings::default();
+
+    let table = pass.dice_table(&settings).unwrap();
+    assert_eq!(MAX_DICE_PER_WORD, table.dice_per_word());
+    assert_eq!(7776, table.entries().count());
+    assert!((table.entropy_per_word() - 12.925).abs() < 0.001);
+
+    let words = table.words_for(&["43126", "22514"]).unwrap();
+    assert_eq!(words, table.words_for(&["43126", "22514"]).unwrap());
+    assert_ne!(words[0], words[1]);
+
+    // lengths & filters are applied to the table
+    let settings = settings.with_word_lengths(Some(5), Some(5)).unwrap();
+    let table = pass.dice_table(&settings).unwrap();
+    assert!(table.entries().all(|(_, word)| word.chars().count() == 5));
+    assert_eq!(4, table.dice_per_word());
+
+    let excluded = table.word_for("1111").unwrap();
+    let settings = settings.with_excluded_words(&[excluded]);
+    let table = pass.dice_table(&settings).unwrap();
+    assert!(table.entries().all(|(_, word)| word != excluded));
+}
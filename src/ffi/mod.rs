@@ -0,0 +1,448 @@
+//! C ABI for native consumers, declared in the generated `include/xkpasswd.h`.
+//!
+//! Settings & generators are opaque pointers, created & freed by the library.
+//! Fallible functions return an `XkStatus`, with a message for the last error
+//! of the calling thread available from `xkpasswd_last_error`.
+//!
+//! The header is generated by cbindgen with `make ffi-header`, as configured in `cbindgen.toml`.
+//! Enums are taken as plain integers and validated, as out-of-range C enum values
+//! would be undefined behavior once converted to Rust enums.
+
+#[cfg(test)]
+mod tests;
+
+use crate::prelude::*;
+use crate::secret::Wipe;
+use crate::settings::Settings;
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+// same as `WordTransform` flags, combined into `u8` fields
+pub const XK_TRANSFORM_LOWERCASE: u8 = 0b00000001;
+pub const XK_TRANSFORM_TITLECASE: u8 = 0b00000010;
+pub const XK_TRANSFORM_UPPERCASE: u8 = 0b00000100;
+pub const XK_TRANSFORM_INVERSED_TITLECASE: u8 = 0b00001000;
+pub const XK_TRANSFORM_ALTERCASE_LOWER_FIRST: u8 = 0b01000000;
+pub const XK_TRANSFORM_ALTERCASE_UPPER_FIRST: u8 = 0b10000000;
+
+/// Result of fallible functions, details are given by `xkpasswd_last_error`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XkStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    InvalidSettings = 3,
+    GenerationFailed = 4,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XkPreset {
+    Default = 0,
+    AppleId = 1,
+    WindowsNtlmV1 = 2,
+    SecurityQuestions = 3,
+    Web16 = 4,
+    Web32 = 5,
+    Wifi = 6,
+    Xkcd = 7,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XkLanguage {
+    English = 0,
+    French = 1,
+    German = 2,
+    Portuguese = 3,
+    Spanish = 4,
+}
+
+/// Entropy of generated passwords, in bits, along with the time to break them.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct XkEntropy {
+    pub blind_min: usize,
+    pub blind_max: usize,
+    pub seen: usize,
+    pub guess_years: usize,
+    pub guess_months: u8,
+    pub guess_days: u8,
+}
+
+/// Password settings, freed with `xkpasswd_settings_free`.
+pub struct XkSettings(Settings);
+
+/// Password generator for a language, freed with `xkpasswd_generator_free`.
+pub struct XkGenerator(Xkpasswd);
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Message of the last error on the calling thread, NULL if none.
+/// Owned by the library and valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn xkpasswd_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| match &*last_error.borrow() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+/// Default settings.
+#[no_mangle]
+pub extern "C" fn xkpasswd_settings_new() -> *mut XkSettings {
+    Box::into_raw(Box::new(XkSettings(Settings::default())))
+}
+
+/// Settings of an `XkPreset`, NULL if the preset is unknown.
+#[no_mangle]
+pub extern "C" fn xkpasswd_settings_from_preset(preset: u32) -> *mut XkSettings {
+    match XkPreset::try_from(preset) {
+        Ok(preset) => Box::into_raw(Box::new(XkSettings(Settings::from_preset(preset.into())))),
+        Err(err) => {
+            set_last_error(err);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// `settings` must come from `xkpasswd_settings_new` or `xkpasswd_settings_from_preset`, or be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_free(settings: *mut XkSettings) {
+    if !settings.is_null() {
+        drop(Box::from_raw(settings));
+    }
+}
+
+/// # Safety
+/// `settings` must be valid settings.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_words_count(
+    settings: *mut XkSettings,
+    words_count: u8,
+) -> XkStatus {
+    update_settings(settings, |settings| settings.with_words_count(words_count))
+}
+
+/// Word lengths, with 0 to keep the current bound.
+///
+/// # Safety
+/// `settings` must be valid settings.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_word_lengths(
+    settings: *mut XkSettings,
+    min_length: u8,
+    max_length: u8,
+) -> XkStatus {
+    update_settings(settings, |settings| {
+        settings.with_word_lengths(non_zero(min_length), non_zero(max_length))
+    })
+}
+
+/// Combination of `XK_TRANSFORM_*` flags.
+///
+/// # Safety
+/// `settings` must be valid settings.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_word_transforms(
+    settings: *mut XkSettings,
+    transforms: u8,
+) -> XkStatus {
+    update_settings(settings, |settings| {
+        settings.with_word_transforms(transforms)
+    })
+}
+
+/// # Safety
+/// `settings` must be valid settings and `separators` a NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_separators(
+    settings: *mut XkSettings,
+    separators: *const c_char,
+) -> XkStatus {
+    match read_str(separators) {
+        Ok(separators) => {
+            update_settings(
+                settings,
+                |settings| Ok(settings.with_separators(separators)),
+            )
+        }
+        Err(status) => status,
+    }
+}
+
+/// # Safety
+/// `settings` must be valid settings.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_padding_digits(
+    settings: *mut XkSettings,
+    prefix: u8,
+    suffix: u8,
+) -> XkStatus {
+    update_settings(settings, |settings| {
+        Ok(settings.with_padding_digits(Some(prefix), Some(suffix)))
+    })
+}
+
+/// # Safety
+/// `settings` must be valid settings and `symbols` a NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_padding_symbols(
+    settings: *mut XkSettings,
+    symbols: *const c_char,
+) -> XkStatus {
+    match read_str(symbols) {
+        Ok(symbols) => update_settings(settings, |settings| {
+            Ok(settings.with_padding_symbols(symbols))
+        }),
+        Err(status) => status,
+    }
+}
+
+/// # Safety
+/// `settings` must be valid settings.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_padding_symbol_lengths(
+    settings: *mut XkSettings,
+    prefix: u8,
+    suffix: u8,
+) -> XkStatus {
+    update_settings(settings, |settings| {
+        Ok(settings.with_padding_symbol_lengths(Some(prefix), Some(suffix)))
+    })
+}
+
+/// Symbols before & after words are padded with fixed lengths.
+///
+/// # Safety
+/// `settings` must be valid settings.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_fixed_padding(
+    settings: *mut XkSettings,
+) -> XkStatus {
+    update_settings(settings, |settings| {
+        settings.with_padding_strategy(PaddingStrategy::Fixed)
+    })
+}
+
+/// Passwords are padded or trimmed to fit a length.
+///
+/// # Safety
+/// `settings` must be valid settings.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_settings_set_adaptive_padding(
+    settings: *mut XkSettings,
+    length: usize,
+) -> XkStatus {
+    update_settings(settings, |settings| {
+        settings.with_padding_strategy(PaddingStrategy::Adaptive(length))
+    })
+}
+
+/// Generator for an `XkLanguage`, NULL if the language is unknown or not bundled.
+#[no_mangle]
+pub extern "C" fn xkpasswd_generator_new(language: u32) -> *mut XkGenerator {
+    let language: Language = match XkLanguage::try_from(language) {
+        Ok(language) => language.into(),
+        Err(err) => {
+            set_last_error(err);
+            return ptr::null_mut();
+        }
+    };
+
+    if !language.is_bundled() {
+        set_last_error(format!("language {:?} is not bundled", language));
+        return ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(XkGenerator(Xkpasswd::for_language(language))))
+}
+
+/// # Safety
+/// `generator` must come from `xkpasswd_generator_new`, or be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_generator_free(generator: *mut XkGenerator) {
+    if !generator.is_null() {
+        drop(Box::from_raw(generator));
+    }
+}
+
+/// Generate a password, to be freed with `xkpasswd_string_free`.
+/// `entropy` may be NULL if not needed.
+///
+/// # Safety
+/// `generator` & `settings` must be valid, `passwd` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_gen_pass(
+    generator: *const XkGenerator,
+    settings: *const XkSettings,
+    passwd: *mut *mut c_char,
+    entropy: *mut XkEntropy,
+) -> XkStatus {
+    let (Some(generator), Some(settings)) = (generator.as_ref(), settings.as_ref()) else {
+        return null_pointer();
+    };
+
+    if passwd.is_null() {
+        return null_pointer();
+    }
+
+    let (generated, generated_entropy) = match generator.0.gen_pass(&settings.0) {
+        Ok(result) => result,
+        Err(err) => {
+            set_last_error(err);
+            return XkStatus::GenerationFailed;
+        }
+    };
+
+    let generated = match CString::new(generated.as_bytes()) {
+        Ok(generated) => generated,
+        Err(err) => {
+            set_last_error(err.to_string());
+            return XkStatus::GenerationFailed;
+        }
+    };
+
+    *passwd = generated.into_raw();
+    if let Some(entropy) = entropy.as_mut() {
+        *entropy = generated_entropy.into();
+    }
+
+    XkStatus::Ok
+}
+
+/// Free a password, wiping it with the `zeroize` feature.
+///
+/// # Safety
+/// `passwd` must come from `xkpasswd_gen_pass`, or be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn xkpasswd_string_free(passwd: *mut c_char) {
+    if !passwd.is_null() {
+        CString::from_raw(passwd).into_bytes().wipe();
+    }
+}
+
+unsafe fn update_settings<F>(settings: *mut XkSettings, update: F) -> XkStatus
+where
+    F: FnOnce(&Settings) -> Result<Settings, String>,
+{
+    let Some(settings) = settings.as_mut() else {
+        return null_pointer();
+    };
+
+    match update(&settings.0) {
+        Ok(updated) => {
+            settings.0 = updated;
+            XkStatus::Ok
+        }
+        Err(err) => {
+            set_last_error(err);
+            XkStatus::InvalidSettings
+        }
+    }
+}
+
+unsafe fn read_str<'a>(value: *const c_char) -> Result<&'a str, XkStatus> {
+    if value.is_null() {
+        return Err(null_pointer());
+    }
+
+    CStr::from_ptr(value).to_str().map_err(|err| {
+        set_last_error(err.to_string());
+        XkStatus::InvalidUtf8
+    })
+}
+
+fn non_zero(value: u8) -> Option<u8> {
+    Some(value).filter(|&value| value > 0)
+}
+
+fn null_pointer() -> XkStatus {
+    set_last_error("unexpected null pointer".to_string());
+    XkStatus::NullPointer
+}
+
+fn set_last_error(message: String) {
+    // messages from settings never contain NUL bytes
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+impl TryFrom<u32> for XkPreset {
+    type Error = String;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => XkPreset::Default,
+            1 => XkPreset::AppleId,
+            2 => XkPreset::WindowsNtlmV1,
+            3 => XkPreset::SecurityQuestions,
+            4 => XkPreset::Web16,
+            5 => XkPreset::Web32,
+            6 => XkPreset::Wifi,
+            7 => XkPreset::Xkcd,
+            _ => return Err(format!("unknown preset {}", value)),
+        })
+    }
+}
+
+impl TryFrom<u32> for XkLanguage {
+    type Error = String;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => XkLanguage::English,
+            1 => XkLanguage::French,
+            2 => XkLanguage::German,
+            3 => XkLanguage::Portuguese,
+            4 => XkLanguage::Spanish,
+            _ => return Err(format!("unknown language {}", value)),
+        })
+    }
+}
+
+impl From<XkPreset> for Preset {
+    fn from(preset: XkPreset) -> Self {
+        match preset {
+            XkPreset::Default => Preset::Default,
+            XkPreset::AppleId => Preset::AppleID,
+            XkPreset::WindowsNtlmV1 => Preset::WindowsNtlmV1,
+            XkPreset::SecurityQuestions => Preset::SecurityQuestions,
+            XkPreset::Web16 => Preset::Web16,
+            XkPreset::Web32 => Preset::Web32,
+            XkPreset::Wifi => Preset::Wifi,
+            XkPreset::Xkcd => Preset::Xkcd,
+        }
+    }
+}
+
+impl From<XkLanguage> for Language {
+    fn from(language: XkLanguage) -> Self {
+        match language {
+            XkLanguage::English => Language::English,
+            XkLanguage::French => Language::French,
+            XkLanguage::German => Language::German,
+            XkLanguage::Portuguese => Language::Portuguese,
+            XkLanguage::Spanish => Language::Spanish,
+        }
+    }
+}
+
+impl From<Entropy> for XkEntropy {
+    fn from(entropy: Entropy) -> Self {
+        XkEntropy {
+            blind_min: entropy.blind_min,
+            blind_max: entropy.blind_max,
+            seen: entropy.seen,
+            guess_years: entropy.guess_time.years,
+            guess_months: entropy.guess_time.months,
+            guess_days: entropy.guess_time.days,
+        }
+    }
+}
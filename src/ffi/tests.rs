@@ -0,0 +1,236 @@
+use super::*;
+use crate::bit_flags::WordTransform;
+
+unsafe fn last_error() -> String {
+    CStr::from_ptr(xkpasswd_last_error())
+        .to_string_lossy()
+        .into_owned()
+}
+
+unsafe fn gen_pass(generator: *const XkGenerator, settings: *const XkSettings) -> String {
+    let mut passwd: *mut c_char = ptr::null_mut();
+    assert_eq!(
+        XkStatus::Ok,
+        xkpasswd_gen_pass(generator, settings, &mut passwd, ptr::null_mut())
+    );
+
+    let generated = CStr::from_ptr(passwd).to_str().unwrap().to_string();
+    xkpasswd_string_free(passwd);
+    generated
+}
+
+#[test]
+fn test_settings() {
+    unsafe {
+        let settings = xkpasswd_settings_new();
+        assert_eq!(Settings::default(), (*settings).0);
+
+        assert_eq!(XkStatus::Ok, xkpasswd_settings_set_words_count(settings, 5));
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_settings_set_word_lengths(settings, 0, 6)
+        );
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_settings_set_word_transforms(
+                settings,
+                XK_TRANSFORM_LOWERCASE | XK_TRANSFORM_UPPERCASE
+            )
+        );
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_settings_set_separators(settings, c"-".as_ptr())
+        );
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_settings_set_padding_digits(settings, 1, 2)
+        );
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_settings_set_padding_symbols(settings, c"!?".as_ptr())
+        );
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_settings_set_padding_symbol_lengths(settings, 0, 3)
+        );
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_settings_set_adaptive_padding(settings, 32)
+        );
+
+        let expected = Settings::default()
+            .with_words_count(5)
+            .unwrap()
+            .with_word_lengths(None, Some(6))
+            .unwrap()
+            .with_word_transforms(WordTransform::Lowercase | WordTransform::Uppercase)
+            .unwrap()
+            .with_separators("-")
+            .with_padding_digits(Some(1), Some(2))
+            .with_padding_symbols("!?")
+            .with_padding_symbol_lengths(Some(0), Some(3))
+            .with_padding_strategy(PaddingStrategy::Adaptive(32))
+            .unwrap();
+        assert_eq!(expected, (*settings).0);
+
+        assert_eq!(XkStatus::Ok, xkpasswd_settings_set_fixed_padding(settings));
+        assert_eq!(
+            expected
+                .with_padding_strategy(PaddingStrategy::Fixed)
+                .unwrap(),
+            (*settings).0
+        );
+
+        xkpasswd_settings_free(settings);
+        xkpasswd_settings_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn test_settings_errors() {
+    unsafe {
+        let settings = xkpasswd_settings_from_preset(XkPreset::Xkcd as u32);
+        assert_eq!(Settings::from_preset(Preset::Xkcd), (*settings).0);
+
+        assert_eq!(
+            XkStatus::InvalidSettings,
+            xkpasswd_settings_set_words_count(settings, 0)
+        );
+        assert_eq!(
+            "only positive integer is allowed for words count",
+            last_error()
+        );
+        // invalid updates leave settings untouched
+        assert_eq!(Settings::from_preset(Preset::Xkcd), (*settings).0);
+
+        assert_eq!(
+            XkStatus::InvalidUtf8,
+            xkpasswd_settings_set_separators(settings, c"\xff".as_ptr())
+        );
+        assert_eq!(
+            XkStatus::NullPointer,
+            xkpasswd_settings_set_padding_symbols(settings, ptr::null())
+        );
+        assert_eq!("unexpected null pointer", last_error());
+        assert_eq!(
+            XkStatus::NullPointer,
+            xkpasswd_settings_set_words_count(ptr::null_mut(), 3)
+        );
+
+        xkpasswd_settings_free(settings);
+
+        assert!(xkpasswd_settings_from_preset(8).is_null());
+        assert_eq!("unknown preset 8", last_error());
+        assert!(xkpasswd_generator_new(u32::MAX).is_null());
+        assert_eq!(format!("unknown language {}", u32::MAX), last_error());
+    }
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_gen_pass() {
+    unsafe {
+        let generator = xkpasswd_generator_new(XkLanguage::English as u32);
+        let settings = xkpasswd_settings_from_preset(XkPreset::Xkcd as u32);
+
+        let passwd = gen_pass(generator, settings);
+        assert_eq!(4, passwd.split('-').count());
+
+        let mut passwd: *mut c_char = ptr::null_mut();
+        let mut entropy = XkEntropy::default();
+        assert_eq!(
+            XkStatus::Ok,
+            xkpasswd_gen_pass(generator, settings, &mut passwd, &mut entropy)
+        );
+        xkpasswd_string_free(passwd);
+
+        let (_, expected) = Xkpasswd::for_language(Language::English)
+            .gen_pass(&Settings::from_preset(Preset::Xkcd))
+            .unwrap();
+        assert_eq!(XkEntropy::from(expected), entropy);
+
+        assert_eq!(
+            XkStatus::NullPointer,
+            xkpasswd_gen_pass(generator, settings, ptr::null_mut(), ptr::null_mut())
+        );
+        assert_eq!(
+            XkStatus::NullPointer,
+            xkpasswd_gen_pass(ptr::null(), settings, &mut passwd, ptr::null_mut())
+        );
+
+        xkpasswd_settings_free(settings);
+        xkpasswd_generator_free(generator);
+        xkpasswd_string_free(ptr::null_mut());
+    }
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_gen_pass_multibyte_symbols() {
+    unsafe {
+        let generator = xkpasswd_generator_new(XkLanguage::English as u32);
+        let settings = xkpasswd_settings_new();
+        xkpasswd_settings_set_separators(settings, c"€".as_ptr());
+        xkpasswd_settings_set_padding_symbols(settings, c"£¥".as_ptr());
+        xkpasswd_settings_set_adaptive_padding(settings, 40);
+
+        for _ in 0..20 {
+            let passwd = gen_pass(generator, settings);
+            assert_eq!(40, passwd.chars().count());
+            assert!(passwd.contains('€'));
+        }
+
+        xkpasswd_settings_free(settings);
+        xkpasswd_generator_free(generator);
+    }
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_gen_pass_failed() {
+    unsafe {
+        let generator = xkpasswd_generator_new(XkLanguage::English as u32);
+        let settings = Box::into_raw(Box::new(XkSettings(
+            Settings::default().with_included_words(&["fooz"]),
+        )));
+
+        let mut passwd: *mut c_char = ptr::null_mut();
+        assert_eq!(
+            XkStatus::GenerationFailed,
+            xkpasswd_gen_pass(generator, settings, &mut passwd, ptr::null_mut())
+        );
+        assert_eq!(
+            "only 0 word(s) left after filtering, 3 required",
+            last_error()
+        );
+        assert!(passwd.is_null());
+
+        xkpasswd_settings_free(settings);
+        xkpasswd_generator_free(generator);
+    }
+}
+
+#[test]
+fn test_transform_flags() {
+    let table = [
+        (XK_TRANSFORM_LOWERCASE, WordTransform::Lowercase),
+        (XK_TRANSFORM_TITLECASE, WordTransform::Titlecase),
+        (XK_TRANSFORM_UPPERCASE, WordTransform::Uppercase),
+        (
+            XK_TRANSFORM_INVERSED_TITLECASE,
+            WordTransform::InversedTitlecase,
+        ),
+        (
+            XK_TRANSFORM_ALTERCASE_LOWER_FIRST,
+            WordTransform::AltercaseLowerFirst,
+        ),
+        (
+            XK_TRANSFORM_ALTERCASE_UPPER_FIRST,
+            WordTransform::AltercaseUpperFirst,
+        ),
+    ];
+
+    for (flag, transform) in table {
+        assert_eq!(transform as u8, flag);
+    }
+}
@@ -0,0 +1,248 @@
+//! Password hashes for provisioning, computed locally with random salts.
+//!
+//! Hashes are in the string formats expected by their consumers: `crypt(3)` for
+//! `/etc/shadow` & `chpasswd -e`, PHC strings for Argon2id and `$2y$` bcrypt for
+//! Apache `htpasswd` files.
+
+#[cfg(test)]
+mod tests;
+
+use argon2::password_hash::{PasswordHasher, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use bcrypt::Version as BcryptVersion;
+use rand::{Rng, RngCore};
+use sha2::{Digest, Sha512};
+
+const CRYPT_ALPHABET: &[u8; 64] =
+    b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const SHA512_CRYPT_SALT_LEN: usize = 16;
+const SHA512_CRYPT_DEFAULT_ROUNDS: u32 = 5000;
+const BCRYPT_COST: u32 = 12;
+const BCRYPT_MAX_LEN: usize = 72;
+// htpasswd files are checked on every request, hence a lower cost
+const HTPASSWD_COST: u32 = 10;
+// OWASP recommendations for Argon2id
+const ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+const ARGON2_SALT_LEN: usize = 16;
+
+// byte order of the SHA-512 digest in the sha512-crypt encoding
+const SHA512_CRYPT_ORDER: [(usize, usize, usize); 21] = [
+    (0, 21, 42),
+    (22, 43, 1),
+    (44, 2, 23),
+    (3, 24, 45),
+    (25, 46, 4),
+    (47, 5, 26),
+    (6, 27, 48),
+    (28, 49, 7),
+    (50, 8, 29),
+    (9, 30, 51),
+    (31, 52, 10),
+    (53, 11, 32),
+    (12, 33, 54),
+    (34, 55, 13),
+    (56, 14, 35),
+    (15, 36, 57),
+    (37, 58, 16),
+    (59, 17, 38),
+    (18, 39, 60),
+    (40, 61, 19),
+    (62, 20, 41),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashScheme {
+    /// `$6$` hashes of `/etc/shadow`, with the default 5000 rounds.
+    Sha512Crypt,
+    /// `$2b$` hashes with a cost of 12.
+    Bcrypt,
+    /// PHC strings with the OWASP recommended parameters.
+    Argon2id,
+    /// `$2y$` bcrypt hashes as written by `htpasswd -B`, with a cost of 10.
+    Htpasswd,
+}
+
+impl HashScheme {
+    pub const ALL: [HashScheme; 4] = [
+        HashScheme::Sha512Crypt,
+        HashScheme::Bcrypt,
+        HashScheme::Argon2id,
+        HashScheme::Htpasswd,
+    ];
+
+    /// Name used in the CLI.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashScheme::Sha512Crypt => "sha512-crypt",
+            HashScheme::Bcrypt => "bcrypt",
+            HashScheme::Argon2id => "argon2id",
+            HashScheme::Htpasswd => "htpasswd",
+        }
+    }
+
+    /// Case-insensitive lookup of [`HashScheme::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Hash a password with a fresh random salt.
+    pub fn hash(&self, passwd: &str) -> Result<String, String> {
+        self.hash_with_rng(passwd, &mut rand::thread_rng())
+    }
+
+    /// Same as [`HashScheme::hash`], drawing the salt from the given generator.
+    pub fn hash_with_rng<R: RngCore>(&self, passwd: &str, rng: &mut R) -> Result<String, String> {
+        match self {
+            HashScheme::Sha512Crypt => {
+                let salt: String = (0..SHA512_CRYPT_SALT_LEN)
+                    .map(|_| CRYPT_ALPHABET[rng.gen_range(0..CRYPT_ALPHABET.len())] as char)
+                    .collect();
+                Ok(sha512_crypt(passwd, &salt, None))
+            }
+            HashScheme::Bcrypt => bcrypt_hash(passwd, BCRYPT_COST, rng.gen(), BcryptVersion::TwoB),
+            HashScheme::Argon2id => {
+                let mut salt = [0u8; ARGON2_SALT_LEN];
+                rng.fill_bytes(&mut salt);
+                argon2id_hash(passwd, &salt, ARGON2_MEMORY_KIB, ARGON2_ITERATIONS)
+            }
+            HashScheme::Htpasswd => {
+                bcrypt_hash(passwd, HTPASSWD_COST, rng.gen(), BcryptVersion::TwoY)
+            }
+        }
+    }
+}
+
+/// `crypt(3)` SHA-512 hash, as specified by Ulrich Drepper.
+///
+/// Salts are cut to 16 characters, rounds are clamped between 1000 & 999999999.
+pub fn sha512_crypt(passwd: &str, salt: &str, rounds: Option<u32>) -> String {
+    let passwd = passwd.as_bytes();
+    let salt = &salt.as_bytes()[..salt.len().min(SHA512_CRYPT_SALT_LEN)];
+    let rounds = rounds.map(|rounds| rounds.clamp(1000, 999_999_999));
+
+    let alternate = Sha512::new()
+        .chain_update(passwd)
+        .chain_update(salt)
+        .chain_update(passwd)
+        .finalize();
+
+    let mut hasher = Sha512::new()
+        .chain_update(passwd)
+        .chain_update(salt)
+        .chain_update(repeated(&alternate, passwd.len()));
+
+    let mut len = passwd.len();
+    while len > 0 {
+        if len & 1 == 1 {
+            hasher.update(alternate);
+        } else {
+            hasher.update(passwd);
+        }
+        len >>= 1;
+    }
+    let mut digest = hasher.finalize();
+
+    let mut hasher = Sha512::new();
+    for _ in 0..passwd.len() {
+        hasher.update(passwd);
+    }
+    let passwd_seq = repeated(&hasher.finalize(), passwd.len());
+
+    let mut hasher = Sha512::new();
+    for _ in 0..16 + digest[0] as usize {
+        hasher.update(salt);
+    }
+    let salt_seq = repeated(&hasher.finalize(), salt.len());
+
+    for round in 0..rounds.unwrap_or(SHA512_CRYPT_DEFAULT_ROUNDS) {
+        let mut hasher = Sha512::new();
+
+        if round % 2 == 1 {
+            hasher.update(&passwd_seq);
+        } else {
+            hasher.update(digest);
+        }
+        if round % 3 != 0 {
+            hasher.update(&salt_seq);
+        }
+        if round % 7 != 0 {
+            hasher.update(&passwd_seq);
+        }
+        if round % 2 == 1 {
+            hasher.update(digest);
+        } else {
+            hasher.update(&passwd_seq);
+        }
+
+        digest = hasher.finalize();
+    }
+
+    let mut hash = String::from("$6$");
+    if let Some(rounds) = rounds {
+        hash.push_str(&format!("rounds={}$", rounds));
+    }
+    hash.push_str(&String::from_utf8_lossy(salt));
+    hash.push('$');
+
+    for (first, second, third) in SHA512_CRYPT_ORDER {
+        push_crypt_base64(&mut hash, [digest[first], digest[second], digest[third]], 4);
+    }
+    push_crypt_base64(&mut hash, [0, 0, digest[63]], 2);
+
+    hash
+}
+
+fn bcrypt_hash(
+    passwd: &str,
+    cost: u32,
+    salt: [u8; 16],
+    version: BcryptVersion,
+) -> Result<String, String> {
+    // bcrypt silently ignores anything after 72 bytes otherwise
+    if passwd.len() > BCRYPT_MAX_LEN {
+        return Err(format!(
+            "bcrypt only supports passwords up to {} bytes, got {}",
+            BCRYPT_MAX_LEN,
+            passwd.len()
+        ));
+    }
+
+    bcrypt::hash_with_salt(passwd, cost, salt)
+        .map(|parts| parts.format_for_version(version))
+        .map_err(|err| format!("unable to hash with bcrypt: {}", err))
+}
+
+fn argon2id_hash(
+    passwd: &str,
+    salt: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+) -> Result<String, String> {
+    let params = Params::new(memory_kib, iterations, ARGON2_PARALLELISM, None)
+        .map_err(|err| format!("invalid Argon2 parameters: {}", err))?;
+    let salt = SaltString::encode_b64(salt).map_err(|err| format!("invalid salt: {}", err))?;
+
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(passwd.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|err| format!("unable to hash with Argon2id: {}", err))
+}
+
+// the digest repeated over the given length
+fn repeated(digest: &[u8], len: usize) -> Vec<u8> {
+    digest.iter().copied().cycle().take(len).collect()
+}
+
+// least significant bits first, unlike regular base64
+fn push_crypt_base64(hash: &mut String, [high, middle, low]: [u8; 3], chars: usize) {
+    let mut value = ((high as u32) << 16) | ((middle as u32) << 8) | low as u32;
+    for _ in 0..chars {
+        hash.push(CRYPT_ALPHABET[(value & 0x3f) as usize] as char);
+        value >>= 6;
+    }
+}
@@ -0,0 +1,134 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// bcrypt salt "CCCCCCCCCCCCCCCCCCCCC." decoded
+const BCRYPT_SALT: [u8; 16] = [16, 65, 4, 16, 65, 4, 16, 65, 4, 16, 65, 4, 16, 65, 4, 16];
+
+#[test]
+fn test_hash_scheme_names() {
+    for scheme in HashScheme::ALL {
+        assert_eq!(Some(scheme), HashScheme::from_name(scheme.name()));
+    }
+
+    assert_eq!(
+        Some(HashScheme::Sha512Crypt),
+        HashScheme::from_name("SHA512-Crypt")
+    );
+    assert_eq!(None, HashScheme::from_name("md5"));
+}
+
+#[test]
+fn test_sha512_crypt_vectors() {
+    let table = [
+        // from the specification
+        (
+            "Hello world!",
+            "saltstring",
+            None,
+            "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1",
+        ),
+        (
+            "Hello world!",
+            "saltstringsaltstring",
+            Some(10000),
+            "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v.",
+        ),
+        (
+            "we have a short salt string but not a short password",
+            "short",
+            Some(77777),
+            "$6$rounds=77777$short$WuQyW2YR.hBNpjjRhpYD/ifIw05xdfeEyQoMxIXbkvr0gge1a1x3yRULJ5CCaUeOxFmtlcGZelFl5CxtgfiAc0",
+        ),
+        (
+            "a very much longer text to encrypt.  This one even stretches over morethan one line.",
+            "anotherlongsaltstring",
+            Some(1400),
+            "$6$rounds=1400$anotherlongsalts$POfYwTEok97VWcjxIiSOjiykti.o/pQs.wPvMxQ6Fm7I6IoYN3CmLs66x9t0oSwbtEW7o7UmJEiDwGqd8p4ur1",
+        ),
+        (
+            "the minimum number is still observed",
+            "roundstoolow",
+            Some(10),
+            "$6$rounds=1000$roundstoolow$kUMsbe306n21p9R.FRkW3IGn.S9NPN0x50YhH1xhLsPuWGsUSklZt58jaTfF4ZEQpyUNGc0dqbpBYYBaHHrsX.",
+        ),
+        // from glibc crypt(3)
+        (
+            "correct-HORSE-battery-staple-42!",
+            "xkpasswd",
+            None,
+            "$6$xkpasswd$5BcJzKpzfeSsTiSE9lJH23CdS8K8RAVa4/9JEvsI/ToAU0TsCBUFRQZtFZwoDM4LSXoWLxZqMCPXVQPpqo8ed/",
+        ),
+        (
+            "6237*JENSEN*DESTROYS*pull*crockett*MADAM*BLAMED*4775???????????~~~~~~",
+            "toolongsaltstring",
+            Some(1000),
+            "$6$rounds=1000$toolongsaltstrin$5XE3Ekz708Kj/egtU706anzJB5u6yRUcIs3x2yw58PAGrJtmoOJJaqwoVF1uXBzVQfjI2zUAcaHJOoIbDtTdl/",
+        ),
+    ];
+
+    for (passwd, salt, rounds, expected) in table {
+        assert_eq!(expected, sha512_crypt(passwd, salt, rounds), "{}", passwd);
+    }
+}
+
+#[test]
+fn test_bcrypt_vectors() {
+    // from OpenBSD's bcrypt tests
+    assert_eq!(
+        Ok("$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW".to_string()),
+        bcrypt_hash("U*U", 5, BCRYPT_SALT, BcryptVersion::TwoB)
+    );
+    assert_eq!(
+        Ok("$2y$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW".to_string()),
+        bcrypt_hash("U*U", 5, BCRYPT_SALT, BcryptVersion::TwoY)
+    );
+
+    assert_eq!(
+        Err("bcrypt only supports passwords up to 72 bytes, got 73".to_string()),
+        bcrypt_hash(&"a".repeat(73), 5, BCRYPT_SALT, BcryptVersion::TwoB)
+    );
+}
+
+#[test]
+fn test_argon2id_vectors() {
+    // from the reference implementation
+    assert_eq!(
+        Ok("$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMaO6Mm5c8y7cJHAph8ArZWb2GRPPc".to_string()),
+        argon2id_hash("password", b"somesalt", 1 << 16, 2)
+    );
+}
+
+#[test]
+fn test_hash_with_rng() {
+    let passwd = "correct-HORSE-battery-staple-42!";
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let hash = HashScheme::Sha512Crypt
+        .hash_with_rng(passwd, &mut rng)
+        .unwrap();
+    let salt = hash.split('$').nth(2).unwrap();
+    assert_eq!(16, salt.len());
+    assert_eq!(sha512_crypt(passwd, salt, None), hash);
+
+    let hash = HashScheme::Bcrypt.hash_with_rng(passwd, &mut rng).unwrap();
+    assert!(hash.starts_with("$2b$12$"));
+    assert!(bcrypt::verify(passwd, &hash).unwrap());
+
+    let hash = HashScheme::Htpasswd
+        .hash_with_rng(passwd, &mut rng)
+        .unwrap();
+    assert!(hash.starts_with("$2y$10$"));
+    assert!(bcrypt::verify(passwd, &hash).unwrap());
+
+    let hash = HashScheme::Argon2id
+        .hash_with_rng(passwd, &mut rng)
+        .unwrap();
+    assert!(hash.starts_with("$argon2id$v=19$m=19456,t=2,p=1$"));
+
+    // fresh salts every time
+    assert_ne!(
+        HashScheme::Sha512Crypt.hash(passwd),
+        HashScheme::Sha512Crypt.hash(passwd)
+    );
+}
@@ -6,11 +6,15 @@
 //!
 //! ## Features
 //!
-//! - **CLI application** for generating secure, memorable passwords
+//! - **CLI application** for generating secure, memorable passwords,
+//!   with a local HTTP API & JSON-RPC over stdio behind the `server` & `stdio` features
 //! - **WASM module** for web integration
+//! - **C & Python bindings** behind the `ffi` & `python` features
 //! - **Multiple language support**: English, German, Spanish, French, Portuguese
 //! - **Configurable presets**: AppleID, Web16, Web32, WiFi, XKCD, and more
 //! - **Entropy calculation** to help assess password strength
+//! - **Password hashes & Wi-Fi QR codes** behind the `hash` & `qr` features
+//! - **`no_std` support**: the core generator only needs `alloc` without the default `std` feature
 //!
 //! ## Usage (Library)
 //!
@@ -20,19 +24,44 @@
 //!
 //! let generator = Xkpasswd::for_language(Language::English);
 //! let settings = Settings::default();
-//! let (password, entropy) = generator.gen_pass(&settings);
-//! println!("Password: {}, Entropy: {:.2}-{:.2} bits", password, entropy.blind_min, entropy.blind_max);
+//! let (password, entropy) = generator.gen_pass(&settings).unwrap();
+//! println!("Password: {}, Entropy: {:.2}-{:.2} bits", password.as_str(), entropy.blind_min, entropy.blind_max);
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod bit_flags;
+mod compat;
+#[cfg(feature = "derive")]
+pub mod derive;
+pub mod dice;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "hash")]
+pub mod hash;
 pub mod prelude;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "qr")]
+pub mod qr;
+pub mod secret;
 pub mod settings;
+pub mod spell;
+#[cfg(feature = "cli")]
+mod value_enum;
+#[cfg(feature = "wasm")]
 mod wasm;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "wasm"))]
 mod tests {
     use super::bit_flags::*;
+    use super::prelude::{Entropy, Keyboard, Language, Preset};
+    #[cfg(feature = "wasm_qr")]
+    use super::qr::WifiSecurity;
     use super::wasm::*;
+    use wasm_bindgen::{JsCast, JsValue};
     use wasm_bindgen_test::*;
     wasm_bindgen_test_configure!(run_in_browser);
 
@@ -42,13 +71,194 @@ mod tests {
 
         let settings = WasmSettings::default()
             .with_words_count(3)
+            .unwrap()
             .with_word_lengths(None, Some(8))
+            .unwrap()
             .with_separators(".")
             .with_padding_digits(None, Some(2))
             .with_padding_symbols("!@#$%^&*-_=+:|~?/;")
             .with_padding_symbol_lengths(None, Some(2))
             .with_word_transforms(WordTransform::Lowercase | WordTransform::Uppercase)
-            .with_fixed_padding();
-        assert_eq!(4, pass.gen_pass(&settings).passwd().split('.').count());
+            .unwrap()
+            .with_fixed_padding()
+            .unwrap();
+        assert_eq!(
+            4,
+            pass.gen_pass(&settings)
+                .unwrap()
+                .passwd()
+                .split('.')
+                .count()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_for_language() {
+        let pass = WasmXkpasswd::for_language(Language::German).unwrap();
+        assert!(pass.gen_pass(&WasmSettings::default()).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_gen_pass_keyboard() {
+        let settings = WasmSettings::from_preset(Preset::Wifi)
+            .with_separators("^")
+            .with_padding_symbol_lengths(Some(0), Some(0));
+
+        // `^` is a dead key on German keyboards, kept as it's given
+        let pass = WasmXkpasswd::for_language(Language::German).unwrap();
+        let passwd = pass.gen_pass(&settings).unwrap().passwd();
+        assert!(passwd.contains('^'));
+
+        // unlike symbols of presets, `~` being typed with AltGr
+        let settings = WasmSettings::from_preset(Preset::WindowsNtlmV1)
+            .with_padding_symbol_lengths(Some(0), Some(0));
+        for _ in 0..20 {
+            let passwd = pass.gen_pass(&settings).unwrap().passwd();
+            assert!(!passwd.contains('~'));
+        }
+
+        // a chosen keyboard leaves out any untypable symbol
+        let settings = WasmSettings::default()
+            .with_separators("^")
+            .with_keyboard(Some(Keyboard::Fr));
+        let passwd = pass.gen_pass(&settings).unwrap().passwd();
+        assert!(!passwd.contains('^'));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_from_dictionary() {
+        let pass = WasmXkpasswd::from_dictionary(b"4:fooz,barz\n5:fooza".to_vec()).unwrap();
+        assert_eq!(vec![4, 5], pass.word_lengths());
+
+        let settings = WasmSettings::default()
+            .with_available_word_lengths(pass.word_lengths())
+            .unwrap()
+            .with_words_count(2)
+            .unwrap();
+        let passwd = pass.gen_pass(&settings).unwrap().passwd().to_lowercase();
+        assert!(passwd.contains("fooz") || passwd.contains("barz"));
+
+        assert!(WasmXkpasswd::from_dictionary(vec![]).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_settings_getters() {
+        let settings = WasmSettings::from_preset(Preset::Xkcd);
+        assert_eq!(4, settings.words_count());
+        assert_eq!(vec![4, 8], settings.word_lengths());
+        assert_eq!("-", settings.separators());
+        assert_eq!(vec![0, 0], settings.padding_digits());
+        assert_eq!(None, settings.adaptive_padding());
+        assert!(settings.describe().contains("4 word(s)"));
+
+        let settings = settings
+            .with_adaptive_padding(24)
+            .unwrap()
+            .with_excluded_words(vec!["Horse".to_string()])
+            .with_readable(true);
+        assert_eq!(Some(24), settings.adaptive_padding());
+        assert_eq!(vec!["horse".to_string()], settings.excluded_words());
+        assert!(settings.readable());
+        assert_eq!(None, settings.keyboard());
+
+        let json = settings.to_json();
+        let field = |key: &str| js_sys::Reflect::get(&json, &key.into()).unwrap();
+        assert_eq!(JsValue::from(4), field("wordsCount"));
+        assert_eq!(JsValue::from(24), field("adaptivePadding"));
+        assert_eq!(JsValue::from("-"), field("separators"));
+        assert_eq!(JsValue::TRUE, field("readable"));
+        assert_eq!(JsValue::NULL, field("keyboard"));
+
+        let settings = settings.with_keyboard(Some(Keyboard::PtBr));
+        assert_eq!(Some(Keyboard::PtBr), settings.keyboard());
+        let json = settings.to_json();
+        let field = |key: &str| js_sys::Reflect::get(&json, &key.into()).unwrap();
+        assert_eq!(JsValue::from("pt-br"), field("keyboard"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_settings_from_object() {
+        let object = js_sys::JSON::parse(
+            r#"{
+                "wordsCount": 5,
+                "wordLengths": [null, 6],
+                "transforms": ["lowercase"],
+                "separators": "-",
+                "padding": { "type": "adaptive", "length": 63 }
+            }"#,
+        )
+        .unwrap();
+        let settings = WasmSettings::from_object(object).unwrap();
+        assert_eq!(5, settings.words_count());
+        assert_eq!(vec![4, 6], settings.word_lengths());
+        assert_eq!(WordTransform::Lowercase as u8, settings.word_transforms());
+        assert_eq!(Some(63), settings.adaptive_padding());
+
+        let object = js_sys::JSON::parse(r#"{ "wordsCount": "5", "color": "red" }"#).unwrap();
+        let error = WasmSettings::from_object(object).unwrap_err();
+        let error: &js_sys::Error = error.dyn_ref().unwrap();
+        assert_eq!("SettingsError", String::from(error.name()));
+        let errors = js_sys::Reflect::get(error, &"errors".into()).unwrap();
+        assert_eq!(2, js_sys::Array::from(&errors).length());
+
+        let object =
+            js_sys::JSON::parse(r#"{ "padding": { "type": "adaptive", "length": 1e11 } }"#)
+                .unwrap();
+        assert!(WasmSettings::from_object(object).is_err());
+
+        assert!(WasmSettings::from_object(JsValue::from(5)).is_err());
+        assert!(WasmSettings::default().with_words_count(0).is_err());
+    }
+
+    #[cfg(feature = "wasm_toml")]
+    #[wasm_bindgen_test]
+    fn test_settings_from_toml() {
+        let settings = WasmSettings::from_toml("preset = \"wifi\"\nwords_count = 3").unwrap();
+        assert_eq!(3, settings.words_count());
+        assert_eq!(Some(63), settings.adaptive_padding());
+
+        assert!(WasmSettings::from_toml("words_count = 0").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_settings_code() {
+        let settings = WasmSettings::from_preset(Preset::Wifi)
+            .with_words_count(5)
+            .unwrap();
+        let code = settings.to_code();
+        assert!(code.starts_with("xk1:w5-"));
+        assert_eq!(code, WasmSettings::from_code(&code).unwrap().to_code());
+
+        assert!(WasmSettings::from_code("xk1:w0").is_err());
+        assert!(WasmSettings::from_code("foo").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_listings() {
+        assert_eq!(Preset::ALL.to_vec(), list_presets());
+        assert!(list_languages().contains(&Language::English));
+        assert!(Entropy::default().describe().contains("guesses/sec"));
+    }
+
+    #[cfg(feature = "wasm_qr")]
+    #[wasm_bindgen_test]
+    fn test_wifi_qr_svg() {
+        let passwd = "correct;horse,battery:staple";
+
+        let svg = wifi_qr_svg("Guests", WifiSecurity::Wpa, passwd).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(wifi_qr_svg("", WifiSecurity::Wpa, passwd).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_spell_passwd() {
+        assert_eq!(
+            vec!["Capital horse", "pipe", "four two"],
+            spell_passwd("Horse|42", Language::English, false)
+        );
+        assert_eq!(
+            vec!["lower ox: oscar, x-ray"],
+            spell_passwd("ox", Language::German, true)
+        );
     }
 }
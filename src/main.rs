@@ -1,21 +1,139 @@
-mod bit_flags;
 mod cli;
-mod prelude;
-mod settings;
+
+// the CLI builds on the library crate rather than compiling its modules again
+use xkpasswd::{bit_flags, derive, hash, prelude, qr, secret, settings, spell};
 
 use cli::*;
 use prelude::*;
 use settings::*;
+use std::io::{self, Write};
 
 fn main() {
-    let mut cli = Cli::init();
-    let settings: Settings = cli.parse_settings();
-    let language = cli.language();
-    log::info!("generating password in {:?} with {}", language, settings);
+    let cli = Cli::init();
+    if cli.interactive() {
+        return run_interactive(cli);
+    }
+
+    if cli.stdio() {
+        return run_stdio(&cli);
+    }
+
+    #[cfg(feature = "server")]
+    if let Some(CliCommand::Serve(args)) = cli.subcommand() {
+        return run_server(&cli, args);
+    }
+
+    let languages = cli.languages();
+    let pass_generator = Xkpasswd::for_languages(&languages);
+
+    let settings: Settings = cli.parse_settings(&pass_generator.source().word_lengths());
+    log::info!("generating password in {:?} with {}", languages, settings);
+
+    if let Some(CliCommand::Config(args)) = cli.subcommand() {
+        let ConfigCommand::Dump(args) = &args.command;
+        return cli.dump_config(&settings, args);
+    }
+
+    cli.warn_untypable_symbols(&settings);
+
+    // derived passwords don't follow the keyboard of the languages, so that they stay the same
+    let gen_settings = cli.generation_settings(&settings);
+
+    if cli.chpasswd() {
+        let result = cli.output_chpasswd(io::stdin().lock(), io::stdout().lock(), || {
+            pass_generator
+                .gen_pass(&gen_settings)
+                .map(|(passwd, _)| passwd)
+        });
+
+        if let Err(err) = result {
+            Cli::exit_with_error(format!("Unable to generate passwords: {}", err));
+        }
+        return;
+    }
+
+    if cli.dice_table() || cli.uses_dice() {
+        let table = pass_generator.dice_table(&settings).unwrap_or_else(|err| {
+            Cli::exit_with_error(format!("Unable to build dice table: {}", err))
+        });
+        log::info!(
+            "{} dice per word, {:.2} bits of entropy per word",
+            table.dice_per_word(),
+            table.entropy_per_word()
+        );
+
+        if cli.dice_table() {
+            // ignore errors of closed pipes, when paging through the table
+            let _ = write!(io::stdout().lock(), "{}", table);
+            return;
+        }
+
+        let rolls = cli.dice_rolls(table.dice_per_word());
+        let rolls: Vec<&str> = rolls.iter().map(String::as_str).collect();
+        let words = table.words_for(&rolls).unwrap_or_else(|err| {
+            Cli::exit_with_error(format!("Unable to generate password: {}", err))
+        });
+
+        cli.output_passwd(&words.join(" "));
+        return;
+    }
 
-    let pass_generator = Xkpasswd::for_language(language);
-    let (passwd, entropy) = pass_generator.gen_pass(&settings);
+    let result = match cli.subcommand() {
+        Some(CliCommand::Derive(args)) => {
+            let master_secret = Cli::read_master_secret();
+            log::info!("deriving password for '{}' #{}", args.site, args.counter);
+            pass_generator.derive_pass(&settings, &master_secret, &args.site, args.counter)
+        }
+        Some(CliCommand::Config(_)) => unreachable!("config commands are handled above"),
+        #[cfg(feature = "server")]
+        Some(CliCommand::Serve(_)) => unreachable!("serve command is handled above"),
+        None => pass_generator.gen_pass(&gen_settings),
+    };
+
+    let (passwd, entropy) = result.unwrap_or_else(|err| {
+        Cli::exit_with_error(format!("Unable to generate password: {}", err))
+    });
     log::info!("calculated entropy: {}", entropy);
 
-    println!("{}", passwd);
+    cli.output_passwd(&passwd);
+}
+
+#[cfg(feature = "server")]
+fn run_server(cli: &Cli, args: &ServeArgs) {
+    let server = PassServer::new(cli)
+        .unwrap_or_else(|err| Cli::exit_with_error(format!("Invalid settings: {}", err)));
+
+    if let Err(err) = server.serve(args) {
+        Cli::exit_with_error(format!("Server failed: {}", err));
+    }
+}
+
+#[cfg(feature = "stdio")]
+fn run_stdio(cli: &Cli) {
+    let session = RpcSession::new(cli)
+        .unwrap_or_else(|err| Cli::exit_with_error(format!("Invalid settings: {}", err)));
+
+    if let Err(err) = session.run(io::stdin().lock(), io::stdout().lock()) {
+        Cli::exit_with_error(format!("JSON-RPC session failed: {}", err));
+    }
+}
+
+#[cfg(not(feature = "stdio"))]
+fn run_stdio(_: &Cli) {
+    Cli::exit_with_error("JSON-RPC over stdio requires the 'stdio' feature".to_string());
+}
+
+#[cfg(feature = "tui")]
+fn run_interactive(cli: Cli) {
+    let tui = Tui::new(cli)
+        .unwrap_or_else(|err| Cli::exit_with_error(format!("Invalid settings: {}", err)));
+
+    if let Err(err) = tui.run() {
+        Cli::exit_with_error(format!("Interactive mode failed: {}", err));
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_interactive(_: Cli) {
+    Cli::exit_with_error("interactive mode requires the 'tui' feature".to_string());
 }
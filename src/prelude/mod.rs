@@ -1,10 +1,13 @@
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashMap;
-use std::fmt;
-use std::ops::Range;
-use std::str::*;
+use crate::compat::*;
+use crate::secret::{Passwd, Wipe};
+use core::fmt;
+use core::ops::Range;
+use core::str::*;
+use rand::RngCore;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -13,6 +16,11 @@ pub enum PaddingStrategy {
     Adaptive(usize),
 }
 
+impl PaddingStrategy {
+    /// Longest adaptive padding length, passwords being allocated to that length.
+    pub const MAX_ADAPTIVE_LENGTH: usize = u8::MAX as usize;
+}
+
 #[derive(Debug)]
 pub enum PaddingResult {
     Unchanged,
@@ -20,8 +28,8 @@ pub enum PaddingResult {
     Pad(String),
 }
 
-#[wasm_bindgen]
-#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Preset {
     Default,
     AppleID,
@@ -33,7 +41,41 @@ pub enum Preset {
     Xkcd,
 }
 
-#[wasm_bindgen]
+impl Preset {
+    pub const ALL: [Preset; 8] = [
+        Preset::Default,
+        Preset::AppleID,
+        Preset::WindowsNtlmV1,
+        Preset::SecurityQuestions,
+        Preset::Web16,
+        Preset::Web32,
+        Preset::Wifi,
+        Preset::Xkcd,
+    ];
+
+    /// Name used in the CLI & config files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Default => "default",
+            Preset::AppleID => "apple-id",
+            Preset::WindowsNtlmV1 => "ntlm",
+            Preset::SecurityQuestions => "secq",
+            Preset::Web16 => "web16",
+            Preset::Web32 => "web32",
+            Preset::Wifi => "wifi",
+            Preset::Xkcd => "xkcd",
+        }
+    }
+
+    /// Case-insensitive lookup of [`Preset::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct GuessTime {
     pub years: usize,
@@ -108,16 +150,17 @@ impl GuessTime {
             };
         }
 
+        // casting positive numbers rounds them down
         let mut time_to_guess =
-            2.0f64.powi(amount as i32) / (Self::SECONDS_PER_DAY * Self::GUESSES_PER_SEC as f64);
+            (1u64 << amount) as f64 / (Self::SECONDS_PER_DAY * Self::GUESSES_PER_SEC as f64);
 
-        let years = (time_to_guess / Self::DAYS_PER_YEAR).floor() as usize;
+        let years = (time_to_guess / Self::DAYS_PER_YEAR) as usize;
         time_to_guess -= Self::DAYS_PER_YEAR * years as f64;
 
-        let months = (time_to_guess / Self::DAYS_PER_MONTH).floor() as u8;
+        let months = (time_to_guess / Self::DAYS_PER_MONTH) as u8;
         time_to_guess -= Self::DAYS_PER_MONTH * months as f64;
 
-        let days = time_to_guess.floor() as u8;
+        let days = time_to_guess as u8;
 
         Self {
             days,
@@ -127,7 +170,7 @@ impl GuessTime {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Entropy {
     pub blind_min: usize,
@@ -152,6 +195,7 @@ impl fmt::Display for Entropy {
     }
 }
 
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Language {
     English,
@@ -161,10 +205,112 @@ pub enum Language {
     Spanish,
 }
 
-type Dict<'a> = HashMap<u8, Vec<&'a str>>;
+impl Language {
+    pub const ALL: [Language; 5] = [
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Portuguese,
+        Language::Spanish,
+    ];
+
+    /// Whether the dictionary of the language is embedded, depending on `lang_*` features.
+    pub fn is_bundled(&self) -> bool {
+        match self {
+            Language::English => cfg!(feature = "lang_en"),
+            Language::French => cfg!(feature = "lang_fr"),
+            Language::German => cfg!(feature = "lang_de"),
+            Language::Portuguese => cfg!(feature = "lang_pt"),
+            Language::Spanish => cfg!(feature = "lang_es"),
+        }
+    }
+}
+
+/// Keyboard layouts restricting separators & padding symbols to those typable without
+/// dead keys, or on the first symbols page of mobile keyboards.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Keyboard {
+    Us,
+    Uk,
+    De,
+    Fr,
+    Es,
+    PtBr,
+    Ios,
+    Android,
+}
+
+impl Keyboard {
+    pub const ALL: [Keyboard; 8] = [
+        Keyboard::Us,
+        Keyboard::Uk,
+        Keyboard::De,
+        Keyboard::Fr,
+        Keyboard::Es,
+        Keyboard::PtBr,
+        Keyboard::Ios,
+        Keyboard::Android,
+    ];
+
+    /// Name used in the CLI, config files & settings codes.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Keyboard::Us => "us",
+            Keyboard::Uk => "uk",
+            Keyboard::De => "de",
+            Keyboard::Fr => "fr",
+            Keyboard::Es => "es",
+            Keyboard::PtBr => "pt-br",
+            Keyboard::Ios => "ios",
+            Keyboard::Android => "android",
+        }
+    }
+
+    /// Case-insensitive lookup of [`Keyboard::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|keyboard| keyboard.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Usual layout for a language, used when no keyboard is chosen.
+    pub fn for_language(language: Language) -> Self {
+        match language {
+            Language::English => Keyboard::Us,
+            Language::French => Keyboard::Fr,
+            Language::German => Keyboard::De,
+            Language::Portuguese => Keyboard::PtBr,
+            Language::Spanish => Keyboard::Es,
+        }
+    }
+
+    /// ASCII symbols typable with this keyboard, besides space, letters & digits.
+    pub fn symbols(&self) -> &'static str {
+        match self {
+            Keyboard::Us | Keyboard::Uk => "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~",
+            // ^ & ` are dead keys, ~ is typed with AltGr
+            Keyboard::De => "!\"#$%&'()*+,-./:;<=>?@[\\]_{|}~",
+            // ^, ` & ~ are dead keys
+            Keyboard::Fr | Keyboard::Es | Keyboard::PtBr => "!\"#$%&'()*+,-./:;<=>?@[\\]_{|}",
+            // first page of symbols, the others need another switch
+            Keyboard::Ios => "!\"$&'(),-./:;?@",
+            Keyboard::Android => "!\"#$&'()*+,-./:;?@_",
+        }
+    }
+
+    /// Whether a separator or padding symbol is typable with this keyboard.
+    pub fn can_type(&self, symbol: char) -> bool {
+        symbol == ' ' || symbol.is_ascii_alphanumeric() || self.symbols().contains(symbol)
+    }
+}
+
+/// Words grouped by length, a `HashMap` with the `std` feature and a `BTreeMap` without.
+pub type Dict<'a> = Map<u8, Vec<&'a str>>;
 
 pub trait L10n {
     fn for_language(language: Language) -> Self;
+    fn for_languages(languages: &[Language]) -> Self;
 }
 
 pub trait Builder: Default + fmt::Display + Sized {
@@ -179,23 +325,311 @@ pub trait Builder: Default + fmt::Display + Sized {
     fn with_padding_symbols(&self, symbols: &str) -> Self;
     fn with_padding_symbol_lengths(&self, prefix: Option<u8>, suffix: Option<u8>) -> Self;
     fn with_padding_strategy(&self, strategy: PaddingStrategy) -> Result<Self, String>;
+    fn with_available_word_lengths(&self, lengths: &[u8]) -> Result<Self, String>;
     fn with_word_transforms(&self, transform: u8) -> Result<Self, String>;
+    fn with_excluded_words(&self, words: &[&str]) -> Self;
+    fn with_included_words(&self, words: &[&str]) -> Self;
+    /// Leave out visually ambiguous symbols, digits & words, e.g. `|`, `0`, `1` or `corn`.
+    fn with_readable(&self, readable: bool) -> Self;
+    /// Only use separators & padding symbols typable with the keyboard, any of them if `None`.
+    fn with_keyboard(&self, keyboard: Option<Keyboard>) -> Self;
+    /// Settings decoded from a code of `Settings::to_code`, validated by the builder methods above.
+    fn from_code(code: &str) -> Result<Self, String>;
     fn from_preset(preset: Preset) -> Self;
 }
 
 pub trait Randomizer {
     fn word_lengths(&self) -> Range<u8>;
-    fn rand_words(&self, pool: &[&str]) -> Vec<String>;
-    fn rand_separator(&self) -> String;
-    fn rand_prefix(&self) -> (String, String);
-    fn rand_suffix(&self) -> (String, String);
-    fn adjust_padding(&self, pass_length: usize) -> PaddingResult;
+    fn filter_words<'a>(&self, pool: Cow<'a, [&'a str]>) -> Result<Cow<'a, [&'a str]>, String>;
+    fn rand_words(&self, rng: &mut dyn RngCore, pool: &[&str]) -> Vec<String>;
+    fn rand_separator(&self, rng: &mut dyn RngCore) -> String;
+    fn rand_prefix(&self, rng: &mut dyn RngCore) -> (String, String);
+    fn rand_suffix(&self, rng: &mut dyn RngCore) -> (String, String);
+    fn adjust_padding(&self, rng: &mut dyn RngCore, pass_length: usize) -> PaddingResult;
     fn calc_entropy(&self, pool_size: usize) -> Entropy;
+
+    /// Pool size given to `calc_entropy`, lower than `pool_size` for settings
+    /// picking some words of `pool` only with some of their word transforms.
+    fn usable_pool_size(&self, _pool: &[&str], pool_size: usize) -> usize {
+        pool_size
+    }
+}
+
+/// A source of candidate words for generated passwords.
+///
+/// Embedded dictionaries implement it as [`DictIndex`], but any custom source
+/// (a filtered list, a database, a generator...) can be plugged into [`Xkpasswd::new`].
+pub trait WordSource {
+    /// Words having exactly `length` characters.
+    fn words_of_length(&self, length: u8) -> Vec<&str>;
+
+    /// Lengths having at least one word, in ascending order.
+    fn word_lengths(&self) -> Vec<u8> {
+        (1..=u8::MAX)
+            .filter(|&len| !self.words_of_length(len).is_empty())
+            .collect()
+    }
+
+    /// Relative weight of a word when randomizing, all words are equally likely by default.
+    /// Weights must be positive, sources with words weighted 0 are rejected when generating.
+    fn weight(&self, _word: &str) -> usize {
+        1
+    }
+
+    /// All words with lengths in the given range.
+    fn words_in(&self, lengths: Range<u8>) -> Cow<'_, [&str]> {
+        Cow::Owned(lengths.flat_map(|len| self.words_of_length(len)).collect())
+    }
+
+    /// Number of distinct words with lengths in the given range, used for entropy calculation.
+    fn pool_size(&self, lengths: Range<u8>) -> usize {
+        self.words_in(lengths).len()
+    }
+}
+
+impl WordSource for Dict<'_> {
+    fn words_of_length(&self, length: u8) -> Vec<&str> {
+        self.get(&length).cloned().unwrap_or_default()
+    }
+
+    fn word_lengths(&self) -> Vec<u8> {
+        let mut lengths: Vec<u8> = self
+            .iter()
+            .filter(|(_, words)| !words.is_empty())
+            .map(|(&len, _)| len)
+            .collect();
+        lengths.sort();
+        lengths
+    }
+
+    fn pool_size(&self, lengths: Range<u8>) -> usize {
+        lengths
+            .filter_map(|len| self.get(&len))
+            .map(|words| words.len())
+            .sum()
+    }
+}
+
+impl WordSource for Vec<&str> {
+    fn words_of_length(&self, length: u8) -> Vec<&str> {
+        self.iter()
+            .filter(|word| word.chars().count() == length as usize)
+            .copied()
+            .collect()
+    }
+
+    fn word_lengths(&self) -> Vec<u8> {
+        let lengths: BTreeSet<u8> = self
+            .iter()
+            .filter_map(|word| u8::try_from(word.chars().count()).ok())
+            .filter(|&len| len > 0)
+            .collect();
+        lengths.into_iter().collect()
+    }
+}
+
+/// Words sorted by length along with per-length offsets,
+/// so that words of any length range form a contiguous slice.
+///
+/// Bundled dictionaries are converted at build time into static indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DictIndex<'a> {
+    words: Cow<'a, [&'a str]>,
+    // words of length `len` are in `words[offsets[len]..offsets[len + 1]]`
+    offsets: Cow<'a, [usize]>,
+}
+
+impl DictIndex<'static> {
+    pub const fn from_static(
+        words: &'static [&'static str],
+        offsets: &'static [usize],
+    ) -> DictIndex<'static> {
+        DictIndex {
+            words: Cow::Borrowed(words),
+            offsets: Cow::Borrowed(offsets),
+        }
+    }
+}
+
+impl<'a> DictIndex<'a> {
+    /// Parse a dictionary in the same `length:word,word,...` format as bundled ones.
+    pub fn from_bytes(dict_bytes: &'a [u8]) -> Self {
+        Self::from(load_dict(dict_bytes))
+    }
+
+    /// Words with lengths in the given range, without any allocation.
+    pub fn words_in_range(&self, lengths: Range<u8>) -> &[&'a str] {
+        &self.words[offsets_range(&self.offsets, lengths)]
+    }
+
+    fn from_groups<I: IntoIterator<Item = (u8, Vec<&'a str>)>>(groups: I) -> Self {
+        let mut words: Vec<&'a str> = vec![];
+        let mut offsets: Vec<usize> = vec![0];
+
+        for (len, group) in groups {
+            offsets.resize(len as usize + 1, words.len());
+            words.extend(group);
+            offsets.push(words.len());
+        }
+
+        DictIndex {
+            words: Cow::Owned(words),
+            offsets: Cow::Owned(offsets),
+        }
+    }
+}
+
+impl<'a> From<Dict<'a>> for DictIndex<'a> {
+    fn from(dict: Dict<'a>) -> Self {
+        let mut groups: Vec<(u8, Vec<&'a str>)> = dict.into_iter().collect();
+        groups.sort_by_key(|(len, _)| *len);
+        Self::from_groups(groups)
+    }
+}
+
+impl WordSource for DictIndex<'_> {
+    fn words_of_length(&self, length: u8) -> Vec<&str> {
+        self.words_in_range(length..length.saturating_add(1))
+            .to_vec()
+    }
+
+    fn word_lengths(&self) -> Vec<u8> {
+        offsets_lengths(&self.offsets)
+    }
+
+    fn words_in(&self, lengths: Range<u8>) -> Cow<'_, [&str]> {
+        Cow::Borrowed(self.words_in_range(lengths))
+    }
+
+    fn pool_size(&self, lengths: Range<u8>) -> usize {
+        self.words_in_range(lengths).len()
+    }
+}
+
+/// Same as [`DictIndex`], owning the dictionary text instead of borrowing it,
+/// for dictionaries loaded at runtime rather than bundled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedDictIndex {
+    text: String,
+    // byte ranges of words in `text`, sorted & offset by length like `DictIndex`
+    spans: Vec<Range<usize>>,
+    offsets: Vec<usize>,
+}
+
+impl OwnedDictIndex {
+    /// Parse a dictionary in the same format as [`DictIndex::from_bytes`],
+    /// failing on invalid UTF-8 or dictionaries without any words.
+    pub fn from_bytes(dict_bytes: Vec<u8>) -> Result<Self, String> {
+        let text =
+            String::from_utf8(dict_bytes).map_err(|err| format!("invalid dictionary: {}", err))?;
+
+        let index = DictIndex::from_bytes(text.as_bytes());
+        if index.words.is_empty() {
+            return Err("no words available in dictionary".to_string());
+        }
+
+        // parsed words are sub-slices of `text`
+        let spans = index
+            .words
+            .iter()
+            .map(|word| {
+                let start = word.as_ptr() as usize - text.as_ptr() as usize;
+                start..start + word.len()
+            })
+            .collect();
+        let offsets = index.offsets.into_owned();
+
+        Ok(OwnedDictIndex {
+            text,
+            spans,
+            offsets,
+        })
+    }
+
+    // same as parsing, for words that may contain any character
+    fn from_words<'w, I: IntoIterator<Item = &'w str>>(words: I) -> Self {
+        let mut sorted: Vec<(u8, &str)> = words
+            .into_iter()
+            .filter_map(|word| Some((u8::try_from(word.chars().count()).ok()?, word)))
+            .collect();
+        sorted.sort_by_key(|(len, _)| *len);
+
+        let mut text = String::new();
+        let mut spans: Vec<Range<usize>> = vec![];
+        let mut offsets: Vec<usize> = vec![0];
+
+        for (len, word) in sorted {
+            offsets.resize(len as usize + 1, spans.len());
+            let start = text.len();
+            text.push_str(word);
+            spans.push(start..text.len());
+        }
+        offsets.push(spans.len());
+
+        OwnedDictIndex {
+            text,
+            spans,
+            offsets,
+        }
+    }
+
+    fn words_in_range(&self, lengths: Range<u8>) -> impl Iterator<Item = &str> {
+        self.spans[offsets_range(&self.offsets, lengths)]
+            .iter()
+            .map(|span| &self.text[span.clone()])
+    }
+}
+
+impl WordSource for OwnedDictIndex {
+    fn words_of_length(&self, length: u8) -> Vec<&str> {
+        self.words_in_range(length..length.saturating_add(1))
+            .collect()
+    }
+
+    fn word_lengths(&self) -> Vec<u8> {
+        offsets_lengths(&self.offsets)
+    }
+
+    fn words_in(&self, lengths: Range<u8>) -> Cow<'_, [&str]> {
+        Cow::Owned(self.words_in_range(lengths).collect())
+    }
+
+    fn pool_size(&self, lengths: Range<u8>) -> usize {
+        offsets_range(&self.offsets, lengths).len()
+    }
+}
+
+// indices of words with lengths in the given range, for words sorted by length
+fn offsets_range(offsets: &[usize], lengths: Range<u8>) -> Range<usize> {
+    let last = offsets.len().saturating_sub(1);
+    let start = (lengths.start as usize).min(last);
+    let end = (lengths.end as usize).clamp(start, last);
+
+    match (offsets.get(start), offsets.get(end)) {
+        (Some(&from), Some(&to)) => from..to,
+        _ => 0..0,
+    }
+}
+
+fn offsets_lengths(offsets: &[usize]) -> Vec<u8> {
+    offsets
+        .windows(2)
+        .enumerate()
+        .filter(|(_, bounds)| bounds[1] > bounds[0])
+        .map(|(len, _)| len as u8)
+        .collect()
 }
 
 #[derive(Debug)]
-pub struct Xkpasswd {
-    dict: Dict<'static>,
+pub struct Xkpasswd<W: WordSource = DictIndex<'static>> {
+    source: W,
+    languages: Vec<Language>,
+    safe_words: bool,
+    // words of the source left after the deny lists, filtered once with safe words,
+    // only for sources with denied words so that others are borrowed as is
+    safe_pool: Option<OwnedDictIndex>,
+    // whether all words have a weight of 1, checked once instead of on every generation
+    uniform_weights: bool,
 }
 
 impl Default for Xkpasswd {
@@ -218,73 +652,348 @@ impl Default for Xkpasswd {
 
 impl L10n for Xkpasswd {
     fn for_language(language: Language) -> Self {
-        let dict_bytes: &[u8] = match language {
-            #[cfg(feature = "lang_en")]
-            Language::English => include_bytes!("../assets/dict_en.txt"),
-            #[cfg(feature = "lang_de")]
-            Language::German => include_bytes!("../assets/dict_de.txt"),
-            #[cfg(feature = "lang_es")]
-            Language::Spanish => include_bytes!("../assets/dict_es.txt"),
-            #[cfg(feature = "lang_fr")]
-            Language::French => include_bytes!("../assets/dict_fr.txt"),
-            #[cfg(feature = "lang_pt")]
-            Language::Portuguese => include_bytes!("../assets/dict_pt.txt"),
-            #[allow(unreachable_patterns)]
-            _ => panic!("no language bundled"),
-        };
+        Xkpasswd::new(bundled_dict(language).clone()).with_languages(&[language])
+    }
+
+    fn for_languages(languages: &[Language]) -> Self {
+        let mut loaded: Vec<Language> = vec![];
+        let mut dicts: Vec<&DictIndex<'static>> = vec![];
 
-        let dict = load_dict(dict_bytes);
-        Xkpasswd { dict }
+        for &language in languages {
+            if !loaded.contains(&language) {
+                loaded.push(language);
+                dicts.push(bundled_dict(language));
+            }
+        }
+
+        match dicts.len() {
+            0 => Xkpasswd::default(),
+            1 => Xkpasswd::new(dicts[0].clone()).with_languages(&loaded),
+            _ => Xkpasswd::new(merge_dicts(&dicts)).with_languages(&loaded),
+        }
     }
 }
 
-impl Xkpasswd {
-    pub fn gen_pass<S: Randomizer>(&self, settings: &S) -> (String, Entropy) {
-        let mut all_words: Vec<&str> = vec![];
+impl<W: WordSource> Xkpasswd<W> {
+    pub fn new(source: W) -> Self {
+        let uniform_weights = source
+            .words_in(0..u8::MAX)
+            .iter()
+            .all(|word| source.weight(word) == 1);
+
+        Xkpasswd {
+            source,
+            languages: vec![],
+            safe_words: false,
+            safe_pool: None,
+            uniform_weights,
+        }
+    }
 
-        settings.word_lengths().for_each(|len| {
-            if let Some(words) = self.dict.get(&len) {
-                all_words.extend(words);
-            };
-        });
+    /// Skip words from the bundled deny lists (slurs, vulgar & sexually explicit words).
+    /// Bundled dictionaries are already filtered at build time,
+    /// so this mostly matters for custom word sources.
+    pub fn with_safe_words(mut self, safe_words: bool) -> Self {
+        self.safe_words = safe_words;
+        self.refresh_safe_pool();
+        self
+    }
+
+    /// Languages of the word source, whose deny lists are used with safe words.
+    /// Set by [`L10n`] constructors, custom sources of unknown languages use all deny lists.
+    pub fn with_languages(mut self, languages: &[Language]) -> Self {
+        self.languages = languages.to_vec();
+        self.refresh_safe_pool();
+        self
+    }
+
+    pub fn languages(&self) -> &[Language] {
+        &self.languages
+    }
 
-        let separator = &settings.rand_separator();
+    fn refresh_safe_pool(&mut self) {
+        if !self.safe_words {
+            self.safe_pool = None;
+            return;
+        }
+
+        let denied = load_denied_words(&self.languages);
+        let words = self.source.words_in(0..u8::MAX);
+        let safe_words: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|word| !denied.contains(word.to_lowercase().as_str()))
+            .collect();
+
+        // bundled dictionaries are already filtered at build time
+        self.safe_pool = if safe_words.len() < words.len() {
+            Some(OwnedDictIndex::from_words(safe_words))
+        } else {
+            None
+        };
+    }
+
+    pub fn source(&self) -> &W {
+        &self.source
+    }
+
+    #[cfg(feature = "std")]
+    pub fn gen_pass<S: Randomizer>(&self, settings: &S) -> Result<(Passwd, Entropy), String> {
+        self.gen_pass_with_rng(settings, &mut rand::thread_rng())
+    }
+
+    /// Same as [`Xkpasswd::gen_pass`], drawing all randomness from the given generator,
+    /// so that seeded generators always produce the same password.
+    // passwords are plain strings without the zeroize feature
+    #[allow(clippy::useless_conversion)]
+    pub fn gen_pass_with_rng<S: Randomizer, R: RngCore>(
+        &self,
+        settings: &S,
+        rng: &mut R,
+    ) -> Result<(Passwd, Entropy), String> {
+        let (all_words, pool_size) = self.filtered_pool(settings)?;
+
+        let mut separator = settings.rand_separator(rng);
         let mut words: Vec<String> = vec![];
 
-        let (prefix_symbols, prefix_digits) = settings.rand_prefix();
+        let (mut prefix_symbols, prefix_digits) = settings.rand_prefix(rng);
         if !prefix_digits.is_empty() {
             words.push(prefix_digits);
         }
 
-        words.extend(settings.rand_words(&all_words));
+        words.extend(settings.rand_words(rng, &all_words));
 
-        let (suffix_digits, suffix_symbols) = settings.rand_suffix();
+        let (suffix_digits, mut suffix_symbols) = settings.rand_suffix(rng);
         if !suffix_digits.is_empty() {
             words.push(suffix_digits);
         }
 
-        let passwd = format!(
-            "{}{}{}",
-            prefix_symbols,
-            words.join(separator),
-            suffix_symbols
-        );
+        // the password is allocated once to its final length, so that no partial copies are left behind
+        let joined_len = prefix_symbols.len()
+            + words.iter().map(String::len).sum::<usize>()
+            + separator.len() * words.len().saturating_sub(1)
+            + suffix_symbols.len();
+        // adaptive lengths are in characters, separators & symbols may be multi-byte
+        let joined_chars = prefix_symbols.chars().count()
+            + words.iter().map(|word| word.chars().count()).sum::<usize>()
+            + separator.chars().count() * words.len().saturating_sub(1)
+            + suffix_symbols.chars().count();
+        let padding_result = settings.adjust_padding(rng, joined_chars);
+
+        let mut passwd = String::with_capacity(match &padding_result {
+            PaddingResult::Pad(padded_symbols) => joined_len + padded_symbols.len(),
+            _ => joined_len,
+        });
+        passwd.push_str(&prefix_symbols);
+        for (idx, word) in words.iter().enumerate() {
+            if idx > 0 {
+                passwd.push_str(&separator);
+            }
+            passwd.push_str(word);
+        }
+        passwd.push_str(&suffix_symbols);
+
+        match padding_result {
+            PaddingResult::Unchanged => {}
+            PaddingResult::TrimTo(len) => {
+                if let Some((idx, _)) = passwd.char_indices().nth(len) {
+                    passwd.truncate(idx);
+                }
+            }
+            PaddingResult::Pad(mut padded_symbols) => {
+                passwd.push_str(&padded_symbols);
+                padded_symbols.wipe();
+            }
+        }
+
+        separator.wipe();
+        prefix_symbols.wipe();
+        words.wipe();
+        suffix_symbols.wipe();
+
+        let entropy = settings.calc_entropy(pool_size);
+
+        Ok((passwd.into(), entropy))
+    }
+
+    /// Entropy of passwords generated with the given settings, without generating any.
+    pub fn calc_entropy<S: Randomizer>(&self, settings: &S) -> Result<Entropy, String> {
+        let (_, pool_size) = self.filtered_pool(settings)?;
+        Ok(settings.calc_entropy(pool_size))
+    }
 
-        let passwd = match settings.adjust_padding(passwd.len()) {
-            PaddingResult::Unchanged => passwd,
-            PaddingResult::TrimTo(len) => passwd[..len].to_string(),
-            PaddingResult::Pad(padded_symbols) => passwd + &padded_symbols,
+    // words to pick from, along with the number of equally likely words they amount to
+    fn filtered_pool<S: Randomizer>(
+        &self,
+        settings: &S,
+    ) -> Result<(Cow<'_, [&str]>, usize), String> {
+        let pool = self.build_pool(settings.word_lengths())?;
+
+        if pool.is_empty() {
+            return Err("no words available with the requested lengths".to_string());
+        }
+
+        let unfiltered_len = pool.len();
+        let all_words = settings.filter_words(pool)?;
+        let source_size = self.source.pool_size(settings.word_lengths());
+
+        let pool_size = if all_words.len() == unfiltered_len
+            && unfiltered_len == source_size
+            && self.safe_pool.is_none()
+        {
+            source_size
+        } else {
+            effective_pool_size(&all_words)
         };
+        let pool_size = settings.usable_pool_size(&all_words, pool_size);
+
+        Ok((all_words, pool_size))
+    }
+
+    pub(crate) fn build_pool(&self, lengths: Range<u8>) -> Result<Cow<'_, [&str]>, String> {
+        let words = match &self.safe_pool {
+            Some(safe_pool) => safe_pool.words_in(lengths),
+            None => self.source.words_in(lengths),
+        };
+
+        if self.uniform_weights {
+            return Ok(words);
+        }
+
+        if let Some(word) = words.iter().find(|word| self.source.weight(word) == 0) {
+            return Err(format!("word '{}' has a weight of 0", word));
+        }
+
+        // weighted words are repeated so that they are picked more often
+        Ok(words
+            .iter()
+            .flat_map(|&word| core::iter::repeat_n(word, self.source.weight(word)))
+            .collect())
+    }
+}
 
-        let entropy = settings.calc_entropy(all_words.len());
+// min-entropy of picking from a pool with repeated words,
+// i.e. as if all words were as likely as the most repeated one
+fn effective_pool_size(pool: &[&str]) -> usize {
+    let mut counts: Map<&str, usize> = Map::new();
+    for &word in pool {
+        *counts.entry(word).or_default() += 1;
+    }
+
+    let max_count = counts.values().copied().max().unwrap_or(1);
+    pool.len() / max_count
+}
 
-        (passwd, entropy)
+#[cfg(feature = "lang_en")]
+static DICT_EN: DictIndex<'static> = include!(concat!(env!("OUT_DIR"), "/dict_en.rs"));
+#[cfg(feature = "lang_de")]
+static DICT_DE: DictIndex<'static> = include!(concat!(env!("OUT_DIR"), "/dict_de.rs"));
+#[cfg(feature = "lang_es")]
+static DICT_ES: DictIndex<'static> = include!(concat!(env!("OUT_DIR"), "/dict_es.rs"));
+#[cfg(feature = "lang_fr")]
+static DICT_FR: DictIndex<'static> = include!(concat!(env!("OUT_DIR"), "/dict_fr.rs"));
+#[cfg(feature = "lang_pt")]
+static DICT_PT: DictIndex<'static> = include!(concat!(env!("OUT_DIR"), "/dict_pt.rs"));
+
+fn bundled_dict(language: Language) -> &'static DictIndex<'static> {
+    match language {
+        #[cfg(feature = "lang_en")]
+        Language::English => &DICT_EN,
+        #[cfg(feature = "lang_de")]
+        Language::German => &DICT_DE,
+        #[cfg(feature = "lang_es")]
+        Language::Spanish => &DICT_ES,
+        #[cfg(feature = "lang_fr")]
+        Language::French => &DICT_FR,
+        #[cfg(feature = "lang_pt")]
+        Language::Portuguese => &DICT_PT,
+        #[allow(unreachable_patterns)]
+        _ => panic!("no language bundled"),
     }
 }
 
+/// Lengths having words in any bundled dictionary, in ascending order,
+/// the default word length bounds of [`Settings`](crate::settings::Settings).
+pub fn bundled_word_lengths() -> Vec<u8> {
+    let lengths: BTreeSet<u8> = Language::ALL
+        .into_iter()
+        .filter(Language::is_bundled)
+        .flat_map(|language| bundled_dict(language).word_lengths())
+        .collect();
+    lengths.into_iter().collect()
+}
+
+// deny lists of the given languages, or of all bundled languages if unknown
+fn load_denied_words(languages: &[Language]) -> Set<&'static str> {
+    let languages = if languages.is_empty() {
+        &Language::ALL[..]
+    } else {
+        languages
+    };
+
+    languages
+        .iter()
+        .copied()
+        .filter_map(deny_list)
+        .flat_map(parse_deny_list)
+        .collect()
+}
+
+fn deny_list(language: Language) -> Option<&'static str> {
+    match language {
+        #[cfg(feature = "lang_en")]
+        Language::English => Some(include_str!("../assets/deny_en.txt")),
+        #[cfg(feature = "lang_de")]
+        Language::German => Some(include_str!("../assets/deny_de.txt")),
+        #[cfg(feature = "lang_es")]
+        Language::Spanish => Some(include_str!("../assets/deny_es.txt")),
+        #[cfg(feature = "lang_fr")]
+        Language::French => Some(include_str!("../assets/deny_fr.txt")),
+        #[cfg(feature = "lang_pt")]
+        Language::Portuguese => Some(include_str!("../assets/deny_pt.txt")),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+fn parse_deny_list(list: &str) -> impl Iterator<Item = &str> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn merge_dicts<'a>(dicts: &[&DictIndex<'a>]) -> DictIndex<'a> {
+    let max_len = dicts
+        .iter()
+        .flat_map(|dict| dict.word_lengths().last().copied())
+        .max()
+        .unwrap_or(0);
+
+    let mut seen_words: Set<&str> = Set::new();
+    let groups: Vec<(u8, Vec<&'a str>)> = (0..=max_len)
+        .map(|len| {
+            let words = dicts
+                .iter()
+                .flat_map(|dict| dict.words_in_range(len..len.saturating_add(1)))
+                .copied()
+                .filter(|word| seen_words.insert(word))
+                .collect();
+            (len, words)
+        })
+        .collect();
+
+    let merged = DictIndex::from_groups(groups);
+    log::debug!(
+        "merged dicts with {:?} distinct entries",
+        merged.words.len()
+    );
+
+    merged
+}
+
 fn load_dict(dict_bytes: &[u8]) -> Dict<'_> {
     let dict_str = from_utf8(dict_bytes).unwrap_or("").trim();
-    let mut dict: Dict = HashMap::new();
+    let mut dict: Dict = Map::new();
 
     log::debug!("loaded raw dict with {} lines", dict_str.lines().count());
 
@@ -300,7 +1009,7 @@ fn load_dict(dict_bytes: &[u8]) -> Dict<'_> {
                 }
             };
             let words_csv = comps.next().unwrap_or("");
-            let words: Vec<&str> = words_csv.split(',').collect();
+            let words: Vec<&str> = words_csv.split(',').filter(|w| !w.is_empty()).collect();
             dict.insert(len, words);
         }
     });
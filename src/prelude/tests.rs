@@ -1,4 +1,5 @@
 use super::*;
+use std::collections::HashSet;
 
 struct MockSettings {
     padding_digits: (usize, usize),
@@ -8,30 +9,34 @@ struct MockSettings {
 
 impl Randomizer for MockSettings {
     fn word_lengths(&self) -> Range<u8> {
-        3..4
+        4..5
     }
 
-    fn rand_words(&self, _: &[&str]) -> Vec<String> {
+    fn filter_words<'a>(&self, pool: Cow<'a, [&'a str]>) -> Result<Cow<'a, [&'a str]>, String> {
+        Ok(pool)
+    }
+
+    fn rand_words(&self, _: &mut dyn RngCore, _: &[&str]) -> Vec<String> {
         vec!["foo".to_string(), "bar".to_string(), "baz".to_string()]
     }
 
-    fn rand_separator(&self) -> String {
+    fn rand_separator(&self, _: &mut dyn RngCore) -> String {
         ".".to_string()
     }
 
-    fn rand_prefix(&self) -> (String, String) {
+    fn rand_prefix(&self, _: &mut dyn RngCore) -> (String, String) {
         let prefix_symbols = &"?????"[..self.padding_symbols.0];
         let prefix_digits = &"12345"[..self.padding_digits.0];
         (prefix_symbols.to_string(), prefix_digits.to_string())
     }
 
-    fn rand_suffix(&self) -> (String, String) {
+    fn rand_suffix(&self, _: &mut dyn RngCore) -> (String, String) {
         let suffix_symbols = &"!!!!!!"[..self.padding_symbols.1];
         let suffix_digits = &"67890"[..self.padding_digits.1];
         (suffix_digits.to_string(), suffix_symbols.to_string())
     }
 
-    fn adjust_padding(&self, _: usize) -> PaddingResult {
+    fn adjust_padding(&self, _: &mut dyn RngCore, _: usize) -> PaddingResult {
         match &self.padding_result {
             PaddingResult::Unchanged => PaddingResult::Unchanged,
             PaddingResult::TrimTo(len) => PaddingResult::TrimTo(*len),
@@ -55,6 +60,7 @@ fn test_load_dict_blank() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_get_then_check)]
 fn test_load_dict_valid_data() {
     let table = [
         "2:an,do\n3:foo,bar",
@@ -83,104 +89,514 @@ fn test_load_dict_invalid_data() {
     assert!(dict.is_empty());
 }
 
+struct WeightedSource {
+    words: Vec<&'static str>,
+    foo_weight: usize,
+}
+
+impl WordSource for WeightedSource {
+    fn words_of_length(&self, length: u8) -> Vec<&str> {
+        self.words.words_of_length(length)
+    }
+
+    fn weight(&self, word: &str) -> usize {
+        if word == "foo" {
+            self.foo_weight
+        } else {
+            1
+        }
+    }
+}
+
+#[test]
+fn test_word_source_for_dict() {
+    let dict = load_dict("2:an,do\n3:foo,bar,baz\n5:fooza".as_bytes());
+
+    assert_eq!(vec!["an", "do"], dict.words_of_length(2));
+    assert!(dict.words_of_length(4).is_empty());
+    assert_eq!(vec!["an", "do", "foo", "bar", "baz"], *dict.words_in(2..4));
+    assert_eq!(5, dict.pool_size(2..4));
+    assert_eq!(4, dict.pool_size(3..6));
+    assert_eq!(0, dict.pool_size(6..10));
+    assert_eq!(1, dict.weight("foo"));
+}
+
+#[test]
+fn test_word_source_word_lengths() {
+    let dict = load_dict("2:an,do\n5:fooza\n3:foo,bar\n4:".as_bytes());
+    assert_eq!(vec![2, 3, 5], dict.word_lengths());
+
+    let words = vec!["an", "fooza", "foo", "café", ""];
+    assert_eq!(vec![2, 3, 4, 5], words.word_lengths());
+
+    let pass = Xkpasswd::new(WeightedSource {
+        words,
+        foo_weight: 3,
+    });
+    assert_eq!(vec![2, 3, 4, 5], pass.source().word_lengths());
+}
+
+#[test]
+fn test_word_source_for_vec() {
+    let words = vec!["an", "foo", "do", "bar", "café"];
+
+    assert_eq!(vec!["an", "do"], words.words_of_length(2));
+    assert_eq!(vec!["café"], words.words_of_length(4));
+    assert_eq!(vec!["an", "do", "foo", "bar"], *words.words_in(2..4));
+    assert_eq!(5, words.pool_size(2..5));
+}
+
+#[test]
+fn test_xkpasswd_build_pool() {
+    let pass = Xkpasswd::new(vec!["foo", "bar", "an"]);
+    assert_eq!(vec!["foo", "bar"], *pass.build_pool(3..4).unwrap());
+
+    let pass = Xkpasswd::new(WeightedSource {
+        words: vec!["foo", "bar", "an"],
+        foo_weight: 3,
+    });
+    assert_eq!(
+        vec!["an", "foo", "foo", "foo", "bar"],
+        *pass.build_pool(2..4).unwrap()
+    );
+    assert_eq!(3, pass.source().pool_size(2..4));
+
+    let pass = Xkpasswd::new(WeightedSource {
+        words: vec!["foo", "bar", "an"],
+        foo_weight: 0,
+    });
+    assert_eq!(
+        Err("word 'foo' has a weight of 0".to_string()),
+        pass.build_pool(2..4)
+    );
+
+    // weights are only checked once for sources of uniform weights
+    assert!(!pass.uniform_weights);
+    let pass = Xkpasswd::new(WeightedSource {
+        words: vec!["foo", "bar", "an"],
+        foo_weight: 1,
+    });
+    assert!(pass.uniform_weights);
+}
+
+#[test]
+fn test_xkpasswd_weighted_entropy() {
+    use crate::settings::Settings;
+
+    let settings = Settings::default()
+        .with_words_count(2)
+        .unwrap()
+        .with_available_word_lengths(&[3])
+        .unwrap();
+    let words = vec!["foo", "bar", "baz", "qux", "fox", "fix"];
+
+    let pass = Xkpasswd::new(WeightedSource {
+        words: words.clone(),
+        foo_weight: 1,
+    });
+    assert_eq!(Ok(settings.calc_entropy(6)), pass.calc_entropy(&settings));
+
+    // "foo" is picked 3 times out of 8, as likely as picking from 8 / 3 words
+    let pass = Xkpasswd::new(WeightedSource {
+        words,
+        foo_weight: 3,
+    });
+    assert_eq!(Ok(settings.calc_entropy(2)), pass.calc_entropy(&settings));
+
+    let settings = settings.with_excluded_words(&["bar"]);
+    assert_eq!(Ok(settings.calc_entropy(2)), pass.calc_entropy(&settings));
+}
+
+#[test]
+fn test_gen_pass_multibyte_symbols() {
+    use crate::settings::Settings;
+
+    let pass = Xkpasswd::new(vec!["fooz", "barz", "bazz", "quxx"]);
+    let settings = Settings::default()
+        .with_available_word_lengths(&[4])
+        .unwrap()
+        .with_word_lengths(Some(4), Some(4))
+        .unwrap()
+        .with_separators("€")
+        .with_padding_symbols("£¥");
+
+    // adaptive lengths are in characters, both when padding & trimming
+    for length in [8, 30] {
+        let settings = settings
+            .with_padding_strategy(PaddingStrategy::Adaptive(length))
+            .unwrap();
+        for _ in 0..20 {
+            let (passwd, _) = pass.gen_pass(&settings).unwrap();
+            assert_eq!(length, passwd.chars().count());
+        }
+    }
+}
+
+#[test]
+fn test_preset_names() {
+    for preset in Preset::ALL {
+        assert_eq!(Some(preset), Preset::from_name(preset.name()));
+    }
+
+    assert_eq!(Some(Preset::AppleID), Preset::from_name("Apple-ID"));
+    assert_eq!(None, Preset::from_name("apple"));
+}
+
+#[test]
+fn test_keyboards() {
+    for keyboard in Keyboard::ALL {
+        assert_eq!(Some(keyboard), Keyboard::from_name(keyboard.name()));
+        assert!(keyboard.can_type(' '));
+        assert!(keyboard.can_type('7'));
+        assert!(!keyboard.can_type('€'));
+    }
+
+    assert_eq!(Some(Keyboard::PtBr), Keyboard::from_name("PT-BR"));
+    assert_eq!(None, Keyboard::from_name("azerty"));
+
+    for language in Language::ALL {
+        let keyboard = Keyboard::for_language(language);
+        assert_eq!(language == Language::English, keyboard.can_type('^'));
+    }
+
+    assert_eq!(32, Keyboard::Us.symbols().len());
+    assert!(Keyboard::De.can_type('~'));
+    assert!(!Keyboard::Fr.can_type('~'));
+    assert!(!Keyboard::Ios.can_type('*'));
+    assert!(Keyboard::Android.can_type('*'));
+}
+
+#[test]
+fn test_dict_index() {
+    let index = DictIndex::from_bytes("3:foo,bar\n5:fooza\n2:an".as_bytes());
+
+    assert_eq!(vec![2, 3, 5], index.word_lengths());
+    assert_eq!(["an", "foo", "bar"], index.words_in_range(2..4));
+    assert_eq!(["foo", "bar", "fooza"], index.words_in_range(3..6));
+    assert_eq!(["fooza"], index.words_in_range(4..u8::MAX));
+    assert!(index.words_in_range(4..5).is_empty());
+    assert!(index.words_in_range(6..10).is_empty());
+    assert_eq!(vec!["foo", "bar"], index.words_of_length(3));
+    assert_eq!(3, index.pool_size(2..4));
+    assert!(matches!(index.words_in(2..4), Cow::Borrowed(_)));
+
+    let index = DictIndex::from_bytes(&[]);
+    assert!(index.word_lengths().is_empty());
+    assert!(index.words_in_range(0..u8::MAX).is_empty());
+}
+
+#[test]
+fn test_owned_dict_index() {
+    let index = OwnedDictIndex::from_bytes(b" 3:foo,bar\n5:fooza\n2:an\n".to_vec()).unwrap();
+
+    assert_eq!(vec![2, 3, 5], index.word_lengths());
+    assert_eq!(vec!["foo", "bar"], index.words_of_length(3));
+    assert_eq!(["an", "foo", "bar"], *index.words_in(2..4));
+    assert_eq!(["fooza"], *index.words_in(4..u8::MAX));
+    assert!(index.words_in(6..10).is_empty());
+    assert_eq!(3, index.pool_size(2..4));
+
+    let pass = Xkpasswd::new(index);
+    assert_eq!(vec!["foo", "bar"], *pass.build_pool(3..4).unwrap());
+
+    assert_eq!(
+        Err("no words available in dictionary".to_string()),
+        OwnedDictIndex::from_bytes(b"3:\nfoo".to_vec())
+    );
+    assert!(OwnedDictIndex::from_bytes(vec![0xff, 0xfe])
+        .unwrap_err()
+        .starts_with("invalid dictionary"));
+}
+
+#[test]
+fn test_merge_dicts() {
+    let dict_en = DictIndex::from_bytes("3:foo,bar\n4:fooz".as_bytes());
+    let dict_de = DictIndex::from_bytes("3:bar,baz\n5:fooza".as_bytes());
+
+    let merged = merge_dicts(&[&dict_en, &dict_de]);
+    assert_eq!(vec![3, 4, 5], merged.word_lengths());
+    assert_eq!(vec!["foo", "bar", "baz"], merged.words_of_length(3));
+    assert_eq!(vec!["fooz"], merged.words_of_length(4));
+    assert_eq!(vec!["fooza"], merged.words_of_length(5));
+    assert_eq!(5, merged.pool_size(3..6));
+}
+
+#[cfg(feature = "all_langs")]
+#[test]
+fn test_bundled_dicts_match_assets() {
+    let table: [(Language, &[u8]); 5] = [
+        (Language::English, include_bytes!("../assets/dict_en.txt")),
+        (Language::German, include_bytes!("../assets/dict_de.txt")),
+        (Language::Spanish, include_bytes!("../assets/dict_es.txt")),
+        (Language::French, include_bytes!("../assets/dict_fr.txt")),
+        (
+            Language::Portuguese,
+            include_bytes!("../assets/dict_pt.txt"),
+        ),
+    ];
+
+    for (language, dict_bytes) in table {
+        let index = bundled_dict(language);
+        let dict = load_dict(dict_bytes);
+
+        assert_eq!(dict.word_lengths(), index.word_lengths());
+        for len in dict.word_lengths() {
+            assert_eq!(dict.words_of_length(len), index.words_of_length(len));
+        }
+    }
+}
+
+#[cfg(all(feature = "lang_en", feature = "lang_de"))]
+#[test]
+fn test_xkpasswd_for_languages() {
+    let english = Xkpasswd::for_language(Language::English);
+    let german = Xkpasswd::for_language(Language::German);
+
+    let pass = Xkpasswd::for_languages(&[Language::English]);
+    assert_eq!(english.source, pass.source);
+
+    // duplicated languages are loaded once
+    let pass = Xkpasswd::for_languages(&[Language::German, Language::German]);
+    assert_eq!(german.source, pass.source);
+
+    let pass = Xkpasswd::for_languages(&[Language::English, Language::German]);
+    let pool_size = pass.source.pool_size(4..11);
+    assert!(pool_size > english.source.pool_size(4..11));
+    assert!(pool_size < english.source.pool_size(4..11) + german.source.pool_size(4..11));
+
+    let words = pass.source.words_in(4..11);
+    let distinct_words: HashSet<&str> = words.iter().copied().collect();
+    assert_eq!(words.len(), distinct_words.len());
+}
+
+#[test]
+fn test_bundled_dicts_have_no_denied_words() {
+    let table: [(&[u8], &str); 5] = [
+        (
+            include_bytes!("../assets/dict_en.txt"),
+            include_str!("../assets/deny_en.txt"),
+        ),
+        (
+            include_bytes!("../assets/dict_de.txt"),
+            include_str!("../assets/deny_de.txt"),
+        ),
+        (
+            include_bytes!("../assets/dict_es.txt"),
+            include_str!("../assets/deny_es.txt"),
+        ),
+        (
+            include_bytes!("../assets/dict_fr.txt"),
+            include_str!("../assets/deny_fr.txt"),
+        ),
+        (
+            include_bytes!("../assets/dict_pt.txt"),
+            include_str!("../assets/deny_pt.txt"),
+        ),
+    ];
+
+    for (dict_bytes, deny_list) in table {
+        let dict = load_dict(dict_bytes);
+        let words = dict.words_in(0..u8::MAX);
+        let denied: Vec<&str> = parse_deny_list(deny_list).collect();
+        assert!(!denied.is_empty());
+
+        for word in denied {
+            assert!(!words.contains(&word), "'{}' found in dictionary", word);
+        }
+    }
+
+    // short vulgar words, which came along with 3-letter words
+    let deny_list = parse_deny_list(include_str!("../assets/deny_en.txt")).collect::<Vec<_>>();
+    for word in ["anal", "anus", "ass", "cum", "tit"] {
+        assert!(deny_list.contains(&word), "'{}' not denied", word);
+    }
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_xkpasswd_with_safe_words() {
+    let words = vec!["fooz", "fuck", "Shit", "barz"];
+
+    let pass = Xkpasswd::new(words.clone());
+    assert_eq!(words, *pass.build_pool(4..5).unwrap());
+
+    let pass = Xkpasswd::new(words).with_safe_words(true);
+    assert_eq!(vec!["fooz", "barz"], *pass.build_pool(4..5).unwrap());
+
+    // bundled dictionaries are already safe, so that their words are borrowed as is
+    let pass = Xkpasswd::for_language(Language::English).with_safe_words(true);
+    assert!(pass.safe_pool.is_none());
+    assert!(matches!(pass.build_pool(4..11).unwrap(), Cow::Borrowed(_)));
+}
+
+#[cfg(all(feature = "lang_en", feature = "lang_es"))]
+#[test]
+fn test_xkpasswd_with_safe_words_for_languages() {
+    let words = vec!["gato", "negro", "perro", "Shit"];
+
+    // deny lists of all languages for sources of unknown languages
+    let pass = Xkpasswd::new(words.clone()).with_safe_words(true);
+    assert_eq!(vec!["gato", "perro"], *pass.build_pool(4..6).unwrap());
+
+    // "negro" is only denied in English
+    let pass = Xkpasswd::new(words.clone())
+        .with_safe_words(true)
+        .with_languages(&[Language::Spanish]);
+    assert_eq!(
+        vec!["gato", "Shit", "negro", "perro"],
+        *pass.build_pool(4..6).unwrap()
+    );
+
+    let pass = Xkpasswd::new(words)
+        .with_languages(&[Language::Spanish, Language::English])
+        .with_safe_words(true);
+    assert_eq!(vec!["gato", "perro"], *pass.build_pool(4..6).unwrap());
+
+    let pass = Xkpasswd::for_languages(&[Language::Spanish, Language::English]);
+    assert_eq!(&[Language::Spanish, Language::English], pass.languages());
+}
+
 #[cfg(feature = "lang_en")]
 #[test]
 fn test_xkpasswd_for_en() {
     let pass = Xkpasswd::for_language(Language::English);
-    assert!(!pass.dict.is_empty());
+    assert!(!pass.source.word_lengths().is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.source.words_of_length(2).is_empty());
+    assert_eq!(717, pass.source.words_of_length(3).len());
 
-    assert_eq!(1500, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1338, pass.dict.get(&9).unwrap().len());
-    assert_eq!(807, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1500, pass.source.words_of_length(4).len());
+    assert_eq!(1500, pass.source.words_of_length(5).len());
+    assert_eq!(1500, pass.source.words_of_length(6).len());
+    assert_eq!(1500, pass.source.words_of_length(7).len());
+    assert_eq!(1500, pass.source.words_of_length(8).len());
+    assert_eq!(1254, pass.source.words_of_length(9).len());
+    assert_eq!(747, pass.source.words_of_length(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert_eq!(407, pass.source.words_of_length(11).len());
+    assert_eq!(188, pass.source.words_of_length(12).len());
+
+    assert!(pass.source.words_of_length(13).is_empty());
+}
+
+#[cfg(feature = "lang_en")]
+#[test]
+fn test_xkpasswd_readable_entropy() {
+    use crate::bit_flags::{FieldSize, WordTransform};
+    use crate::settings::Settings;
+
+    let pass = Xkpasswd::for_language(Language::English);
+    let settings = Settings::default().with_readable(true);
+    let lowercase = settings
+        .with_word_transforms(WordTransform::Lowercase as FieldSize)
+        .unwrap();
+
+    // words with an `i` are still picked in lowercase with the default transforms
+    let all_words = pass.build_pool(settings.word_lengths()).unwrap();
+    let readable_words = lowercase.filter_words(all_words).unwrap();
+    assert!(readable_words.iter().any(|word| word.contains('i')));
+
+    let (_, pool_size) = pass.filtered_pool(&settings).unwrap();
+    let (_, lowercase_pool_size) = pass.filtered_pool(&lowercase).unwrap();
+    assert_eq!(readable_words.len(), lowercase_pool_size);
+    assert!(pool_size > lowercase_pool_size / 2);
+    assert!(pool_size < lowercase_pool_size);
+
+    let entropy = pass.calc_entropy(&settings).unwrap();
+    let lowercase_entropy = pass.calc_entropy(&lowercase).unwrap();
+    assert!(entropy.seen > lowercase_entropy.seen);
 }
 
 #[cfg(feature = "lang_de")]
 #[test]
 fn test_xkpasswd_for_de() {
     let pass = Xkpasswd::for_language(Language::German);
-    assert!(!pass.dict.is_empty());
+    assert!(!pass.source.word_lengths().is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.source.words_of_length(2).is_empty());
+    assert_eq!(485, pass.source.words_of_length(3).len());
 
-    assert_eq!(1277, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&9).unwrap().len());
-    assert_eq!(1185, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1242, pass.source.words_of_length(4).len());
+    assert_eq!(1500, pass.source.words_of_length(5).len());
+    assert_eq!(1500, pass.source.words_of_length(6).len());
+    assert_eq!(1500, pass.source.words_of_length(7).len());
+    assert_eq!(1500, pass.source.words_of_length(8).len());
+    assert_eq!(1500, pass.source.words_of_length(9).len());
+    assert_eq!(1142, pass.source.words_of_length(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert_eq!(833, pass.source.words_of_length(11).len());
+    assert_eq!(507, pass.source.words_of_length(12).len());
+
+    assert!(pass.source.words_of_length(13).is_empty());
 }
 
 #[cfg(feature = "lang_es")]
 #[test]
 fn test_xkpasswd_for_es() {
     let pass = Xkpasswd::for_language(Language::Spanish);
-    assert!(!pass.dict.is_empty());
+    assert!(!pass.source.word_lengths().is_empty());
+
+    assert!(pass.source.words_of_length(2).is_empty());
+    assert_eq!(394, pass.source.words_of_length(3).len());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert_eq!(1086, pass.source.words_of_length(4).len());
+    assert_eq!(1500, pass.source.words_of_length(5).len());
+    assert_eq!(1500, pass.source.words_of_length(6).len());
+    assert_eq!(1500, pass.source.words_of_length(7).len());
+    assert_eq!(1500, pass.source.words_of_length(8).len());
+    assert_eq!(1500, pass.source.words_of_length(9).len());
+    assert_eq!(1088, pass.source.words_of_length(10).len());
 
-    assert_eq!(1111, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&9).unwrap().len());
-    assert_eq!(1129, pass.dict.get(&10).unwrap().len());
+    assert_eq!(557, pass.source.words_of_length(11).len());
+    assert_eq!(249, pass.source.words_of_length(12).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert!(pass.source.words_of_length(13).is_empty());
 }
 
 #[cfg(feature = "lang_fr")]
 #[test]
 fn test_xkpasswd_for_fr() {
     let pass = Xkpasswd::for_language(Language::French);
-    assert!(!pass.dict.is_empty());
+    assert!(!pass.source.word_lengths().is_empty());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert!(pass.source.words_of_length(2).is_empty());
+    assert_eq!(494, pass.source.words_of_length(3).len());
 
-    assert_eq!(1212, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1438, pass.dict.get(&9).unwrap().len());
-    assert_eq!(902, pass.dict.get(&10).unwrap().len());
+    assert_eq!(1170, pass.source.words_of_length(4).len());
+    assert_eq!(1500, pass.source.words_of_length(5).len());
+    assert_eq!(1500, pass.source.words_of_length(6).len());
+    assert_eq!(1500, pass.source.words_of_length(7).len());
+    assert_eq!(1500, pass.source.words_of_length(8).len());
+    assert_eq!(1364, pass.source.words_of_length(9).len());
+    assert_eq!(857, pass.source.words_of_length(10).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert_eq!(470, pass.source.words_of_length(11).len());
+    assert_eq!(246, pass.source.words_of_length(12).len());
+
+    assert!(pass.source.words_of_length(13).is_empty());
 }
 
 #[cfg(feature = "lang_pt")]
 #[test]
 fn test_xkpasswd_for_pt() {
     let pass = Xkpasswd::for_language(Language::Portuguese);
-    assert!(!pass.dict.is_empty());
+    assert!(!pass.source.word_lengths().is_empty());
+
+    assert!(pass.source.words_of_length(2).is_empty());
+    assert_eq!(411, pass.source.words_of_length(3).len());
 
-    assert!(pass.dict.get(&2).is_none());
-    assert!(pass.dict.get(&3).is_none());
+    assert_eq!(1099, pass.source.words_of_length(4).len());
+    assert_eq!(1500, pass.source.words_of_length(5).len());
+    assert_eq!(1500, pass.source.words_of_length(6).len());
+    assert_eq!(1500, pass.source.words_of_length(7).len());
+    assert_eq!(1500, pass.source.words_of_length(8).len());
+    assert_eq!(1346, pass.source.words_of_length(9).len());
+    assert_eq!(881, pass.source.words_of_length(10).len());
 
-    assert_eq!(1130, pass.dict.get(&4).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&5).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&6).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&7).unwrap().len());
-    assert_eq!(1500, pass.dict.get(&8).unwrap().len());
-    assert_eq!(1397, pass.dict.get(&9).unwrap().len());
-    assert_eq!(925, pass.dict.get(&10).unwrap().len());
+    assert_eq!(491, pass.source.words_of_length(11).len());
+    assert_eq!(253, pass.source.words_of_length(12).len());
 
-    assert!(pass.dict.get(&11).is_none());
+    assert!(pass.source.words_of_length(13).is_empty());
 }
 
 #[test]
@@ -254,11 +670,50 @@ fn test_xkpasswd_gen_pass() {
     ];
 
     for (expected, settings) in table {
-        let (passwd, _) = pass.gen_pass(&settings);
-        assert_eq!(expected, passwd);
+        let (passwd, _) = pass.gen_pass(&settings).unwrap();
+        assert_eq!(expected, passwd.as_str());
     }
 }
 
+#[test]
+fn test_xkpasswd_gen_pass_filtered() {
+    use crate::settings::Settings;
+
+    let pass = Xkpasswd::new(vec!["fooz", "barz", "bazz", "quxx", "other"]);
+    let settings = Settings::default()
+        .with_words_count(2)
+        .unwrap()
+        .with_word_lengths(Some(4), Some(4))
+        .unwrap();
+
+    let (_, entropy) = pass.gen_pass(&settings).unwrap();
+    assert_eq!(settings.calc_entropy(4), entropy);
+    assert_eq!(Ok(entropy), pass.calc_entropy(&settings));
+
+    let settings = settings.with_excluded_words(&["barz"]);
+    let (passwd, entropy) = pass.gen_pass(&settings).unwrap();
+    assert!(!passwd.to_lowercase().contains("barz"));
+    assert_eq!(settings.calc_entropy(3), entropy);
+
+    let other_settings = settings.with_available_word_lengths(&[6]).unwrap();
+    let result = pass.gen_pass(&other_settings);
+    assert_eq!(
+        Err("no words available with the requested lengths".to_string()),
+        result
+    );
+
+    let settings = settings.with_included_words(&["barz", "bazz", "other"]);
+    let result = pass.gen_pass(&settings);
+    assert_eq!(
+        Err("only 1 word(s) left after filtering, 2 required".to_string()),
+        result
+    );
+    assert_eq!(
+        Err("only 1 word(s) left after filtering, 2 required".to_string()),
+        pass.calc_entropy(&settings)
+    );
+}
+
 #[test]
 fn test_guess_time_display() {
     // Test "more than a billion years"
@@ -0,0 +1,421 @@
+//! Python bindings, built into the `xkpasswd._xkpasswd` extension module by maturin.
+//!
+//! Settings are immutable: builder methods are keyword arguments of `Settings()`
+//! & `Settings.replace()`, named after `Builder` methods without the `with_` prefix,
+//! and applied in the same order as CLI options whatever the order they are given in.
+//! Invalid settings & failed generations raise `XkpasswdError`, a `ValueError`.
+
+use crate::bit_flags::{FieldSize, WordTransform};
+use crate::prelude::*;
+use crate::settings::Settings;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+create_exception!(
+    xkpasswd,
+    XkpasswdError,
+    PyValueError,
+    "Invalid settings or failed password generation."
+);
+
+#[pyclass(
+    name = "Preset",
+    module = "xkpasswd",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyPreset {
+    #[pyo3(name = "DEFAULT")]
+    Default,
+    #[pyo3(name = "APPLE_ID")]
+    AppleId,
+    #[pyo3(name = "WINDOWS_NTLM_V1")]
+    WindowsNtlmV1,
+    #[pyo3(name = "SECURITY_QUESTIONS")]
+    SecurityQuestions,
+    #[pyo3(name = "WEB16")]
+    Web16,
+    #[pyo3(name = "WEB32")]
+    Web32,
+    #[pyo3(name = "WIFI")]
+    Wifi,
+    #[pyo3(name = "XKCD")]
+    Xkcd,
+}
+
+#[pyclass(
+    name = "Language",
+    module = "xkpasswd",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyLanguage {
+    #[pyo3(name = "ENGLISH")]
+    English,
+    #[pyo3(name = "FRENCH")]
+    French,
+    #[pyo3(name = "GERMAN")]
+    German,
+    #[pyo3(name = "PORTUGUESE")]
+    Portuguese,
+    #[pyo3(name = "SPANISH")]
+    Spanish,
+}
+
+/// Flags combined with `|` into `int` values, as `word_transforms` of settings.
+#[pyclass(
+    name = "WordTransform",
+    module = "xkpasswd",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyWordTransform {
+    #[pyo3(name = "LOWERCASE")]
+    Lowercase = 0b00000001,
+    #[pyo3(name = "TITLECASE")]
+    Titlecase = 0b00000010,
+    #[pyo3(name = "UPPERCASE")]
+    Uppercase = 0b00000100,
+    #[pyo3(name = "INVERSED_TITLECASE")]
+    InversedTitlecase = 0b00001000,
+    #[pyo3(name = "ALTERCASE_LOWER_FIRST")]
+    AltercaseLowerFirst = 0b01000000,
+    #[pyo3(name = "ALTERCASE_UPPER_FIRST")]
+    AltercaseUpperFirst = 0b10000000,
+}
+
+#[pymethods]
+impl PyWordTransform {
+    fn __index__(&self) -> FieldSize {
+        WordTransform::from(*self) as FieldSize
+    }
+
+    fn __or__(&self, other: FieldSize) -> FieldSize {
+        WordTransform::from(*self) as FieldSize | other
+    }
+
+    fn __ror__(&self, other: FieldSize) -> FieldSize {
+        self.__or__(other)
+    }
+}
+
+#[pyclass(
+    name = "Entropy",
+    module = "xkpasswd",
+    eq,
+    frozen,
+    get_all,
+    skip_from_py_object
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PyEntropy {
+    blind_min: usize,
+    blind_max: usize,
+    seen: usize,
+    /// `(years, months, days)` for computers to break passwords.
+    guess_time: (usize, u8, u8),
+    description: String,
+}
+
+#[pymethods]
+impl PyEntropy {
+    fn __str__(&self) -> &str {
+        &self.description
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Entropy(blind_min={}, blind_max={}, seen={})",
+            self.blind_min, self.blind_max, self.seen
+        )
+    }
+}
+
+#[pyclass(
+    name = "Settings",
+    module = "xkpasswd",
+    eq,
+    frozen,
+    skip_from_py_object
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PySettings(Settings);
+
+#[pymethods]
+impl PySettings {
+    #[new]
+    #[pyo3(signature = (preset = None, **options))]
+    fn new(preset: Option<PyPreset>, options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let settings = match preset {
+            Some(preset) => Settings::from_preset(preset.into()),
+            None => Settings::default(),
+        };
+
+        apply_options(settings, options).map(PySettings)
+    }
+
+    /// Copy of the settings with the given options updated.
+    #[pyo3(signature = (**options))]
+    fn replace(&self, options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        apply_options(self.0.clone(), options).map(PySettings)
+    }
+
+    #[getter]
+    fn words_count(&self) -> u8 {
+        self.0.words_count()
+    }
+
+    #[getter]
+    fn word_transforms(&self) -> FieldSize {
+        self.0.word_transforms()
+    }
+
+    #[getter]
+    fn separators(&self) -> &str {
+        self.0.separators()
+    }
+
+    #[getter]
+    fn padding_digits(&self) -> (u8, u8) {
+        self.0.padding_digits()
+    }
+
+    #[getter]
+    fn padding_symbols(&self) -> &str {
+        self.0.padding_symbols()
+    }
+
+    #[getter]
+    fn padding_symbol_lengths(&self) -> (u8, u8) {
+        self.0.padding_symbol_lengths()
+    }
+
+    #[getter]
+    fn adaptive_padding(&self) -> Option<usize> {
+        match self.0.padding_strategy() {
+            PaddingStrategy::Fixed => None,
+            PaddingStrategy::Adaptive(length) => Some(length),
+        }
+    }
+
+    #[getter]
+    fn readable(&self) -> bool {
+        self.0.readable()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+#[pyclass(name = "Xkpasswd", module = "xkpasswd", frozen)]
+#[derive(Debug)]
+pub struct PyXkpasswd(Xkpasswd);
+
+#[pymethods]
+impl PyXkpasswd {
+    /// Generator for the given languages, merging their dictionaries,
+    /// or the first bundled language if none given.
+    #[new]
+    #[pyo3(signature = (*languages))]
+    fn new(languages: Vec<PyLanguage>) -> PyResult<Self> {
+        let languages: Vec<Language> = languages.into_iter().map(Language::from).collect();
+
+        if let Some(language) = languages.iter().find(|language| !language.is_bundled()) {
+            return Err(XkpasswdError::new_err(format!(
+                "{:?} dictionary is not bundled",
+                language
+            )));
+        }
+
+        Ok(PyXkpasswd(Xkpasswd::for_languages(&languages)))
+    }
+
+    fn word_lengths(&self) -> Vec<u8> {
+        self.0.source().word_lengths()
+    }
+
+    /// Generated password with its entropy, using default settings if none given.
+    #[pyo3(signature = (settings = None))]
+    fn gen_pass(&self, settings: Option<PyRef<'_, PySettings>>) -> PyResult<(String, PyEntropy)> {
+        let (passwd, entropy) = match settings {
+            Some(settings) => self.0.gen_pass(&settings.0),
+            None => self.0.gen_pass(&Settings::default()),
+        }
+        .map_err(XkpasswdError::new_err)?;
+
+        Ok((passwd.as_str().to_string(), entropy.into()))
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "_xkpasswd")]
+fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyEntropy>()?;
+    m.add_class::<PyLanguage>()?;
+    m.add_class::<PyPreset>()?;
+    m.add_class::<PySettings>()?;
+    m.add_class::<PyWordTransform>()?;
+    m.add_class::<PyXkpasswd>()?;
+    m.add("XkpasswdError", m.py().get_type::<XkpasswdError>())?;
+    Ok(())
+}
+
+// e.g. word lengths are fit into available ones, and symbol lengths reset adaptive padding
+const SETTINGS_OPTIONS: [&str; 12] = [
+    "available_word_lengths",
+    "word_lengths",
+    "words_count",
+    "word_transforms",
+    "separators",
+    "padding_digits",
+    "padding_symbols",
+    "padding_symbol_lengths",
+    "adaptive_padding",
+    "excluded_words",
+    "included_words",
+    "readable",
+];
+
+fn apply_options(
+    mut settings: Settings,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Settings> {
+    let Some(options) = options else {
+        return Ok(settings);
+    };
+
+    for key in options.keys() {
+        let key: String = key.extract()?;
+        if !SETTINGS_OPTIONS.contains(&key.as_str()) {
+            return Err(PyTypeError::new_err(format!(
+                "unexpected settings option '{}'",
+                key
+            )));
+        }
+    }
+
+    for key in SETTINGS_OPTIONS {
+        let Some(value) = options.get_item(key)? else {
+            continue;
+        };
+
+        settings = match key {
+            "words_count" => settings.with_words_count(value.extract()?),
+            "word_lengths" => {
+                let (min, max) = value.extract()?;
+                settings.with_word_lengths(min, max)
+            }
+            "available_word_lengths" => {
+                let lengths: Vec<u8> = value.extract()?;
+                settings.with_available_word_lengths(&lengths)
+            }
+            "word_transforms" => settings.with_word_transforms(value.extract()?),
+            "separators" => Ok(settings.with_separators(&value.extract::<String>()?)),
+            "padding_digits" => {
+                let (prefix, suffix) = value.extract()?;
+                Ok(settings.with_padding_digits(prefix, suffix))
+            }
+            "padding_symbols" => Ok(settings.with_padding_symbols(&value.extract::<String>()?)),
+            "padding_symbol_lengths" => {
+                let (prefix, suffix) = value.extract()?;
+                Ok(settings.with_padding_symbol_lengths(prefix, suffix))
+            }
+            "adaptive_padding" => {
+                settings.with_padding_strategy(match value.extract::<Option<usize>>()? {
+                    Some(length) => PaddingStrategy::Adaptive(length),
+                    None => PaddingStrategy::Fixed,
+                })
+            }
+            "excluded_words" => {
+                let words: Vec<String> = value.extract()?;
+                let words: Vec<&str> = words.iter().map(String::as_str).collect();
+                Ok(settings.with_excluded_words(&words))
+            }
+            "included_words" => {
+                let words: Vec<String> = value.extract()?;
+                let words: Vec<&str> = words.iter().map(String::as_str).collect();
+                Ok(settings.with_included_words(&words))
+            }
+            "readable" => Ok(settings.with_readable(value.extract()?)),
+            _ => unreachable!("unhandled settings option '{}'", key),
+        }
+        .map_err(XkpasswdError::new_err)?;
+    }
+
+    Ok(settings)
+}
+
+impl From<PyPreset> for Preset {
+    fn from(preset: PyPreset) -> Self {
+        match preset {
+            PyPreset::Default => Preset::Default,
+            PyPreset::AppleId => Preset::AppleID,
+            PyPreset::WindowsNtlmV1 => Preset::WindowsNtlmV1,
+            PyPreset::SecurityQuestions => Preset::SecurityQuestions,
+            PyPreset::Web16 => Preset::Web16,
+            PyPreset::Web32 => Preset::Web32,
+            PyPreset::Wifi => Preset::Wifi,
+            PyPreset::Xkcd => Preset::Xkcd,
+        }
+    }
+}
+
+impl From<PyLanguage> for Language {
+    fn from(language: PyLanguage) -> Self {
+        match language {
+            PyLanguage::English => Language::English,
+            PyLanguage::French => Language::French,
+            PyLanguage::German => Language::German,
+            PyLanguage::Portuguese => Language::Portuguese,
+            PyLanguage::Spanish => Language::Spanish,
+        }
+    }
+}
+
+impl From<PyWordTransform> for WordTransform {
+    fn from(transform: PyWordTransform) -> Self {
+        match transform {
+            PyWordTransform::Lowercase => WordTransform::Lowercase,
+            PyWordTransform::Titlecase => WordTransform::Titlecase,
+            PyWordTransform::Uppercase => WordTransform::Uppercase,
+            PyWordTransform::InversedTitlecase => WordTransform::InversedTitlecase,
+            PyWordTransform::AltercaseLowerFirst => WordTransform::AltercaseLowerFirst,
+            PyWordTransform::AltercaseUpperFirst => WordTransform::AltercaseUpperFirst,
+        }
+    }
+}
+
+impl From<Entropy> for PyEntropy {
+    fn from(entropy: Entropy) -> Self {
+        let GuessTime {
+            years,
+            months,
+            days,
+        } = entropy.guess_time;
+
+        PyEntropy {
+            blind_min: entropy.blind_min,
+            blind_max: entropy.blind_max,
+            seen: entropy.seen,
+            guess_time: (years, months, days),
+            description: entropy.to_string(),
+        }
+    }
+}
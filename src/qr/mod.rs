@@ -0,0 +1,157 @@
+//! Wi-Fi QR codes, scanned by phone cameras to join a network without typing its key.
+//!
+//! Payloads follow the `WIFI:T:<type>;S:<ssid>;P:<password>;;` format, with `\`, `;`, `,`,
+//! `"` & `:` escaped by a backslash, as some of them are separators of the `wifi` preset.
+
+#[cfg(test)]
+mod tests;
+
+use qrcode::render::{svg, unicode};
+use qrcode::{Color, QrCode};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+const SSID_MAX_LEN: usize = 32;
+const QUIET_ZONE: usize = 4;
+const PNG_MODULE_SIZE: usize = 8;
+const SVG_MIN_SIZE: u32 = 256;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WifiSecurity {
+    /// WPA, WPA2 & WPA3 personal networks.
+    #[default]
+    Wpa,
+    Wep,
+    /// Open networks, without any password.
+    NoPass,
+}
+
+impl WifiSecurity {
+    pub const ALL: [WifiSecurity; 3] = [WifiSecurity::Wpa, WifiSecurity::Wep, WifiSecurity::NoPass];
+
+    /// Name used in the CLI.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WifiSecurity::Wpa => "wpa",
+            WifiSecurity::Wep => "wep",
+            WifiSecurity::NoPass => "nopass",
+        }
+    }
+
+    /// Case-insensitive lookup of [`WifiSecurity::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|security| security.name().eq_ignore_ascii_case(name))
+    }
+
+    fn check_passwd(&self, passwd: &str) -> Result<(), String> {
+        let printable = passwd.bytes().all(|byte| (b' '..=b'~').contains(&byte));
+        let hex = passwd.bytes().all(|byte| byte.is_ascii_hexdigit());
+
+        match self {
+            WifiSecurity::Wpa if !printable || !(8..=63).contains(&passwd.len()) => {
+                Err("WPA passwords must be 8 to 63 printable ASCII characters".to_string())
+            }
+            WifiSecurity::Wep
+                if !(printable && [5, 13].contains(&passwd.len())
+                    || hex && [10, 26].contains(&passwd.len())) =>
+            {
+                Err("WEP keys must be 5 or 13 ASCII characters, or 10 or 26 hex digits".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct WifiQr {
+    code: QrCode,
+}
+
+impl WifiQr {
+    /// QR code joining the given network, the password is ignored for open networks.
+    pub fn new(ssid: &str, security: WifiSecurity, passwd: &str) -> Result<Self, String> {
+        if ssid.is_empty() {
+            return Err("SSID can't be empty".to_string());
+        }
+        if ssid.len() > SSID_MAX_LEN {
+            return Err(format!("SSID is limited to {} bytes", SSID_MAX_LEN));
+        }
+        security.check_passwd(passwd)?;
+
+        let code = QrCode::new(wifi_payload(ssid, security, passwd))
+            .map_err(|err| format!("unable to encode QR code: {}", err))?;
+
+        Ok(WifiQr { code })
+    }
+
+    /// UTF-8 half blocks, 2 rows of modules per line, light modules drawn for dark terminals.
+    pub fn to_unicode(&self) -> String {
+        self.code
+            .render::<unicode::Dense1x2>()
+            .dark_color(unicode::Dense1x2::Light)
+            .light_color(unicode::Dense1x2::Dark)
+            .build()
+    }
+
+    pub fn to_svg(&self) -> String {
+        self.code
+            .render::<svg::Color>()
+            .min_dimensions(SVG_MIN_SIZE, SVG_MIN_SIZE)
+            .build()
+    }
+
+    /// Black & white PNG image, 8 pixels per module.
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let modules = self.code.width();
+        let size = (modules + 2 * QUIET_ZONE) * PNG_MODULE_SIZE;
+        let colors = self.code.to_colors();
+
+        let mut pixels = vec![u8::MAX; size * size];
+        for (index, _) in colors
+            .iter()
+            .enumerate()
+            .filter(|(_, &color)| color == Color::Dark)
+        {
+            let left = (index % modules + QUIET_ZONE) * PNG_MODULE_SIZE;
+            let top = (index / modules + QUIET_ZONE) * PNG_MODULE_SIZE;
+
+            for row in top..top + PNG_MODULE_SIZE {
+                pixels[row * size + left..row * size + left + PNG_MODULE_SIZE].fill(0);
+            }
+        }
+
+        let mut image = vec![];
+        let mut encoder = png::Encoder::new(&mut image, size as u32, size as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|err| format!("unable to encode PNG image: {}", err))?;
+
+        Ok(image)
+    }
+}
+
+/// Text encoded by the QR code.
+pub fn wifi_payload(ssid: &str, security: WifiSecurity, passwd: &str) -> String {
+    match security {
+        WifiSecurity::Wpa => format!("WIFI:T:WPA;S:{};P:{};;", escape(ssid), escape(passwd)),
+        WifiSecurity::Wep => format!("WIFI:T:WEP;S:{};P:{};;", escape(ssid), escape(passwd)),
+        WifiSecurity::NoPass => format!("WIFI:T:nopass;S:{};;", escape(ssid)),
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        if matches!(char, '\\' | ';' | ',' | '"' | ':') {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+    }
+    escaped
+}
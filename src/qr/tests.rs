@@ -0,0 +1,130 @@
+use super::*;
+use crate::prelude::{Builder, Preset, Xkpasswd};
+use crate::settings::Settings;
+
+#[test]
+fn test_wifi_security_names() {
+    for security in WifiSecurity::ALL {
+        assert_eq!(Some(security), WifiSecurity::from_name(security.name()));
+    }
+
+    assert_eq!(Some(WifiSecurity::Wpa), WifiSecurity::from_name("WPA"));
+    assert_eq!(None, WifiSecurity::from_name("wpa2"));
+}
+
+#[test]
+fn test_wifi_payload() {
+    let table = [
+        (
+            "mynetwork",
+            WifiSecurity::Wpa,
+            "mypassword",
+            "WIFI:T:WPA;S:mynetwork;P:mypassword;;",
+        ),
+        (
+            "Café; \"Guests\"",
+            WifiSecurity::Wpa,
+            r"a;b,c:d\e",
+            r#"WIFI:T:WPA;S:Café\; \"Guests\";P:a\;b\,c\:d\\e;;"#,
+        ),
+        (
+            "home",
+            WifiSecurity::Wep,
+            "12345",
+            "WIFI:T:WEP;S:home;P:12345;;",
+        ),
+        (
+            "open",
+            WifiSecurity::NoPass,
+            "ignored",
+            "WIFI:T:nopass;S:open;;",
+        ),
+    ];
+
+    for (ssid, security, passwd, expected) in table {
+        assert_eq!(expected, wifi_payload(ssid, security, passwd));
+    }
+}
+
+#[test]
+fn test_wifi_qr_errors() {
+    let table = [
+        ("", WifiSecurity::Wpa, "password", "SSID can't be empty"),
+        (
+            "a-very-long-network-name-over-32-bytes",
+            WifiSecurity::Wpa,
+            "password",
+            "SSID is limited to 32 bytes",
+        ),
+        (
+            "home",
+            WifiSecurity::Wpa,
+            "short",
+            "WPA passwords must be 8 to 63 printable ASCII characters",
+        ),
+        (
+            "home",
+            WifiSecurity::Wpa,
+            "grün.straße.café",
+            "WPA passwords must be 8 to 63 printable ASCII characters",
+        ),
+        (
+            "home",
+            WifiSecurity::Wep,
+            "123456",
+            "WEP keys must be 5 or 13 ASCII characters, or 10 or 26 hex digits",
+        ),
+    ];
+
+    for (ssid, security, passwd, expected) in table {
+        assert_eq!(
+            Err(expected.to_string()),
+            WifiQr::new(ssid, security, passwd).map(|_| ()),
+            "{}",
+            passwd
+        );
+    }
+
+    assert!(WifiQr::new("home", WifiSecurity::Wep, "0123456789").is_ok());
+    assert!(WifiQr::new("open", WifiSecurity::NoPass, "").is_ok());
+}
+
+#[test]
+fn test_wifi_qr_renders() {
+    let settings = Settings::from_preset(Preset::Wifi);
+    for _ in 0..10 {
+        let (passwd, _) = Xkpasswd::default().gen_pass(&settings).unwrap();
+        assert!(WifiQr::new("Guests", WifiSecurity::Wpa, &passwd).is_ok());
+    }
+
+    // the wifi preset's separators & symbols need escaping
+    let passwd = "6237*JENSEN*DESTROYS*pull*crockett*MADAM*BLAMED*4775;;;;;;;,:::";
+    let qr = WifiQr::new("Guests", WifiSecurity::Wpa, passwd).unwrap();
+    let expected = QrCode::new(wifi_payload("Guests", WifiSecurity::Wpa, passwd)).unwrap();
+    assert_eq!(expected.to_colors(), qr.code.to_colors());
+    let modules = qr.code.width() + 2 * QUIET_ZONE;
+
+    let text = qr.to_unicode();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(modules.div_ceil(2), lines.len());
+    assert!(lines.iter().all(|line| line.chars().count() == modules));
+    // the quiet zone is light, drawn as full blocks
+    assert!(lines[0].chars().all(|char| char == '\u{2588}'));
+
+    let svg = qr.to_svg();
+    assert!(svg.starts_with("<?xml"));
+    assert!(svg.contains("<svg"));
+
+    let png = qr.to_png().unwrap();
+    let decoder = png::Decoder::new(png.as_slice());
+    let reader = decoder.read_info().unwrap();
+    let info = reader.info();
+    assert_eq!(
+        (
+            (modules * PNG_MODULE_SIZE) as u32,
+            (modules * PNG_MODULE_SIZE) as u32
+        ),
+        (info.width, info.height)
+    );
+    assert_eq!(png::ColorType::Grayscale, info.color_type);
+}
@@ -0,0 +1,83 @@
+//! Generated passwords, wiped from memory once dropped with the `zeroize` feature.
+//!
+//! Without the feature, [`Passwd`] is a plain `String` and nothing is wiped.
+
+#[cfg(all(test, feature = "zeroize"))]
+mod tests;
+
+use crate::compat::*;
+#[cfg(feature = "zeroize")]
+use core::fmt;
+#[cfg(feature = "zeroize")]
+use core::ops::Deref;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, Zeroizing};
+
+/// Password returned by generators.
+#[cfg(feature = "zeroize")]
+pub type Passwd = SecretString;
+
+/// Password returned by generators.
+#[cfg(not(feature = "zeroize"))]
+pub type Passwd = String;
+
+/// String overwritten with zeroes when dropped, redacted from `Debug` output.
+#[cfg(feature = "zeroize")]
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(Zeroizing<String>);
+
+#[cfg(feature = "zeroize")]
+impl SecretString {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        SecretString(Zeroizing::new(value))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Deref for SecretString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+/// Intermediate buffers holding parts of a password, wiped after use with the `zeroize` feature.
+pub(crate) trait Wipe {
+    fn wipe(&mut self);
+}
+
+impl Wipe for String {
+    fn wipe(&mut self) {
+        #[cfg(feature = "zeroize")]
+        self.zeroize();
+    }
+}
+
+impl Wipe for Vec<String> {
+    fn wipe(&mut self) {
+        #[cfg(feature = "zeroize")]
+        self.zeroize();
+    }
+}
+
+impl Wipe for Vec<u8> {
+    fn wipe(&mut self) {
+        #[cfg(feature = "zeroize")]
+        self.zeroize();
+    }
+}
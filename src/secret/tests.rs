@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn test_secret_string() {
+    let secret = SecretString::from("correct.HORSE.battery.42".to_string());
+    assert_eq!("correct.HORSE.battery.42", secret.as_str());
+    assert_eq!(24, secret.len());
+    assert!(secret.contains("HORSE"));
+
+    assert_eq!(secret.clone(), secret);
+    assert_eq!(SecretString::from(String::new()), SecretString::default());
+}
+
+#[test]
+fn test_secret_string_debug() {
+    let secret = SecretString::from("correct.HORSE.battery.42".to_string());
+    assert_eq!("SecretString([REDACTED])", format!("{:?}", secret));
+
+    let result: Result<(SecretString, u8), String> = Ok((secret, 1));
+    assert!(!format!("{:?}", result).contains("HORSE"));
+}
+
+#[test]
+fn test_wipe() {
+    let mut passwd = "correct.HORSE".to_string();
+    passwd.wipe();
+    assert!(passwd.is_empty());
+
+    let mut words = vec!["correct".to_string(), "HORSE".to_string()];
+    words.wipe();
+    assert!(words.is_empty());
+}
@@ -0,0 +1,284 @@
+//! Compact & versioned settings codes, e.g. `xk1:w4-l4.8-t5-s~2D-d0.0-y-n0.0`,
+//! to share the same password recipe between the CLI & web apps, in URLs or chats.
+//!
+//! A code is a list of `-` separated fields, each made of a key & a value:
+//!
+//! | key | value                                          |
+//! |-----|------------------------------------------------|
+//! | `w` | words count                                    |
+//! | `l` | `min.max` word lengths                         |
+//! | `t` | word transforms as bit flags                   |
+//! | `s` | separators                                     |
+//! | `d` | `before.after` padding digits                  |
+//! | `y` | padding symbols                                |
+//! | `n` | `before.after` padding symbols                 |
+//! | `a` | adaptive padding length, fixed padding if none |
+//! | `x` | `.` separated excluded words                   |
+//! | `i` | `.` separated included words                   |
+//! | `r` | readable settings, without any value           |
+//! | `k` | keyboard name, e.g. `fr` or `pt~2Dbr`          |
+//!
+//! Missing fields keep default values. Characters other than ASCII letters, digits,
+//! `.` & `_` are escaped as `~XX` per UTF-8 byte, so that codes are safe in URLs.
+
+use super::Settings;
+use crate::bit_flags::FieldSize;
+use crate::compat::*;
+use crate::prelude::{Builder, Keyboard, PaddingStrategy};
+use core::fmt::Write;
+
+const CODE_VERSION: &str = "xk1";
+const ESCAPE_CHAR: char = '~';
+const ALL_TRANSFORMS: FieldSize = 0b11001111; // all flags of WordTransform
+
+#[derive(Debug, Default)]
+struct CodeFields {
+    words_count: Option<u8>,
+    word_lengths: Option<(u8, u8)>,
+    word_transforms: Option<FieldSize>,
+    separators: Option<String>,
+    padding_digits: Option<(u8, u8)>,
+    padding_symbols: Option<String>,
+    padding_symbol_lengths: Option<(u8, u8)>,
+    adaptive_length: Option<usize>,
+    excluded_words: Option<Vec<String>>,
+    included_words: Option<Vec<String>>,
+    readable: Option<bool>,
+    keyboard: Option<Keyboard>,
+}
+
+impl Settings {
+    /// Compact code of these settings, to be decoded with [`Builder::from_code`].
+    ///
+    /// Available word lengths depend on the dictionary in use, so they are not part of the code.
+    pub fn to_code(&self) -> String {
+        let mut code = format!(
+            "{}:w{}-l{}.{}-t{}-s{}-d{}.{}-y{}-n{}.{}",
+            CODE_VERSION,
+            self.words_count,
+            self.word_lengths.0,
+            self.word_lengths.1,
+            self.word_transforms,
+            escape(&self.separators, false),
+            self.padding_digits.0,
+            self.padding_digits.1,
+            escape(&self.padding_symbols, false),
+            self.padding_symbol_lengths.0,
+            self.padding_symbol_lengths.1,
+        );
+
+        if let PaddingStrategy::Adaptive(length) = self.padding_strategy {
+            let _ = write!(code, "-a{}", length);
+        }
+
+        for (key, words) in [('x', &self.excluded_words), ('i', &self.included_words)] {
+            if !words.is_empty() {
+                let words: Vec<String> = words.iter().map(|word| escape(word, true)).collect();
+                let _ = write!(code, "-{}{}", key, words.join("."));
+            }
+        }
+
+        if self.readable {
+            code.push_str("-r");
+        }
+
+        if let Some(keyboard) = self.keyboard {
+            let _ = write!(code, "-k{}", escape(keyboard.name(), false));
+        }
+
+        code
+    }
+}
+
+/// Builder methods are applied in the same order as the CLI does,
+/// so that decoded settings are validated just like any other ones.
+pub(crate) fn decode<B: Builder>(code: &str) -> Result<B, String> {
+    let fields = parse_fields(code)?;
+    let mut settings = B::default();
+
+    if let Some((min, max)) = fields.word_lengths {
+        settings = settings.with_word_lengths(Some(min), Some(max))?;
+    }
+
+    if let Some((prefix, suffix)) = fields.padding_digits {
+        settings = settings.with_padding_digits(Some(prefix), Some(suffix));
+    }
+
+    if let Some((prefix, suffix)) = fields.padding_symbol_lengths {
+        settings = settings.with_padding_symbol_lengths(Some(prefix), Some(suffix));
+    }
+
+    if let Some(words_count) = fields.words_count {
+        settings = settings.with_words_count(words_count)?;
+    }
+
+    if let Some(transforms) = fields.word_transforms {
+        settings = settings.with_word_transforms(transforms)?;
+    }
+
+    if let Some(separators) = &fields.separators {
+        settings = settings.with_separators(separators);
+    }
+
+    if let Some(symbols) = &fields.padding_symbols {
+        settings = settings.with_padding_symbols(symbols);
+    }
+
+    if let Some(length) = fields.adaptive_length {
+        settings = settings.with_padding_strategy(PaddingStrategy::Adaptive(length))?;
+    }
+
+    if let Some(words) = &fields.excluded_words {
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        settings = settings.with_excluded_words(&words);
+    }
+
+    if let Some(words) = &fields.included_words {
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        settings = settings.with_included_words(&words);
+    }
+
+    if let Some(readable) = fields.readable {
+        settings = settings.with_readable(readable);
+    }
+
+    if fields.keyboard.is_some() {
+        settings = settings.with_keyboard(fields.keyboard);
+    }
+
+    Ok(settings)
+}
+
+fn parse_fields(code: &str) -> Result<CodeFields, String> {
+    let body = match code.trim().split_once(':') {
+        Some((CODE_VERSION, body)) => body,
+        Some((version, _)) if version.starts_with("xk") => {
+            return Err(format!("unsupported settings code version '{}'", version))
+        }
+        _ => {
+            return Err(format!(
+                "invalid settings code, expect a '{}:' prefix",
+                CODE_VERSION
+            ))
+        }
+    };
+
+    let mut fields = CodeFields::default();
+    if body.is_empty() {
+        return Ok(fields);
+    }
+
+    for field in body.split('-') {
+        let mut chars = field.chars();
+        let key = chars.next().ok_or("empty field in settings code")?;
+        let value = chars.as_str();
+
+        let duplicated = match key {
+            'w' => fields
+                .words_count
+                .replace(parse_number(key, value)?)
+                .is_some(),
+            'l' => fields
+                .word_lengths
+                .replace(parse_pair(key, value)?)
+                .is_some(),
+            't' => {
+                let transforms = parse_number(key, value)?;
+                if transforms & !ALL_TRANSFORMS != 0 {
+                    return Err(format!("invalid word transforms '{}'", value));
+                }
+                fields.word_transforms.replace(transforms).is_some()
+            }
+            's' => fields.separators.replace(unescape(value)?).is_some(),
+            'd' => fields
+                .padding_digits
+                .replace(parse_pair(key, value)?)
+                .is_some(),
+            'y' => fields.padding_symbols.replace(unescape(value)?).is_some(),
+            'n' => fields
+                .padding_symbol_lengths
+                .replace(parse_pair(key, value)?)
+                .is_some(),
+            'a' => fields
+                .adaptive_length
+                .replace(parse_number(key, value)?)
+                .is_some(),
+            'x' => fields.excluded_words.replace(parse_words(value)?).is_some(),
+            'i' => fields.included_words.replace(parse_words(value)?).is_some(),
+            'r' if value.is_empty() => fields.readable.replace(true).is_some(),
+            'r' => return Err(format!("unexpected value '{}' for field 'r'", value)),
+            'k' => {
+                let name = unescape(value)?;
+                let keyboard = Keyboard::from_name(&name)
+                    .ok_or_else(|| format!("unknown keyboard '{}'", name))?;
+                fields.keyboard.replace(keyboard).is_some()
+            }
+            _ => return Err(format!("unknown field '{}' in settings code", key)),
+        };
+
+        if duplicated {
+            return Err(format!("duplicate field '{}' in settings code", key));
+        }
+    }
+
+    Ok(fields)
+}
+
+fn parse_number<T: core::str::FromStr>(key: char, value: &str) -> Result<T, String> {
+    // signs are not part of codes
+    if !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()) {
+        if let Ok(number) = value.parse() {
+            return Ok(number);
+        }
+    }
+
+    Err(format!("invalid number '{}' for field '{}'", value, key))
+}
+
+fn parse_pair(key: char, value: &str) -> Result<(u8, u8), String> {
+    let (first, second) = value
+        .split_once('.')
+        .ok_or_else(|| format!("invalid pair '{}' for field '{}'", value, key))?;
+    Ok((parse_number(key, first)?, parse_number(key, second)?))
+}
+
+fn parse_words(value: &str) -> Result<Vec<String>, String> {
+    value.split('.').map(unescape).collect()
+}
+
+fn escape(text: &str, in_list: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for char in text.chars() {
+        if char.is_ascii_alphanumeric() || char == '_' || (char == '.' && !in_list) {
+            escaped.push(char);
+            continue;
+        }
+
+        let mut bytes = [0; 4];
+        for byte in char.encode_utf8(&mut bytes).bytes() {
+            let _ = write!(escaped, "{}{:02X}", ESCAPE_CHAR, byte);
+        }
+    }
+
+    escaped
+}
+
+fn unescape(text: &str) -> Result<String, String> {
+    let invalid = || format!("invalid escape sequence in '{}'", text);
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find(ESCAPE_CHAR) {
+        bytes.extend_from_slice(&rest.as_bytes()[..index]);
+        let hex = rest
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .ok_or_else(invalid)?;
+        bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+        rest = &rest[index + 3..];
+    }
+
+    bytes.extend_from_slice(rest.as_bytes());
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
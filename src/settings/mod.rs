@@ -1,20 +1,19 @@
+mod code;
 #[cfg(test)]
 mod tests;
 
 use crate::bit_flags::{BitFlags, FieldSize, WordTransform};
+use crate::compat::*;
 use crate::prelude::{
-    Builder, Entropy, GuessTime, PaddingResult, PaddingStrategy, Preset, Randomizer,
+    bundled_word_lengths, Builder, Entropy, GuessTime, Keyboard, PaddingResult, PaddingStrategy,
+    Preset, Randomizer,
 };
+use core::cmp;
+use core::fmt;
+use core::ops::Range;
+use core::result::Result;
 use rand::distributions::{Distribution, Uniform};
-use rand::Rng;
-use std::cmp;
-use std::collections::HashMap;
-use std::fmt;
-use std::ops::Range;
-use std::result::Result;
-
-const MIN_WORD_LENGTH_ERR: &str = "min word length must be 4 or higher";
-const MAX_WORD_LENGTH_ERR: &str = "max word length must be 10 or lower";
+use rand::{Rng, RngCore};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Settings {
@@ -26,6 +25,11 @@ pub struct Settings {
     padding_symbols: String,
     padding_symbol_lengths: (u8, u8),
     padding_strategy: PaddingStrategy,
+    excluded_words: BTreeSet<String>,
+    included_words: BTreeSet<String>,
+    available_word_lengths: Vec<u8>,
+    readable: bool,
+    keyboard: Option<Keyboard>,
 }
 
 impl Default for Settings {
@@ -39,6 +43,11 @@ impl Default for Settings {
             padding_symbols: Self::DEFAULT_SYMBOLS.to_string(),
             padding_symbol_lengths: (0, Self::DEFAULT_PADDING_LENGTH),
             padding_strategy: Self::DEFAULT_PADDING_STRATEGY,
+            excluded_words: BTreeSet::new(),
+            included_words: BTreeSet::new(),
+            available_word_lengths: bundled_word_lengths(),
+            readable: false,
+            keyboard: None,
         }
     }
 }
@@ -62,10 +71,11 @@ impl fmt::Display for Settings {
             desc.push(format!("{} only", word_transforms[0]))
         }
 
-        if self.separators.len() > 1 {
-            desc.push(format!("a separator from ⟪{}⟫", self.separators));
+        let separators = self.separators_in_use();
+        if separators.chars().count() > 1 {
+            desc.push(format!("a separator from ⟪{}⟫", separators));
         } else {
-            desc.push(format!("'{}' as separator", self.separators));
+            desc.push(format!("'{}' as separator", separators));
         }
 
         let (prefix, suffix) = self.padding_digits;
@@ -81,10 +91,11 @@ impl fmt::Display for Settings {
             desc.push(format!("{} digit(s) after", suffix));
         }
 
-        let padding_symbols = if self.padding_symbols.len() > 1 {
-            format!("from ⟪{}⟫", self.padding_symbols)
+        let padding_symbols_in_use = self.padding_symbols_in_use();
+        let padding_symbols = if padding_symbols_in_use.chars().count() > 1 {
+            format!("from ⟪{}⟫", padding_symbols_in_use)
         } else {
-            format!("of '{}'", self.padding_symbols)
+            format!("of '{}'", padding_symbols_in_use)
         };
 
         let (prefix, suffix) = self.padding_symbol_lengths;
@@ -115,6 +126,32 @@ impl fmt::Display for Settings {
 
         desc.push(padding);
 
+        if !self.included_words.is_empty() {
+            desc.push(format!(
+                "only from {} allowed word(s)",
+                self.included_words.len()
+            ));
+        }
+
+        if !self.excluded_words.is_empty() {
+            desc.push(format!("excluding {} word(s)", self.excluded_words.len()));
+        }
+
+        if self.readable {
+            desc.push("no visually ambiguous characters & words".to_string());
+        }
+
+        // keyboards only matter when they leave out some symbols
+        if let Some(keyboard) = self.keyboard {
+            let mut symbols = self.separators.chars().chain(self.padding_symbols.chars());
+            if symbols.any(|symbol| !keyboard.can_type(symbol)) {
+                desc.push(format!(
+                    "only symbols typable on {} keyboards",
+                    keyboard.name()
+                ));
+            }
+        }
+
         let len = desc.len();
         if len > 1 {
             write!(
@@ -151,12 +188,23 @@ impl Builder for Settings {
         let min = cmp::min(min_length, max_length);
         let max = cmp::max(min_length, max_length);
 
-        if min < Self::MIN_WORD_LENGTH {
-            return Err(MIN_WORD_LENGTH_ERR.to_string());
+        let (lowest, highest) = self.word_length_bounds();
+
+        if min < lowest {
+            return Err(format!("min word length must be {} or higher", lowest));
         }
 
-        if max > Self::MAX_WORD_LENGTH {
-            return Err(MAX_WORD_LENGTH_ERR.to_string());
+        if max > highest {
+            return Err(format!("max word length must be {} or lower", highest));
+        }
+
+        if !self.available_word_lengths.is_empty()
+            && !self
+                .available_word_lengths
+                .iter()
+                .any(|len| (min..=max).contains(len))
+        {
+            return Err(format!("no words with length from {} to {}", min, max));
         }
 
         let mut cloned = self.clone();
@@ -164,6 +212,30 @@ impl Builder for Settings {
         Ok(cloned)
     }
 
+    fn with_available_word_lengths(&self, lengths: &[u8]) -> Result<Self, String> {
+        let mut available_word_lengths: Vec<u8> =
+            lengths.iter().copied().filter(|&len| len > 0).collect();
+        available_word_lengths.sort();
+        available_word_lengths.dedup();
+
+        let (lowest, highest) = match (
+            available_word_lengths.first(),
+            available_word_lengths.last(),
+        ) {
+            (Some(&lowest), Some(&highest)) => (lowest, highest),
+            _ => return Err("no words available in dictionary".to_string()),
+        };
+
+        // fit current word lengths into the new bounds
+        let (min, max) = self.word_lengths;
+        let min = min.clamp(lowest, highest);
+        let max = max.clamp(lowest, highest);
+
+        let mut cloned = self.clone();
+        cloned.available_word_lengths = available_word_lengths;
+        cloned.with_word_lengths(Some(min), Some(max))
+    }
+
     fn with_separators(&self, separators: &str) -> Self {
         let mut cloned = self.clone();
         cloned.separators = separators.to_string();
@@ -210,6 +282,12 @@ impl Builder for Settings {
             PaddingStrategy::Adaptive(0) => {
                 return Err("invalid adaptive padding number".to_string())
             }
+            PaddingStrategy::Adaptive(length) if length > PaddingStrategy::MAX_ADAPTIVE_LENGTH => {
+                return Err(format!(
+                    "adaptive padding number must be {} or lower",
+                    PaddingStrategy::MAX_ADAPTIVE_LENGTH
+                ))
+            }
             PaddingStrategy::Adaptive(_) => {
                 cloned.padding_strategy = strategy;
                 cloned.padding_symbol_lengths = (0, 0);
@@ -250,6 +328,34 @@ impl Builder for Settings {
         Ok(cloned)
     }
 
+    fn with_excluded_words(&self, words: &[&str]) -> Self {
+        let mut cloned = self.clone();
+        cloned.excluded_words = normalize_words(words);
+        cloned
+    }
+
+    fn with_included_words(&self, words: &[&str]) -> Self {
+        let mut cloned = self.clone();
+        cloned.included_words = normalize_words(words);
+        cloned
+    }
+
+    fn with_readable(&self, readable: bool) -> Self {
+        let mut cloned = self.clone();
+        cloned.readable = readable;
+        cloned
+    }
+
+    fn with_keyboard(&self, keyboard: Option<Keyboard>) -> Self {
+        let mut cloned = self.clone();
+        cloned.keyboard = keyboard;
+        cloned
+    }
+
+    fn from_code(code: &str) -> Result<Self, String> {
+        code::decode(code)
+    }
+
     fn from_preset(preset: Preset) -> Self {
         match preset {
             Preset::AppleID => Settings {
@@ -261,6 +367,7 @@ impl Builder for Settings {
                 padding_symbols: "!?@&".to_string(),
                 padding_symbol_lengths: (1, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                ..Self::default()
             },
             Preset::WindowsNtlmV1 => Settings {
                 words_count: 2,
@@ -271,6 +378,7 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (0, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                ..Self::default()
             },
             Preset::SecurityQuestions => Settings {
                 words_count: 6,
@@ -281,6 +389,7 @@ impl Builder for Settings {
                 padding_symbols: ".!?".to_string(),
                 padding_symbol_lengths: (0, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                ..Self::default()
             },
             Preset::Web16 => Settings {
                 words_count: 3,
@@ -291,6 +400,7 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (1, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                ..Self::default()
             },
             Preset::Web32 => Settings {
                 words_count: 4,
@@ -301,6 +411,7 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (1, 1),
                 padding_strategy: PaddingStrategy::Fixed,
+                ..Self::default()
             },
             Preset::Wifi => Settings {
                 words_count: 6,
@@ -311,6 +422,7 @@ impl Builder for Settings {
                 padding_symbols: "!@$%^&*+=:|~?".to_string(),
                 padding_symbol_lengths: (0, 0),
                 padding_strategy: PaddingStrategy::Adaptive(63),
+                ..Self::default()
             },
             Preset::Xkcd => Settings {
                 words_count: 4,
@@ -321,6 +433,7 @@ impl Builder for Settings {
                 padding_symbols: "".to_string(),
                 padding_symbol_lengths: (0, 0),
                 padding_strategy: PaddingStrategy::Fixed,
+                ..Self::default()
             },
             _ => Self::default(),
         }
@@ -333,8 +446,50 @@ impl Randomizer for Settings {
         min..(max + 1)
     }
 
-    fn rand_words(&self, pool: &[&str]) -> Vec<String> {
-        let words_list = self.build_words_list(pool);
+    fn filter_words<'a>(&self, pool: Cow<'a, [&'a str]>) -> Result<Cow<'a, [&'a str]>, String> {
+        if self.excluded_words.is_empty() && self.included_words.is_empty() && !self.readable {
+            return Ok(pool);
+        }
+
+        let filtered: Vec<&str> = pool
+            .iter()
+            .copied()
+            .filter(|word| self.accepts_word(word))
+            .collect();
+
+        let distinct_count = filtered.iter().collect::<Set<_>>().len();
+        log::debug!("filtered pool down to {} distinct words", distinct_count);
+
+        if distinct_count < self.words_count as usize {
+            return Err(format!(
+                "only {} word(s) left after filtering, {} required",
+                distinct_count, self.words_count
+            ));
+        }
+
+        // alternating cases need words without any `i` to uppercase
+        if self.readable
+            && self.has_uppercase_position()
+            && filtered
+                .iter()
+                .all(|word| has_uppercase_i(word, WordTransform::Uppercase))
+        {
+            return Err("no word left without an 'i' to uppercase".to_string());
+        }
+
+        Ok(Cow::Owned(filtered))
+    }
+
+    fn rand_words(&self, rng: &mut dyn RngCore, pool: &[&str]) -> Vec<String> {
+        if self.readable {
+            return self
+                .build_readable_words_list(rng, pool)
+                .into_iter()
+                .map(|(word, transform)| transform_word(word, transform))
+                .collect();
+        }
+
+        let words_list = self.build_words_list(rng, pool);
 
         log::debug!(
             "randomizing {} words from a pool of {} entries",
@@ -342,7 +497,7 @@ impl Randomizer for Settings {
             pool.len()
         );
 
-        let transforms_list = self.build_transforms_list();
+        let transforms_list = self.build_transforms_list(rng);
 
         log::debug!(
             "transforming words in order of [{}]",
@@ -356,29 +511,29 @@ impl Randomizer for Settings {
             .collect()
     }
 
-    fn rand_separator(&self) -> String {
-        rand_chars(&self.separators, 1)
+    fn rand_separator(&self, rng: &mut dyn RngCore) -> String {
+        rand_chars(rng, &self.separators_in_use(), 1)
     }
 
-    fn rand_prefix(&self) -> (String, String) {
+    fn rand_prefix(&self, rng: &mut dyn RngCore) -> (String, String) {
         let (prefix_digits, _) = self.padding_digits;
         let (prefix_symbols, _) = self.padding_symbol_lengths;
         (
-            rand_chars(&self.padding_symbols, prefix_symbols as usize),
-            rand_digits(prefix_digits),
+            rand_chars(rng, &self.padding_symbols_in_use(), prefix_symbols as usize),
+            self.rand_padding_digits(rng, prefix_digits),
         )
     }
 
-    fn rand_suffix(&self) -> (String, String) {
+    fn rand_suffix(&self, rng: &mut dyn RngCore) -> (String, String) {
         let (_, suffix_digits) = self.padding_digits;
         let (_, suffix_symbols) = self.padding_symbol_lengths;
         (
-            rand_digits(suffix_digits),
-            rand_chars(&self.padding_symbols, suffix_symbols as usize),
+            self.rand_padding_digits(rng, suffix_digits),
+            rand_chars(rng, &self.padding_symbols_in_use(), suffix_symbols as usize),
         )
     }
 
-    fn adjust_padding(&self, pass_length: usize) -> PaddingResult {
+    fn adjust_padding(&self, rng: &mut dyn RngCore, pass_length: usize) -> PaddingResult {
         match self.padding_strategy {
             PaddingStrategy::Fixed => PaddingResult::Unchanged,
             PaddingStrategy::Adaptive(len) => match len.cmp(&pass_length) {
@@ -392,11 +547,12 @@ impl Randomizer for Settings {
                     PaddingResult::TrimTo(len)
                 }
                 cmp::Ordering::Greater => {
-                    let padded_symbols = rand_chars(&self.padding_symbols, len - pass_length);
+                    let padded_symbols =
+                        rand_chars(rng, &self.padding_symbols_in_use(), len - pass_length);
 
                     log::debug!(
                         "padded {} symbols to fit padding strategy",
-                        padded_symbols.len()
+                        len - pass_length
                     );
 
                     PaddingResult::Pad(padded_symbols)
@@ -450,70 +606,110 @@ impl Randomizer for Settings {
         let mut blind_pool_size = if single_word_transform { 26 } else { 52 };
 
         if self.padding_digits != (0, 0) {
-            blind_pool_size += 10
+            blind_pool_size += self.digits_count()
         } else {
             blind_pool_size += 0
         };
 
-        if !self.separators.is_empty() || !self.padding_symbols.is_empty() {
-            blind_pool_size += 32;
+        let separators = self.separators_in_use();
+        let padding_symbols = self.padding_symbols_in_use();
+
+        if !separators.is_empty() || !padding_symbols.is_empty() {
+            blind_pool_size += (b'!'..=b'~')
+                .map(char::from)
+                .filter(|&symbol| symbol.is_ascii_punctuation() && self.uses_symbol(symbol))
+                .count();
         }
 
         log::debug!("entropy: blind pool size of {}", blind_pool_size);
 
-        let blind_pool = (blind_pool_size as f64).log2();
+        let blind_pool = log2(blind_pool_size as f64);
         let blind_min = (min_total_len as f64) * blind_pool;
         let blind_max = (max_total_len as f64) * blind_pool;
 
         let seen_words_pool_size = pool_size * self.word_transforms.to_flags().len();
         log::debug!("entropy: seen words pool size of {}", seen_words_pool_size);
 
-        let seen_words_entropy = (self.words_count as f64) * (seen_words_pool_size as f64).log2();
+        let seen_words_entropy = (self.words_count as f64) * log2(seen_words_pool_size as f64);
 
-        let seen_separator_entropy = if self.separators.is_empty() {
+        let seen_separator_entropy = if separators.is_empty() {
             0.0
         } else {
-            (self.separators.len() as f64).log2()
+            log2(separators.chars().count() as f64)
         };
 
         let seen_digits_entropy = if self.padding_digits == (0, 0) {
             0.0
         } else {
-            f64::from(self.padding_digits.0 + self.padding_digits.1) * 10f64.log2()
+            f64::from(self.padding_digits.0 + self.padding_digits.1)
+                * log2(self.digits_count() as f64)
         };
 
-        let seen_symbols_entropy = if self.padding_symbols.is_empty() {
+        let seen_symbols_entropy = if padding_symbols.is_empty() {
             0.0
         } else {
-            (self.padding_symbols.len() as f64).log2()
+            log2(padding_symbols.chars().count() as f64)
         };
 
-        let seen = (seen_words_entropy
-            + seen_separator_entropy
-            + seen_digits_entropy
-            + seen_symbols_entropy)
-            .round() as usize;
+        let seen = round(
+            seen_words_entropy
+                + seen_separator_entropy
+                + seen_digits_entropy
+                + seen_symbols_entropy,
+        ) as usize;
 
         Entropy {
-            blind_min: blind_min.round() as usize,
-            blind_max: blind_max.round() as usize,
+            blind_min: round(blind_min) as usize,
+            blind_max: round(blind_max) as usize,
             seen,
             guess_time: GuessTime::for_entropy(seen),
         }
     }
+
+    fn usable_pool_size(&self, pool: &[&str], pool_size: usize) -> usize {
+        if !self.readable || pool.is_empty() {
+            return pool_size;
+        }
+
+        // alternating cases are counted as if all words were uppercase, a lower bound
+        let transforms = if self.has_uppercase_position() {
+            vec![WordTransform::Uppercase]
+        } else if self.altercase_transform(0).is_some() {
+            vec![WordTransform::Lowercase]
+        } else {
+            self.transforms_in_use()
+        };
+
+        // words & transforms pairs are equally likely, see `build_readable_words_list`
+        let readable_pairs = pool
+            .iter()
+            .flat_map(|word| {
+                transforms
+                    .iter()
+                    .filter(move |&&transform| !has_uppercase_i(word, transform))
+            })
+            .count();
+
+        pool_size * readable_pairs / (pool.len() * transforms.len())
+    }
 }
 
 impl Settings {
-    const MIN_WORD_LENGTH: u8 = 4;
-    const MAX_WORD_LENGTH: u8 = 10;
     const DEFAULT_PADDING_LENGTH: u8 = 2;
     const DEFAULT_PADDING_STRATEGY: PaddingStrategy = PaddingStrategy::Fixed;
     const DEFAULT_SEPARATORS: &str = ".-_~";
     const DEFAULT_SYMBOLS: &str = "~@$%^&*-_+=:|?/.;";
     const DEFAULT_WORDS_COUNT: u8 = 3;
-    const DEFAULT_WORD_LENGTHS: (u8, u8) = (Self::MIN_WORD_LENGTH, Self::MAX_WORD_LENGTH);
+    const DEFAULT_WORD_LENGTHS: (u8, u8) = (4, 10);
     const DEFAULT_WORD_TRANSFORMS: FieldSize = 0b00000101; // WordTransform::Lowercase | WordTransform::Uppercase
 
+    // left out of separators & padding symbols of readable settings
+    const AMBIGUOUS_SYMBOLS: &str = "|`'\",";
+    // 0 & 1 look like O, I & l
+    const READABLE_DIGITS: &str = "23456789";
+    // letter runs mistaken for single letters once printed, e.g. `rn` for `m`
+    const CONFUSABLE_RUNS: [&str; 3] = ["rn", "vv", "cl"];
+
     const ALL_SINGLE_WORD_TRANSFORMS: [WordTransform; 4] = [
         WordTransform::Lowercase,
         WordTransform::Titlecase,
@@ -521,88 +717,332 @@ impl Settings {
         WordTransform::InversedTitlecase,
     ];
 
-    fn build_words_list<'a>(&self, pool: &[&'a str]) -> Vec<&'a str> {
+    pub fn words_count(&self) -> u8 {
+        self.words_count
+    }
+
+    pub fn word_transforms(&self) -> FieldSize {
+        self.word_transforms
+    }
+
+    pub fn separators(&self) -> &str {
+        &self.separators
+    }
+
+    pub fn padding_digits(&self) -> (u8, u8) {
+        self.padding_digits
+    }
+
+    pub fn padding_symbols(&self) -> &str {
+        &self.padding_symbols
+    }
+
+    pub fn padding_symbol_lengths(&self) -> (u8, u8) {
+        self.padding_symbol_lengths
+    }
+
+    pub fn padding_strategy(&self) -> PaddingStrategy {
+        self.padding_strategy.clone()
+    }
+
+    pub fn excluded_words(&self) -> &BTreeSet<String> {
+        &self.excluded_words
+    }
+
+    pub fn included_words(&self) -> &BTreeSet<String> {
+        &self.included_words
+    }
+
+    pub fn available_word_lengths(&self) -> &[u8] {
+        &self.available_word_lengths
+    }
+
+    pub fn readable(&self) -> bool {
+        self.readable
+    }
+
+    pub fn keyboard(&self) -> Option<Keyboard> {
+        self.keyboard
+    }
+
+    /// Settings restricted to the usual layout of a language unless they have their own keyboard:
+    /// separators & padding symbols of presets leave out those untypable on it,
+    /// custom ones are kept as is.
+    pub fn with_language_keyboard(&self, keyboard: Keyboard) -> Self {
+        let mut cloned = self.clone();
+        if self.keyboard.is_some() {
+            return cloned;
+        }
+
+        cloned.separators = typable_preset_symbols(&self.separators, keyboard, Self::separators);
+        cloned.padding_symbols =
+            typable_preset_symbols(&self.padding_symbols, keyboard, Self::padding_symbols);
+        cloned
+    }
+
+    fn word_length_bounds(&self) -> (u8, u8) {
+        match (
+            self.available_word_lengths.first(),
+            self.available_word_lengths.last(),
+        ) {
+            (Some(&lowest), Some(&highest)) => (lowest, highest),
+            // no dictionary bundled, lengths are checked against the word source when generating
+            _ => (1, u8::MAX),
+        }
+    }
+
+    fn accepts_word(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+
+        if !self.included_words.is_empty() && !self.included_words.contains(&word) {
+            return false;
+        }
+
+        if self.readable && !self.is_readable_word(&word) {
+            return false;
+        }
+
+        !self.excluded_words.contains(&word)
+    }
+
+    // words without confusable runs, that some word transform keeps without an `I`
+    fn is_readable_word(&self, word: &str) -> bool {
+        if Self::CONFUSABLE_RUNS.iter().any(|run| word.contains(run)) {
+            return false;
+        }
+
+        self.transforms_in_use()
+            .into_iter()
+            .any(|transform| !has_uppercase_i(word, transform))
+    }
+
+    // single word transforms picked from, lowercase & uppercase for alternating cases
+    fn transforms_in_use(&self) -> Vec<WordTransform> {
+        if self.altercase_transform(0).is_some() {
+            return vec![WordTransform::Lowercase, WordTransform::Uppercase];
+        }
+
+        Self::ALL_SINGLE_WORD_TRANSFORMS
+            .into_iter()
+            .filter(|&transform| self.word_transforms & transform)
+            .collect()
+    }
+
+    // transform of the word at the given index for alternating cases, `None` for random ones
+    fn altercase_transform(&self, idx: u8) -> Option<WordTransform> {
+        let upper_first = if self
+            .word_transforms
+            .has_flag(WordTransform::AltercaseLowerFirst)
+        {
+            false
+        } else if self
+            .word_transforms
+            .has_flag(WordTransform::AltercaseUpperFirst)
+        {
+            true
+        } else {
+            return None;
+        };
+
+        if idx.is_multiple_of(2) == upper_first {
+            Some(WordTransform::Uppercase)
+        } else {
+            Some(WordTransform::Lowercase)
+        }
+    }
+
+    fn has_uppercase_position(&self) -> bool {
+        (0..self.words_count)
+            .any(|idx| self.altercase_transform(idx) == Some(WordTransform::Uppercase))
+    }
+
+    fn rand_transform(&self, rng: &mut dyn RngCore, idx: u8) -> WordTransform {
+        if let Some(transform) = self.altercase_transform(idx) {
+            return transform;
+        }
+
+        let transforms = self.transforms_in_use();
+        let index: usize = Uniform::from(0..transforms.len()).sample(rng);
+        transforms[index]
+    }
+
+    fn separators_in_use(&self) -> Cow<'_, str> {
+        self.symbols_in_use(&self.separators)
+    }
+
+    fn padding_symbols_in_use(&self) -> Cow<'_, str> {
+        self.symbols_in_use(&self.padding_symbols)
+    }
+
+    // symbols left once ambiguous & untypable ones are left out
+    fn symbols_in_use<'a>(&self, symbols: &'a str) -> Cow<'a, str> {
+        if !self.readable && self.keyboard.is_none() {
+            return Cow::Borrowed(symbols);
+        }
+
+        Cow::Owned(
+            symbols
+                .chars()
+                .filter(|&symbol| self.uses_symbol(symbol))
+                .collect(),
+        )
+    }
+
+    fn uses_symbol(&self, symbol: char) -> bool {
+        if self.readable && Self::AMBIGUOUS_SYMBOLS.contains(symbol) {
+            return false;
+        }
+
+        self.keyboard
+            .is_none_or(|keyboard| keyboard.can_type(symbol))
+    }
+
+    fn digits_count(&self) -> usize {
+        if self.readable {
+            Self::READABLE_DIGITS.len()
+        } else {
+            10
+        }
+    }
+
+    fn rand_padding_digits(&self, rng: &mut dyn RngCore, count: u8) -> String {
+        if self.readable {
+            rand_readable_digits(rng, count)
+        } else {
+            rand_digits(rng, count)
+        }
+    }
+
+    fn build_words_list<'a>(&self, rng: &mut dyn RngCore, pool: &[&'a str]) -> Vec<&'a str> {
         if pool.is_empty() {
             return vec![];
         }
 
-        let mut rng = rand::thread_rng();
         let word_indices = Uniform::from(0..pool.len());
 
         // not enough words to distinguishably randomize
-        if pool.len() < self.words_count as usize {
+        if !has_distinct_words(pool, self.words_count as usize) {
             return (0..self.words_count)
                 .map(|_| {
-                    let index: usize = word_indices.sample(&mut rng);
+                    let index: usize = word_indices.sample(rng);
                     pool[index]
                 })
                 .collect();
         }
 
         // enough words, ensure no duplicates
-        let mut index_marker: HashMap<usize, bool> = HashMap::new();
+        let mut picked_words: Set<&str> = Set::new();
         (0..self.words_count)
             .map(|_| loop {
-                let index: usize = word_indices.sample(&mut rng);
+                let index: usize = word_indices.sample(rng);
                 let word = pool[index];
 
-                if let std::collections::hash_map::Entry::Vacant(e) = index_marker.entry(index) {
-                    e.insert(true);
+                if picked_words.insert(word) {
                     break word;
                 }
             })
             .collect()
     }
 
-    fn build_transforms_list(&self) -> Vec<WordTransform> {
-        if self
-            .word_transforms
-            .has_flag(WordTransform::AltercaseLowerFirst)
-        {
-            return (0..self.words_count)
-                .map(|idx| {
-                    if idx % 2 == 0 {
-                        WordTransform::Lowercase
-                    } else {
-                        WordTransform::Uppercase
-                    }
-                })
-                .collect();
-        }
+    fn build_transforms_list(&self, rng: &mut dyn RngCore) -> Vec<WordTransform> {
+        (0..self.words_count)
+            .map(|idx| self.rand_transform(rng, idx))
+            .collect()
+    }
 
-        if self
-            .word_transforms
-            .has_flag(WordTransform::AltercaseUpperFirst)
-        {
-            return (0..self.words_count)
-                .map(|idx| {
-                    if idx % 2 == 0 {
-                        WordTransform::Uppercase
-                    } else {
-                        WordTransform::Lowercase
-                    }
-                })
-                .collect();
+    // words are picked along with their transforms, drawn again together if the transform
+    // turns an `i` into an `I`, so that words with an `i` are still picked with other transforms
+    fn build_readable_words_list<'a>(
+        &self,
+        rng: &mut dyn RngCore,
+        pool: &[&'a str],
+    ) -> Vec<(&'a str, WordTransform)> {
+        if pool.is_empty() {
+            return vec![];
         }
 
-        let whitelisted_transforms: Vec<&WordTransform> = Self::ALL_SINGLE_WORD_TRANSFORMS
+        let word_indices = Uniform::from(0..pool.len());
+
+        // uppercase words of alternating cases first, other words can be picked from any word
+        let mut positions: Vec<u8> = (0..self.words_count).collect();
+        positions
+            .sort_by_key(|&idx| self.altercase_transform(idx) != Some(WordTransform::Uppercase));
+
+        let uppercase_count = positions
+            .iter()
+            .filter(|&&idx| self.altercase_transform(idx) == Some(WordTransform::Uppercase))
+            .count();
+        let uppercase_words: Vec<&str> = pool
             .iter()
-            .filter(|&&transform| self.word_transforms & transform)
+            .copied()
+            .filter(|word| !has_uppercase_i(word, WordTransform::Uppercase))
             .collect();
 
-        let mut rng = rand::thread_rng();
-        let transform_indices = Uniform::from(0..whitelisted_transforms.len());
+        // not enough words to distinguishably randomize
+        let distinct = has_distinct_words(pool, self.words_count as usize)
+            && has_distinct_words(&uppercase_words, uppercase_count);
 
-        (0..self.words_count)
-            .map(|_| {
-                let index: usize = transform_indices.sample(&mut rng);
-                *whitelisted_transforms[index]
-            })
-            .collect()
+        let mut picked_words: Set<&str> = Set::new();
+        let mut words_list = vec![(pool[0], WordTransform::Lowercase); self.words_count as usize];
+
+        for idx in positions {
+            words_list[idx as usize] = loop {
+                let index: usize = word_indices.sample(rng);
+                let word = pool[index];
+                let transform = self.rand_transform(rng, idx);
+
+                if !has_uppercase_i(word, transform) && (!distinct || picked_words.insert(word)) {
+                    break (word, transform);
+                }
+            };
+        }
+
+        words_list
+    }
+}
+
+// stops as soon as enough distinct words are seen, which is almost immediate
+// for dictionaries, so that no whole-pool set is built on every generation
+fn has_distinct_words(pool: &[&str], count: usize) -> bool {
+    let mut seen_words: Set<&str> = Set::new();
+
+    for &word in pool {
+        if seen_words.insert(word) && seen_words.len() >= count {
+            return true;
+        }
     }
+
+    seen_words.len() >= count
+}
+
+// symbols typable on the keyboard if they're those of a preset, as is otherwise
+fn typable_preset_symbols(
+    symbols: &str,
+    keyboard: Keyboard,
+    preset_symbols: fn(&Settings) -> &str,
+) -> String {
+    let is_preset = Preset::ALL
+        .into_iter()
+        .any(|preset| preset_symbols(&Settings::from_preset(preset)) == symbols);
+    if !is_preset {
+        return symbols.to_string();
+    }
+
+    symbols
+        .chars()
+        .filter(|&symbol| keyboard.can_type(symbol))
+        .collect()
+}
+
+fn normalize_words(words: &[&str]) -> BTreeSet<String> {
+    words
+        .iter()
+        .map(|word| word.trim().to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
 }
 
-fn rand_digits(count: u8) -> String {
+fn rand_digits(rng: &mut dyn RngCore, count: u8) -> String {
     if count == 0 {
         return "".to_string();
     }
@@ -621,31 +1061,57 @@ fn rand_digits(count: u8) -> String {
         u64::MAX
     };
 
-    let mut rng = rand::thread_rng();
-    let padding_digits: u64 = Uniform::from(lower_bound..upper_bound).sample(&mut rng);
+    let padding_digits: u64 = Uniform::from(lower_bound..upper_bound).sample(rng);
     padding_digits.to_string()
 }
 
-fn rand_chars(pool: &str, count: usize) -> String {
+fn rand_readable_digits(rng: &mut dyn RngCore, count: u8) -> String {
+    let digits = Settings::READABLE_DIGITS.as_bytes();
+    (0..count)
+        .map(|_| digits[rng.gen_range(0..digits.len())] as char)
+        .collect()
+}
+
+fn rand_chars(rng: &mut dyn RngCore, pool: &str, count: usize) -> String {
     if pool.is_empty() {
         return "".to_string();
     }
 
-    let mut rng = rand::thread_rng();
-    let idx = rng.gen_range(0..pool.len());
-    pool.chars()
-        .nth(idx)
-        .unwrap()
-        .to_string()
-        .repeat(count as _)
+    // symbols may be multi-byte characters, e.g. `€`
+    let idx = rng.gen_range(0..pool.chars().count());
+    let symbol = pool.chars().nth(idx).unwrap();
+    core::iter::repeat_n(symbol, count).collect()
+}
+
+// whether the transform turns an `i` into an `I`, easily mistaken for an `l`
+fn has_uppercase_i(word: &str, transform: WordTransform) -> bool {
+    word.chars()
+        .enumerate()
+        .any(|(idx, char)| match (transform, idx) {
+            (WordTransform::InversedTitlecase, 0) => false,
+            (WordTransform::Titlecase, 0)
+            | (WordTransform::Uppercase, _)
+            | (WordTransform::InversedTitlecase, _) => char == 'i' || char == 'I',
+            (WordTransform::Titlecase, _) => char == 'I',
+            _ => false,
+        })
 }
 
 fn transform_word(word: &str, transform: WordTransform) -> String {
-    match transform {
-        WordTransform::Titlecase => word[..1].to_uppercase() + &word[1..],
-        WordTransform::Uppercase => word.to_uppercase(),
-        WordTransform::InversedTitlecase => word[..1].to_lowercase() + &word[1..].to_uppercase(),
-        // lowercase by default
-        _ => word.to_lowercase(),
+    // allocated once, case mappings take up to 3 times as many bytes
+    let mut transformed = String::with_capacity(word.len() * 3);
+
+    for (idx, char) in word.chars().enumerate() {
+        match (transform, idx) {
+            (WordTransform::Titlecase, 0) => transformed.extend(char.to_uppercase()),
+            (WordTransform::Titlecase, _) => transformed.push(char),
+            (WordTransform::Uppercase, _) => transformed.extend(char.to_uppercase()),
+            (WordTransform::InversedTitlecase, 0) => transformed.extend(char.to_lowercase()),
+            (WordTransform::InversedTitlecase, _) => transformed.extend(char.to_uppercase()),
+            // lowercase by default
+            _ => transformed.extend(char.to_lowercase()),
+        }
     }
+
+    transformed
 }
@@ -1,5 +1,7 @@
 use super::*;
-use std::collections::HashSet;
+use rand::thread_rng;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
 
 #[test]
 fn test_default_settings() {
@@ -73,27 +75,33 @@ fn test_with_words_count() {
 
 #[test]
 fn test_with_word_lengths() {
-    let _err: Result<Settings, String> = Err(MIN_WORD_LENGTH_ERR.to_string());
-
     // invalid lengths
-    assert!(matches!(
-        Settings::default().with_word_lengths(
-            Some(Settings::MIN_WORD_LENGTH - 1),
-            Some(Settings::MAX_WORD_LENGTH + 1)
-        ),
-        _err
-    ));
-
-    let _err: Result<Settings, String> = Err(MAX_WORD_LENGTH_ERR.to_string());
+    assert_eq!(
+        Err("min word length must be 3 or higher".to_string()),
+        Settings::default().with_word_lengths(Some(2), Some(13))
+    );
 
     // max word length has lower priority
-    assert!(matches!(
-        Settings::default().with_word_lengths(
-            Some(Settings::MIN_WORD_LENGTH),
-            Some(Settings::MAX_WORD_LENGTH + 1)
-        ),
-        _err
-    ));
+    assert_eq!(
+        Err("max word length must be 12 or lower".to_string()),
+        Settings::default().with_word_lengths(Some(4), Some(13))
+    );
+
+    // gaps in available lengths
+    let settings = Settings::default()
+        .with_available_word_lengths(&[3, 4, 8])
+        .unwrap();
+    assert_eq!(
+        Err("no words with length from 5 to 7".to_string()),
+        settings.with_word_lengths(Some(5), Some(7))
+    );
+    assert_eq!(
+        (3, 8),
+        settings
+            .with_word_lengths(Some(3), Some(8))
+            .unwrap()
+            .word_lengths
+    );
 
     let settings = Settings::default()
         .with_word_lengths(Some(4), Some(6))
@@ -140,6 +148,57 @@ fn test_with_word_lengths() {
     assert_eq!((7, 8), other_settings.word_lengths);
 }
 
+#[test]
+fn test_with_available_word_lengths() {
+    // bounds of bundled dictionaries by default
+    assert_eq!(
+        bundled_word_lengths(),
+        Settings::default().available_word_lengths()
+    );
+    if cfg!(feature = "all_langs") {
+        assert_eq!(
+            (3..=12).collect::<Vec<u8>>(),
+            Settings::default().available_word_lengths()
+        );
+    }
+
+    let settings = Settings::default()
+        .with_available_word_lengths(&[8, 3, 5, 3, 0])
+        .unwrap();
+    // current word lengths are fitted into new bounds
+    assert_eq!(vec![3, 5, 8], settings.available_word_lengths);
+    assert_eq!((4, 8), settings.word_lengths);
+
+    // other fields remain unchanged
+    assert_eq!(
+        Settings::default(),
+        Settings {
+            word_lengths: Settings::DEFAULT_WORD_LENGTHS,
+            available_word_lengths: Settings::default().available_word_lengths,
+            ..settings.clone()
+        }
+    );
+
+    let settings = Settings::default()
+        .with_available_word_lengths(&[12, 14])
+        .unwrap();
+    assert_eq!((12, 12), settings.word_lengths);
+
+    // no overlapping lengths
+    assert_eq!(
+        Err("no words with length from 4 to 4".to_string()),
+        Settings::default()
+            .with_word_lengths(Some(4), Some(4))
+            .unwrap()
+            .with_available_word_lengths(&[3, 5])
+    );
+
+    assert_eq!(
+        Err("no words available in dictionary".to_string()),
+        Settings::default().with_available_word_lengths(&[])
+    );
+}
+
 #[test]
 fn test_with_separators() {
     let settings = Settings::default().with_separators("abc123");
@@ -314,6 +373,14 @@ fn test_with_padding_strategy() {
         _err
     ));
 
+    assert_eq!(
+        Err("adaptive padding number must be 255 or lower".to_string()),
+        Settings::default().with_padding_strategy(PaddingStrategy::Adaptive(256))
+    );
+    assert!(Settings::default()
+        .with_padding_strategy(PaddingStrategy::Adaptive(255))
+        .is_ok());
+
     let settings = Settings::default()
         .with_padding_symbol_lengths(Some(2), Some(3))
         .with_padding_strategy(PaddingStrategy::Fixed)
@@ -445,6 +512,265 @@ fn test_with_word_transforms_group() {
     }
 }
 
+#[test]
+fn test_with_excluded_words() {
+    let settings = Settings::default().with_excluded_words(&["Foo", " bar ", "", "foo"]);
+    // only excluded_words updated, normalized & de-duplicated
+    assert_eq!(
+        BTreeSet::from(["bar".to_string(), "foo".to_string()]),
+        settings.excluded_words
+    );
+
+    // other fields remain unchanged
+    assert_eq!(
+        Settings::default(),
+        Settings {
+            excluded_words: BTreeSet::new(),
+            ..settings.clone()
+        }
+    );
+
+    // overriding with multiple calls
+    let other_settings = settings.with_excluded_words(&[]);
+    assert!(other_settings.excluded_words.is_empty());
+}
+
+#[test]
+fn test_with_included_words() {
+    let settings = Settings::default().with_included_words(&["Foo", "bar"]);
+    // only included_words updated
+    assert_eq!(
+        BTreeSet::from(["bar".to_string(), "foo".to_string()]),
+        settings.included_words
+    );
+
+    // other fields remain unchanged
+    assert_eq!(
+        Settings::default(),
+        Settings {
+            included_words: BTreeSet::new(),
+            ..settings.clone()
+        }
+    );
+}
+
+#[test]
+fn test_with_readable() {
+    let settings = Settings::default().with_readable(true);
+    assert!(settings.readable());
+
+    // other fields remain unchanged
+    assert_eq!(
+        Settings::default(),
+        Settings {
+            readable: false,
+            ..settings.clone()
+        }
+    );
+    assert_eq!(Settings::default(), settings.with_readable(false));
+}
+
+#[test]
+fn test_with_keyboard() {
+    let settings = Settings::default().with_keyboard(Some(Keyboard::Fr));
+    assert_eq!(Some(Keyboard::Fr), settings.keyboard());
+
+    // other fields remain unchanged
+    assert_eq!(
+        Settings::default(),
+        Settings {
+            keyboard: None,
+            ..settings.clone()
+        }
+    );
+    assert_eq!(Settings::default(), settings.with_keyboard(None));
+}
+
+#[test]
+fn test_with_language_keyboard() {
+    // symbols of presets are restricted to the keyboard
+    let settings = Settings::from_preset(Preset::WindowsNtlmV1);
+    let fr_settings = settings.with_language_keyboard(Keyboard::Fr);
+    assert_eq!(None, fr_settings.keyboard());
+    assert_eq!("-+=.*_|,", fr_settings.separators());
+    assert_eq!("!@$%&*+=:|?", fr_settings.padding_symbols());
+    assert_eq!(settings, settings.with_language_keyboard(Keyboard::Us));
+
+    // given ones are kept as is
+    let settings = settings.with_separators("€^").with_padding_symbols("€");
+    let fr_settings = settings.with_language_keyboard(Keyboard::Fr);
+    assert_eq!("€^", fr_settings.separators());
+    assert_eq!("€", fr_settings.padding_symbols());
+
+    // settings keep their own keyboard
+    let settings = Settings::from_preset(Preset::WindowsNtlmV1).with_keyboard(Some(Keyboard::Ios));
+    assert_eq!(settings, settings.with_language_keyboard(Keyboard::Fr));
+}
+
+#[test]
+fn test_filter_words() {
+    let pool: &[&str] = &["foo", "bar", "baz", "Qux"];
+
+    // no filters
+    let settings = Settings::default();
+    let filtered = settings.filter_words(Cow::from(pool)).unwrap();
+    assert!(matches!(filtered, Cow::Borrowed(_)));
+    assert_eq!(pool, &*filtered);
+
+    // blocklist
+    let settings = Settings::default().with_excluded_words(&["bar", "qux"]);
+    let settings = settings.with_words_count(2).unwrap();
+    assert_eq!(
+        vec!["foo", "baz"],
+        *settings.filter_words(Cow::from(pool)).unwrap()
+    );
+
+    // allowlist
+    let settings = settings
+        .with_excluded_words(&[])
+        .with_included_words(&["qux", "foo", "other"]);
+    assert_eq!(
+        vec!["foo", "Qux"],
+        *settings.filter_words(Cow::from(pool)).unwrap()
+    );
+
+    // both, blocklist wins
+    let settings = settings.with_excluded_words(&["foo"]);
+    assert_eq!(
+        Err("only 1 word(s) left after filtering, 2 required".to_string()),
+        settings.filter_words(Cow::from(pool))
+    );
+}
+
+#[test]
+fn test_filter_readable_words() {
+    let pool: &[&str] = &["corn", "clay", "savvy", "iris", "lime", "milk", "Oslo"];
+    let settings = Settings::default().with_words_count(1).unwrap();
+
+    let table = [
+        (
+            WordTransform::Lowercase as FieldSize,
+            vec!["iris", "lime", "milk", "Oslo"],
+        ),
+        (WordTransform::Uppercase as FieldSize, vec!["Oslo"]),
+        (
+            WordTransform::Titlecase as FieldSize,
+            vec!["lime", "milk", "Oslo"],
+        ),
+        (WordTransform::InversedTitlecase as FieldSize, vec!["Oslo"]),
+        // words with an `i` are kept for other transforms
+        (
+            WordTransform::Lowercase | WordTransform::Titlecase,
+            vec!["iris", "lime", "milk", "Oslo"],
+        ),
+        (
+            WordTransform::Lowercase | WordTransform::Uppercase,
+            vec!["iris", "lime", "milk", "Oslo"],
+        ),
+        (
+            WordTransform::Titlecase | WordTransform::Uppercase,
+            vec!["lime", "milk", "Oslo"],
+        ),
+        (
+            WordTransform::AltercaseLowerFirst as FieldSize,
+            vec!["iris", "lime", "milk", "Oslo"],
+        ),
+    ];
+
+    for (transforms, expected) in table {
+        let settings = settings
+            .with_word_transforms(transforms)
+            .unwrap()
+            .with_readable(true);
+        assert_eq!(
+            expected,
+            *settings.filter_words(Cow::from(pool)).unwrap(),
+            "{}",
+            transforms
+        );
+    }
+
+    let settings = settings
+        .with_word_transforms(WordTransform::AltercaseUpperFirst as FieldSize)
+        .unwrap()
+        .with_readable(true);
+    assert_eq!(
+        Err("no word left without an 'i' to uppercase".to_string()),
+        settings.filter_words(Cow::from(&["iris", "lime", "milk"][..]))
+    );
+}
+
+#[test]
+fn test_rand_readable_words() {
+    let mut rng = thread_rng();
+    let pool: &[&str] = &["kiwi", "fizz", "lime", "fooz", "barz"];
+
+    for transforms in [
+        WordTransform::Lowercase | WordTransform::Uppercase,
+        WordTransform::Titlecase | WordTransform::InversedTitlecase,
+        WordTransform::AltercaseLowerFirst as FieldSize,
+        WordTransform::AltercaseUpperFirst as FieldSize,
+    ] {
+        let settings = Settings::default()
+            .with_words_count(4)
+            .unwrap()
+            .with_word_transforms(transforms)
+            .unwrap()
+            .with_readable(true);
+
+        let mut picked_words: HashSet<String> = HashSet::new();
+        for _ in 0..100 {
+            let words = settings.rand_words(&mut rng, pool);
+            assert_eq!(4, words.len());
+            assert!(words.iter().all(|word| !word.contains('I')), "{:?}", words);
+            picked_words.extend(words.iter().map(|word| word.to_lowercase()));
+        }
+
+        // words with an `i` are still picked, without turning it into an `I`
+        assert!(picked_words.contains("kiwi"), "{}", transforms);
+    }
+}
+
+#[test]
+fn test_usable_pool_size() {
+    let pool: &[&str] = &["fooz", "barz", "fizz", "kiwi"];
+    let settings = Settings::default();
+    assert_eq!(4, settings.usable_pool_size(pool, 4));
+
+    // 6 readable words & transforms pairs out of 8
+    let readable = settings.with_readable(true);
+    assert_eq!(3, readable.usable_pool_size(pool, 4));
+    assert_eq!(6, readable.usable_pool_size(pool, 8));
+
+    let table = [
+        (WordTransform::Lowercase as FieldSize, 4),
+        (WordTransform::Uppercase as FieldSize, 2),
+        (WordTransform::Titlecase as FieldSize, 4),
+        (WordTransform::AltercaseUpperFirst as FieldSize, 2),
+    ];
+
+    for (transforms, expected) in table {
+        let readable = readable.with_word_transforms(transforms).unwrap();
+        assert_eq!(
+            expected,
+            readable.usable_pool_size(pool, 4),
+            "{}",
+            transforms
+        );
+    }
+}
+
+#[test]
+fn test_has_distinct_words() {
+    let pool = ["foo", "foo", "bar", "foo", "baz"];
+
+    assert!(has_distinct_words(&pool, 0));
+    assert!(has_distinct_words(&pool, 2));
+    assert!(has_distinct_words(&pool, 3));
+    assert!(!has_distinct_words(&pool, 4));
+    assert!(!has_distinct_words(&[], 1));
+}
+
 #[test]
 fn test_get_word_lengths() {
     let table = [((4, 6), 4..7), ((5, 5), 5..6), ((6, 10), 6..11)];
@@ -466,14 +792,16 @@ fn test_rand_words() {
         .unwrap();
 
     // empty pool
-    assert!(settings.rand_words(&vec![] as &Vec<&str>).is_empty());
+    assert!(settings
+        .rand_words(&mut thread_rng(), &vec![] as &Vec<&str>)
+        .is_empty());
 
     // not enough pool
-    let words = settings.rand_words(&["foo", "bar"]);
+    let words = settings.rand_words(&mut thread_rng(), &["foo", "bar"]);
     assert_eq!(3, words.len());
 
     // enough pool
-    let words = settings.rand_words(&["foo", "bar", "barz"]);
+    let words = settings.rand_words(&mut thread_rng(), &["foo", "bar", "barz"]);
     assert_eq!(3, words.len());
     assert_eq!(
         HashSet::from([&"FOO".to_string(), &"BAR".to_string(), &"BARZ".to_string()]),
@@ -494,7 +822,7 @@ fn test_rand_prefix() {
         let settings = Settings::default()
             .with_padding_digits(Some(prefix_digits), Some(suffix_digits))
             .with_padding_symbol_lengths(Some(prefix_symbols), Some(suffix_symbols));
-        let (symbols, digits) = settings.rand_prefix();
+        let (symbols, digits) = settings.rand_prefix(&mut thread_rng());
         assert_eq!("", symbols);
         assert_eq!("", digits);
     }
@@ -505,7 +833,7 @@ fn test_rand_prefix() {
                 .with_padding_digits(Some(prefix_digits as u8), Some(2))
                 .with_padding_symbols("#")
                 .with_padding_symbol_lengths(Some(prefix_symbols as u8), Some(3));
-            let (symbols, digits) = settings.rand_prefix();
+            let (symbols, digits) = settings.rand_prefix(&mut thread_rng());
 
             // total length of prefix
             assert_eq!(prefix_symbols, symbols.len());
@@ -533,7 +861,7 @@ fn test_rand_suffix() {
         let settings = Settings::default()
             .with_padding_digits(Some(prefix_digits), Some(suffix_digits))
             .with_padding_symbol_lengths(Some(prefix_symbols), Some(suffix_symbols));
-        let (digits, symbols) = settings.rand_suffix();
+        let (digits, symbols) = settings.rand_suffix(&mut thread_rng());
         assert_eq!("", digits);
         assert_eq!("", symbols);
     }
@@ -544,7 +872,7 @@ fn test_rand_suffix() {
                 .with_padding_digits(Some(2), Some(suffix_digits as u8))
                 .with_padding_symbols("~")
                 .with_padding_symbol_lengths(Some(3), Some(suffix_symbols as u8));
-            let (digits, symbols) = settings.rand_suffix();
+            let (digits, symbols) = settings.rand_suffix(&mut thread_rng());
 
             // total length of suffix
             assert_eq!(suffix_digits, digits.len());
@@ -568,7 +896,7 @@ fn test_adjust_padding() {
         .with_padding_strategy(PaddingStrategy::Fixed)
         .unwrap();
     assert!(matches!(
-        settings.adjust_padding(pass_length),
+        settings.adjust_padding(&mut thread_rng(), pass_length),
         PaddingResult::Unchanged
     ));
 
@@ -577,7 +905,7 @@ fn test_adjust_padding() {
         .with_padding_symbols("@")
         .with_padding_strategy(PaddingStrategy::Adaptive(15))
         .unwrap();
-    match settings.adjust_padding(pass_length) {
+    match settings.adjust_padding(&mut thread_rng(), pass_length) {
         PaddingResult::Pad(padded_symbols) => assert_eq!("@@@", padded_symbols),
         _ => panic!("invalid padding result"),
     }
@@ -587,7 +915,7 @@ fn test_adjust_padding() {
         .with_padding_strategy(PaddingStrategy::Adaptive(10))
         .unwrap();
     assert!(matches!(
-        settings.adjust_padding(pass_length),
+        settings.adjust_padding(&mut thread_rng(), pass_length),
         PaddingResult::TrimTo(10)
     ));
 }
@@ -625,18 +953,116 @@ fn test_calc_entropy() {
     }
 }
 
+#[test]
+fn test_calc_readable_entropy() {
+    let settings = Settings::from_preset(Preset::Wifi);
+    let readable = settings.with_readable(true);
+
+    // `|` & `,` are left out of separators, 0 & 1 out of digits
+    assert_eq!(
+        Entropy {
+            blind_min: 406,
+            blind_max: 406,
+            seen: 113,
+            guess_time: GuessTime::for_entropy(113),
+        },
+        readable.calc_entropy(6631)
+    );
+    assert!(readable.calc_entropy(6631).seen < settings.calc_entropy(6631).seen);
+}
+
+#[test]
+fn test_rand_readable_padding() {
+    let mut rng = thread_rng();
+    let settings = Settings::default()
+        .with_separators("|`'\",-")
+        .with_padding_symbols("|,")
+        .with_padding_digits(Some(20), Some(20))
+        .with_padding_symbol_lengths(Some(2), Some(2))
+        .with_readable(true);
+
+    for _ in 0..10 {
+        assert_eq!("-", settings.rand_separator(&mut rng));
+
+        let (symbols, digits) = settings.rand_prefix(&mut rng);
+        assert_eq!("", symbols);
+        assert_eq!(20, digits.len());
+        assert!(digits.chars().all(|digit| ('2'..='9').contains(&digit)));
+    }
+
+    assert!(settings.to_string().contains("'-' as separator"));
+    assert!(settings
+        .to_string()
+        .contains("and no visually ambiguous characters & words"));
+}
+
+#[test]
+fn test_calc_keyboard_entropy() {
+    let settings = Settings::from_preset(Preset::Wifi);
+    let ios = settings.with_keyboard(Some(Keyboard::Ios));
+
+    // `-.,` separators & `!@$:?` symbols are left, out of 15 symbols on the first page
+    assert_eq!(
+        Entropy {
+            blind_min: 395,
+            blind_max: 395,
+            seen: 113,
+            guess_time: GuessTime::for_entropy(113),
+        },
+        ios.calc_entropy(6631)
+    );
+
+    // all symbols are typable on US keyboards
+    assert_eq!(
+        settings.calc_entropy(6631),
+        settings
+            .with_keyboard(Some(Keyboard::Us))
+            .calc_entropy(6631)
+    );
+}
+
+#[test]
+fn test_rand_keyboard_padding() {
+    let mut rng = thread_rng();
+    let settings = Settings::default()
+        .with_separators("^~`-")
+        .with_padding_symbols("~^€!")
+        .with_padding_symbol_lengths(Some(2), Some(2))
+        .with_keyboard(Some(Keyboard::Fr));
+
+    for _ in 0..10 {
+        assert_eq!("-", settings.rand_separator(&mut rng));
+
+        let (symbols, _) = settings.rand_prefix(&mut rng);
+        assert_eq!("!!", symbols);
+    }
+
+    // German keyboards type `~` with AltGr
+    let settings = settings.with_keyboard(Some(Keyboard::De));
+    assert!(settings.to_string().contains("a separator from ⟪~-⟫"));
+    assert!(settings
+        .to_string()
+        .contains("and only symbols typable on de keyboards"));
+
+    // nothing left out
+    let settings = Settings::default().with_keyboard(Some(Keyboard::Us));
+    assert_eq!(Settings::default().to_string(), settings.to_string());
+}
+
 #[test]
 fn test_build_words_list() {
     let settings = Settings::default().with_words_count(3).unwrap();
 
     // empty pool
-    assert!(settings.build_words_list(&vec![] as &Vec<&str>).is_empty());
+    assert!(settings
+        .build_words_list(&mut thread_rng(), &vec![] as &Vec<&str>)
+        .is_empty());
 
     // pool size smaller than words count
     let pool = &["foo", "bar"];
 
     for _ in 0..10 {
-        let words = settings.build_words_list(pool);
+        let words = settings.build_words_list(&mut thread_rng(), pool);
         assert_eq!(3, words.len());
 
         let unique_words: HashSet<String> = words.iter().map(|word| word.to_lowercase()).collect();
@@ -647,12 +1073,23 @@ fn test_build_words_list() {
     let pool = &["foo", "bar", "fooz", "barz"];
 
     for _ in 0..10 {
-        let words = settings.build_words_list(pool);
+        let words = settings.build_words_list(&mut thread_rng(), pool);
         assert_eq!(3, words.len());
 
         let unique_words: HashSet<String> = words.iter().map(|word| word.to_lowercase()).collect();
         assert_eq!(3, unique_words.len());
     }
+
+    // repeated words don't count towards the pool size
+    let pool = &["foo", "foo", "foo", "bar"];
+
+    for _ in 0..10 {
+        let words = settings.build_words_list(&mut thread_rng(), pool);
+        assert_eq!(3, words.len());
+
+        let unique_words: HashSet<String> = words.iter().map(|word| word.to_lowercase()).collect();
+        assert!(unique_words.len() < 3);
+    }
 }
 
 #[test]
@@ -668,7 +1105,7 @@ fn test_build_transforms_list() {
         .with_word_transforms(all_transforms)
         .unwrap();
 
-    let transforms_list = settings.build_transforms_list();
+    let transforms_list = settings.build_transforms_list(&mut thread_rng());
     assert_eq!(3, transforms_list.len());
 
     let table = [
@@ -696,44 +1133,45 @@ fn test_build_transforms_list() {
             .unwrap()
             .with_word_transforms(all_transforms | group_transform)
             .unwrap();
-        let transforms_list = settings.build_transforms_list();
+        let transforms_list = settings.build_transforms_list(&mut thread_rng());
         assert_eq!(expected, transforms_list);
     }
 }
 
 #[test]
 fn test_rand_digits() {
-    assert_eq!("", rand_digits(0));
+    assert_eq!("", rand_digits(&mut thread_rng(), 0));
 
     for count in 1..21 {
         for _ in 0..100 {
-            let digits = rand_digits(count);
+            let digits = rand_digits(&mut thread_rng(), count);
             assert_eq!(count as usize, digits.len());
         }
     }
 
     for count in 21..100 {
         for _ in 0..100 {
-            let digits = rand_digits(count);
+            let digits = rand_digits(&mut thread_rng(), count);
             assert_eq!(20, digits.len());
         }
     }
 }
 
 #[test]
+#[allow(clippy::unnecessary_cast, clippy::iter_nth_zero)]
 fn test_rand_chars() {
-    assert_eq!("".to_string(), rand_chars("", 1));
+    assert_eq!("".to_string(), rand_chars(&mut thread_rng(), "", 1));
 
     // single char randomize
     for _ in 0..10 {
-        let result = rand_chars(Settings::DEFAULT_SYMBOLS, 1);
+        let result = rand_chars(&mut thread_rng(), Settings::DEFAULT_SYMBOLS, 1);
         assert!(Settings::DEFAULT_SYMBOLS.contains(&result));
     }
 
     // multi char randomize
     for _ in 0..10 {
         for count in 2..5 {
-            let result = rand_chars(Settings::DEFAULT_SYMBOLS, count);
+            let result = rand_chars(&mut thread_rng(), Settings::DEFAULT_SYMBOLS, count);
             assert_eq!(count as usize, result.len());
             assert_eq!(
                 result
@@ -748,6 +1186,28 @@ fn test_rand_chars() {
     }
 }
 
+#[test]
+fn test_rand_multibyte_chars() {
+    for _ in 0..20 {
+        let result = rand_chars(&mut thread_rng(), "€£¥", 3);
+        assert_eq!(3, result.chars().count());
+        assert!(["€€€", "£££", "¥¥¥"].contains(&result.as_str()));
+    }
+
+    // symbols are counted by characters, not bytes
+    let settings = Settings::default()
+        .with_separators("€")
+        .with_padding_symbols("£¥");
+    assert!(settings.to_string().contains("'€' as separator"));
+    assert_eq!(
+        Settings::default()
+            .with_separators("-")
+            .with_padding_symbols("!?")
+            .calc_entropy(1000),
+        settings.calc_entropy(1000)
+    );
+}
+
 #[test]
 fn test_transform_word() {
     let table = [
@@ -794,4 +1254,106 @@ fn test_transform_word() {
             assert_eq!(expected, transform_word(word, transform));
         }
     }
+    // multi-byte first characters
+    assert_eq!("Élan", transform_word("élan", WordTransform::Titlecase));
+    assert_eq!(
+        "éLAN",
+        transform_word("Élan", WordTransform::InversedTitlecase)
+    );
+    assert_eq!(
+        "STRASSE",
+        transform_word("straße", WordTransform::Uppercase)
+    );
+}
+
+#[test]
+fn test_to_code() {
+    assert_eq!(
+        "xk1:w4-l4.8-t5-s~2D-d0.0-y-n0.0",
+        Settings::from_preset(Preset::Xkcd).to_code()
+    );
+    assert_eq!(
+        "xk1:w6-l4.8-t5-s~2D~2B~3D.~2A_~7C~7E~2C-d4.4-y~21~40~24~25~5E~26~2A~2B~3D~3A~7C~7E~3F-n0.0-a63",
+        Settings::from_preset(Preset::Wifi).to_code()
+    );
+
+    let settings = Settings::default()
+        .with_separators(" ")
+        .with_excluded_words(&["foo", "Bar"])
+        .with_included_words(&["a.b", "été"]);
+    assert!(settings
+        .to_code()
+        .ends_with("-s~20-d0.2-y~7E~40~24~25~5E~26~2A~2D_~2B~3D~3A~7C~3F~2F.~3B-n0.2-xbar.foo-ia~2Eb.~C3~A9t~C3~A9"));
+}
+
+#[test]
+fn test_from_code() {
+    for preset in Preset::ALL {
+        let settings = Settings::from_preset(preset);
+        assert_eq!(
+            Ok(settings.clone()),
+            Settings::from_code(&settings.to_code())
+        );
+    }
+
+    let settings = Settings::default()
+        .with_separators("-~ ")
+        .with_excluded_words(&["foo", "Bar"])
+        .with_included_words(&["a.b", "été"])
+        .with_readable(true);
+    assert!(settings.to_code().ends_with("-r"));
+    assert_eq!(
+        Ok(settings.clone()),
+        Settings::from_code(&settings.to_code())
+    );
+
+    let settings = settings.with_keyboard(Some(Keyboard::PtBr));
+    assert!(settings.to_code().ends_with("-r-kpt~2Dbr"));
+    assert_eq!(
+        Ok(settings.clone()),
+        Settings::from_code(&settings.to_code())
+    );
+
+    // missing fields keep default values
+    assert_eq!(Ok(Settings::default()), Settings::from_code("xk1:"));
+    assert_eq!(
+        Settings::default().with_words_count(5),
+        Settings::from_code(" xk1:w5 ")
+    );
+
+    let table = [
+        ("w5", "invalid settings code, expect a 'xk1:' prefix"),
+        ("xk2:w5", "unsupported settings code version 'xk2'"),
+        ("xk1:w5-z1", "unknown field 'z' in settings code"),
+        ("xk1:w5-w6", "duplicate field 'w' in settings code"),
+        ("xk1:w5--l4.8", "empty field in settings code"),
+        ("xk1:w-5", "invalid number '' for field 'w'"),
+        ("xk1:w256", "invalid number '256' for field 'w'"),
+        ("xk1:l4", "invalid pair '4' for field 'l'"),
+        ("xk1:t16", "invalid word transforms '16'"),
+        ("xk1:r1", "unexpected value '1' for field 'r'"),
+        ("xk1:kazerty", "unknown keyboard 'azerty'"),
+        ("xk1:kfr-kde", "duplicate field 'k' in settings code"),
+        ("xk1:s~2", "invalid escape sequence in '~2'"),
+        ("xk1:s~+F", "invalid escape sequence in '~+F'"),
+        ("xk1:s~FF", "invalid escape sequence in '~FF'"),
+        // builder validations
+        ("xk1:w0", "only positive integer is allowed for words count"),
+        ("xk1:l2.8", "min word length must be 3 or higher"),
+        ("xk1:t0", "invalid transform"),
+        ("xk1:a0", "invalid adaptive padding number"),
+        (
+            "xk1:a100000000000",
+            "adaptive padding number must be 255 or lower",
+        ),
+    ];
+
+    for (code, expected) in table {
+        assert_eq!(
+            Err(expected.to_string()),
+            Settings::from_code(code),
+            "{}",
+            code
+        );
+    }
 }
@@ -0,0 +1,436 @@
+//! Passwords spelled out, to be read over the phone or by screen readers.
+//!
+//! Letters are grouped into words annotated with their case, digits are spoken one by one
+//! and symbols are named, in any of the supported languages. Words can be spelled letter
+//! by letter with the NATO phonetic alphabet, mixed-case words always are.
+
+#[cfg(test)]
+mod tests;
+
+use crate::compat::*;
+use crate::prelude::Language;
+
+// ICAO spelling, shared by all languages
+const NATO_ALPHABET: [&str; 26] = [
+    "alfa", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliett",
+    "kilo", "lima", "mike", "november", "oscar", "papa", "quebec", "romeo", "sierra", "tango",
+    "uniform", "victor", "whiskey", "x-ray", "yankee", "zulu",
+];
+
+// runs of the same symbol from this length are counted instead of repeated
+const MIN_COUNTED_RUN: usize = 3;
+
+// space & ASCII punctuation, in the order of the `symbols` names of vocabularies
+const SYMBOLS: [char; 33] = [
+    ' ', '!', '"', '#', '$', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', ':', ';', '<',
+    '=', '>', '?', '@', '[', '\\', ']', '^', '_', '`', '{', '|', '}', '~',
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Speller {
+    language: Language,
+    nato: bool,
+}
+
+impl Speller {
+    pub fn new(language: Language) -> Self {
+        Speller {
+            language,
+            nato: false,
+        }
+    }
+
+    /// Spell all words letter by letter with the NATO phonetic alphabet.
+    pub fn with_nato(&self, nato: bool) -> Self {
+        Speller { nato, ..*self }
+    }
+
+    /// Spoken groups of the password in order: words, digits & symbols.
+    pub fn spell(&self, passwd: &str) -> Vec<String> {
+        let vocabulary = vocabulary(self.language);
+        let chars: Vec<char> = passwd.chars().collect();
+
+        let mut groups = vec![];
+        let mut start = 0;
+        while start < chars.len() {
+            let first = chars[start];
+            let len = chars[start..]
+                .iter()
+                .take_while(|&&char| same_group(first, char))
+                .count();
+            let span = &chars[start..start + len];
+
+            if first.is_alphabetic() {
+                groups.push(self.spell_word(span, vocabulary));
+            } else if first.is_ascii_digit() {
+                let digits: Vec<&str> = span
+                    .iter()
+                    .map(|digit| vocabulary.digits[(*digit as u8 - b'0') as usize])
+                    .collect();
+                groups.push(digits.join(" "));
+            } else if len >= MIN_COUNTED_RUN {
+                groups.push(format!(
+                    "{}, {}{}",
+                    symbol_name(first, vocabulary),
+                    len,
+                    vocabulary.times
+                ));
+            } else {
+                groups.extend(span.iter().map(|&symbol| symbol_name(symbol, vocabulary)));
+            }
+
+            start += len;
+        }
+
+        groups
+    }
+
+    fn spell_word(&self, letters: &[char], vocabulary: &Vocabulary) -> String {
+        let case = WordCase::of(letters);
+        let label = match case {
+            WordCase::Lower => vocabulary.lower,
+            WordCase::Upper => vocabulary.upper,
+            WordCase::Capital => vocabulary.capital,
+            WordCase::Mixed => vocabulary.mixed,
+        };
+
+        let word: String = letters.iter().collect();
+        let mut group = format!("{} {}", label, word.to_lowercase());
+
+        if self.nato || case == WordCase::Mixed {
+            let spelled: Vec<String> = letters
+                .iter()
+                .map(|&letter| match case {
+                    WordCase::Mixed if letter.is_uppercase() => {
+                        format!("{} {}", vocabulary.capital_letter, self.letter_name(letter))
+                    }
+                    WordCase::Mixed => {
+                        format!("{} {}", vocabulary.small_letter, self.letter_name(letter))
+                    }
+                    _ => self.letter_name(letter),
+                })
+                .collect();
+
+            group.push_str(": ");
+            group.push_str(&spelled.join(", "));
+        }
+
+        group
+    }
+
+    // letters without NATO code word, e.g. accented ones, are spoken as is
+    fn letter_name(&self, letter: char) -> String {
+        let lowercase = letter.to_ascii_lowercase();
+        if self.nato && lowercase.is_ascii_lowercase() {
+            return NATO_ALPHABET[(lowercase as u8 - b'a') as usize].to_string();
+        }
+
+        letter.to_lowercase().collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WordCase {
+    Lower,
+    Upper,
+    /// Only the first letter in uppercase.
+    Capital,
+    Mixed,
+}
+
+impl WordCase {
+    fn of(letters: &[char]) -> Self {
+        match letters
+            .iter()
+            .filter(|letter| letter.is_uppercase())
+            .count()
+        {
+            0 => WordCase::Lower,
+            count if count == letters.len() => WordCase::Upper,
+            1 if letters[0].is_uppercase() => WordCase::Capital,
+            _ => WordCase::Mixed,
+        }
+    }
+}
+
+fn same_group(first: char, next: char) -> bool {
+    if first.is_alphabetic() {
+        next.is_alphabetic()
+    } else if first.is_ascii_digit() {
+        next.is_ascii_digit()
+    } else {
+        next == first
+    }
+}
+
+// symbols without name are spoken as is
+fn symbol_name(symbol: char, vocabulary: &Vocabulary) -> String {
+    SYMBOLS
+        .iter()
+        .position(|&item| item == symbol)
+        .map(|index| vocabulary.symbols[index].to_string())
+        .unwrap_or_else(|| symbol.to_string())
+}
+
+struct Vocabulary {
+    upper: &'static str,
+    lower: &'static str,
+    capital: &'static str,
+    mixed: &'static str,
+    capital_letter: &'static str,
+    small_letter: &'static str,
+    // appended to the count of repeated symbols
+    times: &'static str,
+    digits: [&'static str; 10],
+    symbols: [&'static str; 33],
+}
+
+fn vocabulary(language: Language) -> &'static Vocabulary {
+    match language {
+        Language::English => &ENGLISH,
+        Language::French => &FRENCH,
+        Language::German => &GERMAN,
+        Language::Portuguese => &PORTUGUESE,
+        Language::Spanish => &SPANISH,
+    }
+}
+
+const ENGLISH: Vocabulary = Vocabulary {
+    upper: "UPPER",
+    lower: "lower",
+    capital: "Capital",
+    mixed: "mixed",
+    capital_letter: "capital",
+    small_letter: "small",
+    times: " times",
+    digits: [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ],
+    symbols: [
+        "space",
+        "exclamation mark",
+        "double quote",
+        "hash",
+        "dollar",
+        "percent",
+        "ampersand",
+        "apostrophe",
+        "left parenthesis",
+        "right parenthesis",
+        "asterisk",
+        "plus",
+        "comma",
+        "hyphen",
+        "period",
+        "slash",
+        "colon",
+        "semicolon",
+        "less than",
+        "equals",
+        "greater than",
+        "question mark",
+        "at sign",
+        "left bracket",
+        "backslash",
+        "right bracket",
+        "caret",
+        "underscore",
+        "backtick",
+        "left brace",
+        "pipe",
+        "right brace",
+        "tilde",
+    ],
+};
+
+const FRENCH: Vocabulary = Vocabulary {
+    upper: "MAJUSCULES",
+    lower: "minuscules",
+    capital: "Initiale majuscule",
+    mixed: "mixte",
+    capital_letter: "majuscule",
+    small_letter: "minuscule",
+    times: " fois",
+    digits: [
+        "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+    ],
+    symbols: [
+        "espace",
+        "point d'exclamation",
+        "guillemet",
+        "dièse",
+        "dollar",
+        "pour cent",
+        "esperluette",
+        "apostrophe",
+        "parenthèse ouvrante",
+        "parenthèse fermante",
+        "astérisque",
+        "plus",
+        "virgule",
+        "tiret",
+        "point",
+        "barre oblique",
+        "deux-points",
+        "point-virgule",
+        "inférieur à",
+        "égal",
+        "supérieur à",
+        "point d'interrogation",
+        "arobase",
+        "crochet ouvrant",
+        "barre oblique inversée",
+        "crochet fermant",
+        "accent circonflexe",
+        "tiret bas",
+        "accent grave",
+        "accolade ouvrante",
+        "barre verticale",
+        "accolade fermante",
+        "tilde",
+    ],
+};
+
+const GERMAN: Vocabulary = Vocabulary {
+    upper: "GROSS",
+    lower: "klein",
+    capital: "Anfang groß",
+    mixed: "gemischt",
+    capital_letter: "groß",
+    small_letter: "klein",
+    times: "-mal",
+    digits: [
+        "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+    ],
+    symbols: [
+        "Leerzeichen",
+        "Ausrufezeichen",
+        "Anführungszeichen",
+        "Raute",
+        "Dollar",
+        "Prozent",
+        "Und-Zeichen",
+        "Apostroph",
+        "Klammer auf",
+        "Klammer zu",
+        "Sternchen",
+        "Plus",
+        "Komma",
+        "Bindestrich",
+        "Punkt",
+        "Schrägstrich",
+        "Doppelpunkt",
+        "Semikolon",
+        "kleiner als",
+        "gleich",
+        "größer als",
+        "Fragezeichen",
+        "At-Zeichen",
+        "eckige Klammer auf",
+        "Backslash",
+        "eckige Klammer zu",
+        "Zirkumflex",
+        "Unterstrich",
+        "Gravis",
+        "geschweifte Klammer auf",
+        "senkrechter Strich",
+        "geschweifte Klammer zu",
+        "Tilde",
+    ],
+};
+
+const PORTUGUESE: Vocabulary = Vocabulary {
+    upper: "MAIÚSCULAS",
+    lower: "minúsculas",
+    capital: "Inicial maiúscula",
+    mixed: "misto",
+    capital_letter: "maiúscula",
+    small_letter: "minúscula",
+    times: " vezes",
+    digits: [
+        "zero", "um", "dois", "três", "quatro", "cinco", "seis", "sete", "oito", "nove",
+    ],
+    symbols: [
+        "espaço",
+        "ponto de exclamação",
+        "aspas",
+        "cerquilha",
+        "cifrão",
+        "por cento",
+        "e comercial",
+        "apóstrofo",
+        "abre parênteses",
+        "fecha parênteses",
+        "asterisco",
+        "mais",
+        "vírgula",
+        "hífen",
+        "ponto",
+        "barra",
+        "dois-pontos",
+        "ponto e vírgula",
+        "menor que",
+        "igual",
+        "maior que",
+        "ponto de interrogação",
+        "arroba",
+        "abre colchete",
+        "barra invertida",
+        "fecha colchete",
+        "acento circunflexo",
+        "sublinhado",
+        "acento grave",
+        "abre chaves",
+        "barra vertical",
+        "fecha chaves",
+        "til",
+    ],
+};
+
+const SPANISH: Vocabulary = Vocabulary {
+    upper: "MAYÚSCULAS",
+    lower: "minúsculas",
+    capital: "Inicial mayúscula",
+    mixed: "mixto",
+    capital_letter: "mayúscula",
+    small_letter: "minúscula",
+    times: " veces",
+    digits: [
+        "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+    ],
+    symbols: [
+        "espacio",
+        "signo de exclamación",
+        "comillas",
+        "almohadilla",
+        "dólar",
+        "por ciento",
+        "et",
+        "apóstrofo",
+        "abre paréntesis",
+        "cierra paréntesis",
+        "asterisco",
+        "más",
+        "coma",
+        "guion",
+        "punto",
+        "barra",
+        "dos puntos",
+        "punto y coma",
+        "menor que",
+        "igual",
+        "mayor que",
+        "signo de interrogación",
+        "arroba",
+        "abre corchete",
+        "barra invertida",
+        "cierra corchete",
+        "acento circunflejo",
+        "guion bajo",
+        "acento grave",
+        "abre llave",
+        "barra vertical",
+        "cierra llave",
+        "virgulilla",
+    ],
+};
@@ -0,0 +1,131 @@
+use super::*;
+
+#[test]
+fn test_spell() {
+    let speller = Speller::new(Language::English);
+
+    assert_eq!(
+        vec![
+            "three one",
+            "tilde",
+            "UPPER correct",
+            "pipe",
+            "lower horse",
+            "pipe",
+            "Capital battery",
+            "zero seven",
+            "at sign, 6 times",
+        ],
+        speller.spell("31~CORRECT|horse|Battery07@@@@@@")
+    );
+
+    assert_eq!(
+        vec![
+            "question mark",
+            "question mark",
+            "mixed staple: small s, capital t, small a, capital p, small l, capital e",
+            "space",
+            "€",
+        ],
+        speller.spell("??sTaPlE €")
+    );
+
+    assert!(speller.spell("").is_empty());
+}
+
+#[test]
+fn test_spell_nato() {
+    let speller = Speller::new(Language::English).with_nato(true);
+
+    assert_eq!(
+        vec![
+            "UPPER jazz: juliett, alfa, zulu, zulu",
+            "hyphen",
+            "mixed ox: small oscar, capital x-ray",
+            "space",
+            "lower élan: é, lima, alfa, november",
+            "four two",
+        ],
+        speller.spell("JAZZ-oX élan42")
+    );
+    assert_eq!(vec!["Capital jazz"], speller.with_nato(false).spell("Jazz"));
+}
+
+#[test]
+fn test_spell_languages() {
+    let passwd = "Straße|CAFÉ.2+";
+    let table = [
+        (
+            Language::English,
+            vec![
+                "Capital straße",
+                "pipe",
+                "UPPER café",
+                "period",
+                "two",
+                "plus",
+            ],
+        ),
+        (
+            Language::French,
+            vec![
+                "Initiale majuscule straße",
+                "barre verticale",
+                "MAJUSCULES café",
+                "point",
+                "deux",
+                "plus",
+            ],
+        ),
+        (
+            Language::German,
+            vec![
+                "Anfang groß straße",
+                "senkrechter Strich",
+                "GROSS café",
+                "Punkt",
+                "zwei",
+                "Plus",
+            ],
+        ),
+        (
+            Language::Portuguese,
+            vec![
+                "Inicial maiúscula straße",
+                "barra vertical",
+                "MAIÚSCULAS café",
+                "ponto",
+                "dois",
+                "mais",
+            ],
+        ),
+        (
+            Language::Spanish,
+            vec![
+                "Inicial mayúscula straße",
+                "barra vertical",
+                "MAYÚSCULAS café",
+                "punto",
+                "dos",
+                "más",
+            ],
+        ),
+    ];
+
+    for (language, expected) in table {
+        assert_eq!(
+            expected,
+            Speller::new(language).spell(passwd),
+            "{:?}",
+            language
+        );
+    }
+
+    // every symbol of the default settings is named
+    for language in Language::ALL {
+        let vocabulary = vocabulary(language);
+        for symbol in "~@$%^&*-_+=:|?/.;!".chars() {
+            assert_ne!(symbol.to_string(), symbol_name(symbol, vocabulary));
+        }
+    }
+}
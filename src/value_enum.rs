@@ -0,0 +1,118 @@
+//! [`ValueEnum`] impls for the library enums used as CLI arguments.
+//!
+//! They live in the library as the orphan rule forbids implementing a foreign
+//! trait for foreign types in the binary crate.
+
+use crate::bit_flags::WordTransform;
+use crate::hash::HashScheme;
+use crate::prelude::{Keyboard, Language, Preset};
+use crate::qr::WifiSecurity;
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+
+impl ValueEnum for Preset {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let help = match self {
+            Self::Default => "Some sensible default values",
+            Self::AppleID => "Apple ID passwords",
+            Self::WindowsNtlmV1 => "Windows NTLM v1",
+            Self::SecurityQuestions => "Security questions",
+            Self::Web16 => "Maxium 16 characters for older websites",
+            Self::Web32 => "Maximum 32 characters for modern websites",
+            Self::Wifi => "Fixed 63 characters for Wifi WPA2 keys",
+            Self::Xkcd => "As described in the original XKCD comic",
+        };
+
+        Some(PossibleValue::new(self.name()).help(help))
+    }
+}
+
+impl ValueEnum for Keyboard {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let help = match self {
+            Self::Us => "US QWERTY, all symbols",
+            Self::Uk => "UK QWERTY, all symbols",
+            Self::De => "German QWERTZ, without the ^ & ` dead keys",
+            Self::Fr => "French AZERTY, without the ^, ` & ~ dead keys",
+            Self::Es => "Spanish QWERTY, without the ^, ` & ~ dead keys",
+            Self::PtBr => "Brazilian ABNT2, without the ^, ` & ~ dead keys",
+            Self::Ios => "iOS, only symbols of the first page",
+            Self::Android => "Android, only symbols of the first page",
+        };
+
+        Some(PossibleValue::new(self.name()).help(help))
+    }
+}
+
+impl ValueEnum for HashScheme {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let help = match self {
+            Self::Sha512Crypt => "$6$ crypt(3) hash for /etc/shadow & chpasswd -e",
+            Self::Bcrypt => "$2b$ bcrypt hash, cost 12",
+            Self::Argon2id => "Argon2id PHC string, with the OWASP recommended parameters",
+            Self::Htpasswd => "$2y$ bcrypt hash for Apache htpasswd files, cost 10",
+        };
+
+        Some(PossibleValue::new(self.name()).help(help))
+    }
+}
+
+impl ValueEnum for WifiSecurity {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let help = match self {
+            Self::Wpa => "WPA, WPA2 & WPA3 personal",
+            Self::Wep => "Legacy WEP",
+            Self::NoPass => "Open network, the password is left out of the QR code",
+        };
+
+        Some(PossibleValue::new(self.name()).help(help))
+    }
+}
+
+impl ValueEnum for WordTransform {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()).help(self.to_string()))
+    }
+}
+
+impl ValueEnum for Language {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::English,
+            Self::French,
+            Self::German,
+            Self::Portuguese,
+            Self::Spanish,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Self::English => PossibleValue::new("en").help("English"),
+            Self::French => PossibleValue::new("fr").help("French"),
+            Self::German => PossibleValue::new("de").help("German"),
+            Self::Portuguese => PossibleValue::new("pt").help("Portuguese"),
+            Self::Spanish => PossibleValue::new("es").help("Spanish"),
+        })
+    }
+}
@@ -0,0 +1,742 @@
+//! Settings from plain JS objects, or from the same `xkpasswd.toml` config files as the CLI.
+//!
+//! Values are validated as a whole, so that all invalid fields are reported at once
+//! before builder errors, which are reported one at a time.
+
+use crate::bit_flags::{FieldSize, WordTransform};
+use crate::prelude::*;
+use crate::settings::Settings;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const SETTINGS_OBJECT: &str = r#"
+export type PresetName =
+  | "default" | "apple-id" | "ntlm" | "secq" | "web16" | "web32" | "wifi" | "xkcd";
+
+export type TransformName =
+  | "lowercase" | "titlecase" | "uppercase" | "inversed-titlecase"
+  | "altercase-lower-first" | "altercase-upper-first";
+
+export type Padding = { type: "fixed" } | { type: "adaptive"; length: number };
+
+export type KeyboardName =
+  | "us" | "uk" | "de" | "fr" | "es" | "pt-br" | "ios" | "android";
+
+export interface SettingsObject {
+  preset?: PresetName;
+  wordsCount?: number;
+  wordLengths?: [number | null, number | null];
+  transforms?: TransformName[];
+  separators?: string;
+  paddingDigits?: [number | null, number | null];
+  paddingSymbols?: string;
+  paddingSymbolLengths?: [number | null, number | null];
+  padding?: Padding;
+  excludedWords?: string[];
+  includedWords?: string[];
+  readable?: boolean;
+  keyboard?: KeyboardName;
+}
+
+export interface SettingsFieldError {
+  /** Invalid field, `null` for malformed inputs. */
+  field: string | null;
+  message: string;
+}
+
+/** Thrown by `Settings` constructors & builder methods on invalid values. */
+export interface SettingsError extends Error {
+  name: "SettingsError";
+  errors: SettingsFieldError[];
+}
+"#;
+
+const OBJECT_FIELDS: [&str; 13] = [
+    "preset",
+    "wordsCount",
+    "wordLengths",
+    "transforms",
+    "separators",
+    "paddingDigits",
+    "paddingSymbols",
+    "paddingSymbolLengths",
+    "padding",
+    "excludedWords",
+    "includedWords",
+    "readable",
+    "keyboard",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        FieldError {
+            field: Some(field.to_string()),
+            message: message.into(),
+        }
+    }
+
+    pub fn malformed(message: impl Into<String>) -> Self {
+        FieldError {
+            field: None,
+            message: message.into(),
+        }
+    }
+}
+
+// `(first, second)` with `None` to keep the current value
+type Pair = (Option<u8>, Option<u8>);
+
+// input fields to report builder errors at
+#[derive(Debug, Default, PartialEq, Eq)]
+struct BuilderFields {
+    words_count: &'static str,
+    word_lengths: &'static str,
+    word_transforms: &'static str,
+    padding_strategy: &'static str,
+}
+
+/// All fields are optional, overriding the preset if any, or default settings.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SettingsConfig {
+    preset: Option<Preset>,
+    words_count: Option<u8>,
+    word_lengths: Pair,
+    word_transforms: Option<FieldSize>,
+    separators: Option<String>,
+    padding_digits: Pair,
+    padding_symbols: Option<String>,
+    padding_symbol_lengths: Pair,
+    padding_strategy: Option<PaddingStrategy>,
+    excluded_words: Option<Vec<String>>,
+    included_words: Option<Vec<String>>,
+    readable: Option<bool>,
+    keyboard: Option<Keyboard>,
+    fields: BuilderFields,
+}
+
+impl SettingsConfig {
+    /// Same keys as CLI config files, ignoring CLI-only ones such as `lang` or `exclude_file`.
+    #[cfg(feature = "wasm_toml")]
+    pub fn from_toml(data: &str) -> Result<Self, Vec<FieldError>> {
+        let table: toml::Table = data
+            .parse()
+            .map_err(|err: toml::de::Error| vec![FieldError::malformed(err.message())])?;
+
+        let mut errors: Vec<FieldError> = vec![];
+
+        let word_min = collect_error(&mut errors, toml_u8(&table, "word_min"));
+        let padding = collect_error(&mut errors, toml_string(&table, "padding"));
+        let adaptive_length = collect_error(
+            &mut errors,
+            toml_usize(
+                &table,
+                "adaptive_length",
+                PaddingStrategy::MAX_ADAPTIVE_LENGTH,
+            ),
+        );
+
+        let config = SettingsConfig {
+            preset: collect_error(
+                &mut errors,
+                toml_string(&table, "preset").and_then(|name| parse_preset("preset", name)),
+            ),
+            words_count: collect_error(&mut errors, toml_u8(&table, "words_count")),
+            word_lengths: (
+                word_min,
+                collect_error(&mut errors, toml_u8(&table, "word_max")),
+            ),
+            word_transforms: collect_error(
+                &mut errors,
+                toml_str_array(&table, "transforms")
+                    .and_then(|names| parse_transforms("transforms", names)),
+            ),
+            separators: collect_error(&mut errors, toml_string(&table, "separators")),
+            padding_digits: (
+                collect_error(&mut errors, toml_u8(&table, "digits_before")),
+                collect_error(&mut errors, toml_u8(&table, "digits_after")),
+            ),
+            padding_symbols: collect_error(&mut errors, toml_string(&table, "symbols")),
+            padding_symbol_lengths: (
+                collect_error(&mut errors, toml_u8(&table, "symbols_before")),
+                collect_error(&mut errors, toml_u8(&table, "symbols_after")),
+            ),
+            padding_strategy: collect_error(
+                &mut errors,
+                parse_padding("padding", padding, adaptive_length),
+            ),
+            excluded_words: collect_error(&mut errors, toml_str_array(&table, "exclude_words")),
+            included_words: collect_error(&mut errors, toml_str_array(&table, "include_words")),
+            readable: collect_error(&mut errors, toml_bool(&table, "readable")),
+            keyboard: collect_error(
+                &mut errors,
+                toml_string(&table, "keyboard").and_then(|name| parse_keyboard("keyboard", name)),
+            ),
+            fields: BuilderFields {
+                words_count: "words_count",
+                word_lengths: if word_min.is_some() {
+                    "word_min"
+                } else {
+                    "word_max"
+                },
+                word_transforms: "transforms",
+                padding_strategy: "adaptive_length",
+            },
+        };
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Fields of `SettingsObject`, rejecting unknown ones.
+    pub fn from_object(value: &JsValue) -> Result<Self, Vec<FieldError>> {
+        if !value.is_object() || Array::is_array(value) {
+            return Err(vec![FieldError::malformed("expect a settings object")]);
+        }
+
+        let object: &Object = value.unchecked_ref();
+        let mut errors: Vec<FieldError> = Object::keys(object)
+            .iter()
+            .filter_map(|key| key.as_string())
+            .filter(|key| !OBJECT_FIELDS.contains(&key.as_str()))
+            .map(|key| FieldError::new(&key, "unknown field"))
+            .collect();
+
+        let config = SettingsConfig {
+            preset: collect_error(
+                &mut errors,
+                js_string(object, "preset").and_then(|name| parse_preset("preset", name)),
+            ),
+            words_count: collect_error(&mut errors, js_u8(object, "wordsCount")),
+            word_lengths: collect_error(&mut errors, js_pair(object, "wordLengths"))
+                .unwrap_or_default(),
+            word_transforms: collect_error(
+                &mut errors,
+                js_str_array(object, "transforms")
+                    .and_then(|names| parse_transforms("transforms", names)),
+            ),
+            separators: collect_error(&mut errors, js_string(object, "separators")),
+            padding_digits: collect_error(&mut errors, js_pair(object, "paddingDigits"))
+                .unwrap_or_default(),
+            padding_symbols: collect_error(&mut errors, js_string(object, "paddingSymbols")),
+            padding_symbol_lengths: collect_error(
+                &mut errors,
+                js_pair(object, "paddingSymbolLengths"),
+            )
+            .unwrap_or_default(),
+            padding_strategy: collect_error(&mut errors, js_padding(object, "padding")),
+            excluded_words: collect_error(&mut errors, js_str_array(object, "excludedWords")),
+            included_words: collect_error(&mut errors, js_str_array(object, "includedWords")),
+            readable: collect_error(&mut errors, js_bool(object, "readable")),
+            keyboard: collect_error(
+                &mut errors,
+                js_string(object, "keyboard").and_then(|name| parse_keyboard("keyboard", name)),
+            ),
+            fields: BuilderFields {
+                words_count: "wordsCount",
+                word_lengths: "wordLengths",
+                word_transforms: "transforms",
+                padding_strategy: "padding",
+            },
+        };
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Settings built in the same order as the CLI does.
+    pub fn build(&self) -> Result<Settings, FieldError> {
+        let fields = &self.fields;
+        let mut settings = match self.preset {
+            Some(preset) => Settings::from_preset(preset),
+            None => Settings::default(),
+        };
+
+        if let (None, None) = self.word_lengths {
+        } else {
+            let (min, max) = self.word_lengths;
+            settings = settings
+                .with_word_lengths(min, max)
+                .map_err(|err| FieldError::new(fields.word_lengths, err))?;
+        }
+
+        let (prefix, suffix) = self.padding_digits;
+        settings = settings.with_padding_digits(prefix, suffix);
+        let (prefix, suffix) = self.padding_symbol_lengths;
+        settings = settings.with_padding_symbol_lengths(prefix, suffix);
+
+        if let Some(words_count) = self.words_count {
+            settings = settings
+                .with_words_count(words_count)
+                .map_err(|err| FieldError::new(fields.words_count, err))?;
+        }
+
+        if let Some(transforms) = self.word_transforms {
+            settings = settings
+                .with_word_transforms(transforms)
+                .map_err(|err| FieldError::new(fields.word_transforms, err))?;
+        }
+
+        if let Some(separators) = &self.separators {
+            settings = settings.with_separators(separators);
+        }
+
+        if let Some(symbols) = &self.padding_symbols {
+            settings = settings.with_padding_symbols(symbols);
+        }
+
+        if let Some(strategy) = &self.padding_strategy {
+            settings = settings
+                .with_padding_strategy(strategy.clone())
+                .map_err(|err| FieldError::new(fields.padding_strategy, err))?;
+        }
+
+        if let Some(words) = &self.excluded_words {
+            let words: Vec<&str> = words.iter().map(String::as_str).collect();
+            settings = settings.with_excluded_words(&words);
+        }
+
+        if let Some(words) = &self.included_words {
+            let words: Vec<&str> = words.iter().map(String::as_str).collect();
+            settings = settings.with_included_words(&words);
+        }
+
+        if let Some(readable) = self.readable {
+            settings = settings.with_readable(readable);
+        }
+
+        if self.keyboard.is_some() {
+            settings = settings.with_keyboard(self.keyboard);
+        }
+
+        Ok(settings)
+    }
+}
+
+/// `SettingsError` to be thrown in JS.
+pub fn settings_error(errors: &[FieldError]) -> JsValue {
+    let message = errors
+        .iter()
+        .map(|error| match &error.field {
+            Some(field) => format!("{}: {}", field, error.message),
+            None => error.message.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join("; ");
+
+    let error = js_sys::Error::new(&format!("Invalid settings: {}", message));
+    error.set_name("SettingsError");
+
+    let field_errors: Array = errors
+        .iter()
+        .map(|error| {
+            let field_error = Object::new();
+            let field = error.field.as_deref().map_or(JsValue::NULL, JsValue::from);
+            let _ = Reflect::set(&field_error, &"field".into(), &field);
+            let _ = Reflect::set(
+                &field_error,
+                &"message".into(),
+                &JsValue::from(&error.message),
+            );
+            JsValue::from(field_error)
+        })
+        .collect();
+    let _ = Reflect::set(&error, &"errors".into(), &field_errors);
+
+    error.into()
+}
+
+fn collect_error<T>(
+    errors: &mut Vec<FieldError>,
+    result: Result<Option<T>, FieldError>,
+) -> Option<T> {
+    result.unwrap_or_else(|err| {
+        errors.push(err);
+        None
+    })
+}
+
+fn parse_preset(field: &str, name: Option<String>) -> Result<Option<Preset>, FieldError> {
+    name.map(|name| {
+        Preset::from_name(&name)
+            .ok_or_else(|| FieldError::new(field, format!("unknown preset '{}'", name)))
+    })
+    .transpose()
+}
+
+fn parse_keyboard(field: &str, name: Option<String>) -> Result<Option<Keyboard>, FieldError> {
+    name.map(|name| {
+        Keyboard::from_name(&name)
+            .ok_or_else(|| FieldError::new(field, format!("unknown keyboard '{}'", name)))
+    })
+    .transpose()
+}
+
+fn parse_transforms(
+    field: &str,
+    names: Option<Vec<String>>,
+) -> Result<Option<FieldSize>, FieldError> {
+    names
+        .map(|names| {
+            names.iter().try_fold(0 as FieldSize, |transforms, name| {
+                WordTransform::from_name(name)
+                    .map(|transform| transforms | transform)
+                    .ok_or_else(|| FieldError::new(field, format!("unknown transform '{}'", name)))
+            })
+        })
+        .transpose()
+}
+
+fn parse_padding(
+    field: &str,
+    padding: Option<String>,
+    adaptive_length: Option<usize>,
+) -> Result<Option<PaddingStrategy>, FieldError> {
+    match (padding.as_deref(), adaptive_length) {
+        (None, _) => Ok(None),
+        (Some("fixed"), _) => Ok(Some(PaddingStrategy::Fixed)),
+        (Some("adaptive"), Some(length)) => Ok(Some(PaddingStrategy::Adaptive(length))),
+        (Some("adaptive"), None) => Err(FieldError::new(
+            field,
+            "adaptive length is required for adaptive padding strategy",
+        )),
+        (Some(padding), _) => Err(FieldError::new(
+            field,
+            format!(
+                "unknown padding '{}', expect 'fixed' or 'adaptive'",
+                padding
+            ),
+        )),
+    }
+}
+
+#[cfg(feature = "wasm_toml")]
+fn toml_u8(table: &toml::Table, field: &str) -> Result<Option<u8>, FieldError> {
+    Ok(toml_usize(table, field, u8::MAX as usize)?.map(|value| value as u8))
+}
+
+#[cfg(feature = "wasm_toml")]
+fn toml_usize(table: &toml::Table, field: &str, max: usize) -> Result<Option<usize>, FieldError> {
+    table
+        .get(field)
+        .map(|value| {
+            value
+                .as_integer()
+                .and_then(|value| usize::try_from(value).ok())
+                .filter(|&value| value <= max)
+                .ok_or_else(|| number_error(field, max))
+        })
+        .transpose()
+}
+
+#[cfg(feature = "wasm_toml")]
+fn toml_string(table: &toml::Table, field: &str) -> Result<Option<String>, FieldError> {
+    table
+        .get(field)
+        .map(|value| {
+            value
+                .as_str()
+                .map(String::from)
+                .ok_or_else(|| FieldError::new(field, "expect a string"))
+        })
+        .transpose()
+}
+
+#[cfg(feature = "wasm_toml")]
+fn toml_bool(table: &toml::Table, field: &str) -> Result<Option<bool>, FieldError> {
+    table
+        .get(field)
+        .map(|value| {
+            value
+                .as_bool()
+                .ok_or_else(|| FieldError::new(field, "expect a boolean"))
+        })
+        .transpose()
+}
+
+#[cfg(feature = "wasm_toml")]
+fn toml_str_array(table: &toml::Table, field: &str) -> Result<Option<Vec<String>>, FieldError> {
+    table
+        .get(field)
+        .map(|value| {
+            value
+                .as_array()
+                .and_then(|values| {
+                    values
+                        .iter()
+                        .map(|value| value.as_str().map(String::from))
+                        .collect()
+                })
+                .ok_or_else(|| FieldError::new(field, "expect an array of strings"))
+        })
+        .transpose()
+}
+
+fn js_get(object: &Object, field: &str) -> Option<JsValue> {
+    Reflect::get(object, &field.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+fn js_u8(object: &Object, field: &str) -> Result<Option<u8>, FieldError> {
+    js_get(object, field)
+        .map(|value| as_u8(&value).ok_or_else(|| number_error(field, u8::MAX as usize)))
+        .transpose()
+}
+
+fn js_string(object: &Object, field: &str) -> Result<Option<String>, FieldError> {
+    js_get(object, field)
+        .map(|value| {
+            value
+                .as_string()
+                .ok_or_else(|| FieldError::new(field, "expect a string"))
+        })
+        .transpose()
+}
+
+fn js_bool(object: &Object, field: &str) -> Result<Option<bool>, FieldError> {
+    js_get(object, field)
+        .map(|value| {
+            value
+                .as_bool()
+                .ok_or_else(|| FieldError::new(field, "expect a boolean"))
+        })
+        .transpose()
+}
+
+fn js_str_array(object: &Object, field: &str) -> Result<Option<Vec<String>>, FieldError> {
+    js_get(object, field)
+        .map(|value| {
+            Some(value)
+                .filter(Array::is_array)
+                .and_then(|values| {
+                    Array::from(&values)
+                        .iter()
+                        .map(|value| value.as_string())
+                        .collect()
+                })
+                .ok_or_else(|| FieldError::new(field, "expect an array of strings"))
+        })
+        .transpose()
+}
+
+fn js_pair(object: &Object, field: &str) -> Result<Option<Pair>, FieldError> {
+    let pair_error =
+        || FieldError::new(field, "expect an array of 2 integers from 0 to 255 or null");
+
+    js_get(object, field)
+        .map(|value| {
+            if !Array::is_array(&value) {
+                return Err(pair_error());
+            }
+
+            let values = Array::from(&value);
+            if values.length() != 2 {
+                return Err(pair_error());
+            }
+
+            let item = |index: u32| {
+                let value = values.get(index);
+                if value.is_null() || value.is_undefined() {
+                    Ok(None)
+                } else {
+                    as_u8(&value).map(Some).ok_or_else(pair_error)
+                }
+            };
+
+            Ok((item(0)?, item(1)?))
+        })
+        .transpose()
+}
+
+fn js_padding(object: &Object, field: &str) -> Result<Option<PaddingStrategy>, FieldError> {
+    let Some(padding) = js_get(object, field) else {
+        return Ok(None);
+    };
+
+    if !padding.is_object() {
+        return Err(FieldError::new(
+            field,
+            "expect { type: \"fixed\" } or { type: \"adaptive\", length: number }",
+        ));
+    }
+
+    let padding: &Object = padding.unchecked_ref();
+    let padding_type =
+        js_string(padding, "type").map_err(|err| FieldError::new(field, err.message))?;
+    let length = js_get(padding, "length")
+        .map(|length| {
+            length
+                .as_f64()
+                .filter(|length| {
+                    length.fract() == 0.0
+                        && (0.0..=PaddingStrategy::MAX_ADAPTIVE_LENGTH as f64).contains(length)
+                })
+                .map(|length| length as usize)
+                .ok_or_else(|| number_error(field, PaddingStrategy::MAX_ADAPTIVE_LENGTH))
+        })
+        .transpose()?;
+
+    parse_padding(field, padding_type, length)
+}
+
+fn as_u8(value: &JsValue) -> Option<u8> {
+    value
+        .as_f64()
+        .filter(|value| value.fract() == 0.0 && (0.0..=u8::MAX as f64).contains(value))
+        .map(|value| value as u8)
+}
+
+fn number_error(field: &str, max: usize) -> FieldError {
+    FieldError::new(field, format!("expect an integer from 0 to {}", max))
+}
+
+// config tests are written as TOML
+#[cfg(all(test, feature = "wasm_toml"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let config = SettingsConfig::from_toml(
+            r#"
+preset = "xkcd"
+words_count = 5
+word_min = 5
+transforms = ["lowercase", "Titlecase"]
+digits_after = 2
+symbols = "!?"
+symbols_before = 1
+padding = "adaptive"
+adaptive_length = 32
+exclude_words = ["foo", "bar"]
+readable = true
+keyboard = "android"
+lang = ["en", "de"]
+exclude_file = "excluded.txt"
+"#,
+        )
+        .unwrap();
+
+        let expected = Settings::from_preset(Preset::Xkcd)
+            .with_word_lengths(Some(5), None)
+            .unwrap()
+            .with_padding_digits(None, Some(2))
+            .with_padding_symbol_lengths(Some(1), None)
+            .with_words_count(5)
+            .unwrap()
+            .with_word_transforms(WordTransform::Lowercase | WordTransform::Titlecase)
+            .unwrap()
+            .with_padding_symbols("!?")
+            .with_padding_strategy(PaddingStrategy::Adaptive(32))
+            .unwrap()
+            .with_excluded_words(&["foo", "bar"])
+            .with_readable(true)
+            .with_keyboard(Some(Keyboard::Android));
+        assert_eq!(Ok(expected), config.build());
+
+        assert_eq!(
+            Ok(Settings::default()),
+            SettingsConfig::from_toml("").unwrap().build()
+        );
+    }
+
+    #[test]
+    fn test_from_toml_errors() {
+        let errors = SettingsConfig::from_toml(
+            r#"
+preset = "foo"
+words_count = 256
+transforms = ["lowercase", "camelcase"]
+separators = 1
+padding = "adaptive"
+include_words = "foo"
+readable = "yes"
+keyboard = "azerty"
+"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                FieldError::new("preset", "unknown preset 'foo'"),
+                FieldError::new("words_count", "expect an integer from 0 to 255"),
+                FieldError::new("transforms", "unknown transform 'camelcase'"),
+                FieldError::new("separators", "expect a string"),
+                FieldError::new(
+                    "padding",
+                    "adaptive length is required for adaptive padding strategy"
+                ),
+                FieldError::new("include_words", "expect an array of strings"),
+                FieldError::new("readable", "expect a boolean"),
+                FieldError::new("keyboard", "unknown keyboard 'azerty'"),
+            ],
+            errors
+        );
+
+        // passwords are allocated to the adaptive length
+        let errors =
+            SettingsConfig::from_toml("padding = \"adaptive\"\nadaptive_length = 100000000000")
+                .unwrap_err();
+        assert_eq!(
+            vec![
+                FieldError::new("adaptive_length", "expect an integer from 0 to 255"),
+                FieldError::new(
+                    "padding",
+                    "adaptive length is required for adaptive padding strategy"
+                ),
+            ],
+            errors
+        );
+
+        let errors = SettingsConfig::from_toml("words_count = ").unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!(None, errors[0].field);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let table = [
+            (
+                "words_count = 0",
+                FieldError::new(
+                    "words_count",
+                    "only positive integer is allowed for words count",
+                ),
+            ),
+            (
+                "word_max = 20",
+                FieldError::new("word_max", "max word length must be 12 or lower"),
+            ),
+            (
+                "word_min = 2\nword_max = 6",
+                FieldError::new("word_min", "min word length must be 3 or higher"),
+            ),
+            (
+                "transforms = []",
+                FieldError::new("transforms", "invalid transform"),
+            ),
+            (
+                "padding = \"adaptive\"\nadaptive_length = 0",
+                FieldError::new("adaptive_length", "invalid adaptive padding number"),
+            ),
+        ];
+
+        for (data, expected) in table {
+            let config = SettingsConfig::from_toml(data).unwrap();
+            assert_eq!(Err(expected), config.build(), "{}", data);
+        }
+    }
+}
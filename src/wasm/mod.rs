@@ -1,12 +1,36 @@
+mod config;
 mod utils;
 
 use crate::prelude::*;
+#[cfg(feature = "wasm_qr")]
+use crate::qr::{WifiQr, WifiSecurity};
+use crate::secret::Passwd;
 use crate::settings::*;
+use crate::spell::Speller;
+use js_sys::{Array, Object, Reflect};
 use wasm_bindgen::prelude::*;
 
+use config::*;
 use utils::*;
 
-const DEFAULT_SETTING_BUILDER_ERR: &str = "Invalid settings";
+#[wasm_bindgen(typescript_custom_section)]
+const SETTINGS_JSON: &str = r#"
+export interface SettingsJSON {
+  wordsCount: number;
+  wordLengths: [number, number];
+  wordTransforms: number;
+  separators: string;
+  paddingDigits: [number, number];
+  paddingSymbols: string;
+  paddingSymbolLengths: [number, number];
+  adaptivePadding: number | null;
+  excludedWords: string[];
+  includedWords: string[];
+  readable: boolean;
+  keyboard: KeyboardName | null;
+  availableWordLengths: number[];
+}
+"#;
 
 #[wasm_bindgen(js_name = "Settings")]
 #[derive(Debug, Default)]
@@ -22,21 +46,34 @@ impl WasmSettings {
     }
 
     #[wasm_bindgen(js_name = "withWordsCount")]
-    pub fn with_words_count(&self, words_count: u8) -> WasmSettings {
+    pub fn with_words_count(&self, words_count: u8) -> Result<WasmSettings, JsValue> {
         let settings = self
             .settings
             .with_words_count(words_count)
-            .expect(DEFAULT_SETTING_BUILDER_ERR);
-        WasmSettings { settings }
+            .map_err(|err| settings_error(&[FieldError::new("wordsCount", err)]))?;
+        Ok(WasmSettings { settings })
     }
 
     #[wasm_bindgen(js_name = "withWordLengths")]
-    pub fn with_word_lengths(&self, min: Option<u8>, max: Option<u8>) -> WasmSettings {
+    pub fn with_word_lengths(
+        &self,
+        min: Option<u8>,
+        max: Option<u8>,
+    ) -> Result<WasmSettings, JsValue> {
         let settings = self
             .settings
             .with_word_lengths(min, max)
-            .expect(DEFAULT_SETTING_BUILDER_ERR);
-        WasmSettings { settings }
+            .map_err(|err| settings_error(&[FieldError::new("wordLengths", err)]))?;
+        Ok(WasmSettings { settings })
+    }
+
+    #[wasm_bindgen(js_name = "withAvailableWordLengths")]
+    pub fn with_available_word_lengths(&self, lengths: Vec<u8>) -> Result<WasmSettings, JsValue> {
+        let settings = self
+            .settings
+            .with_available_word_lengths(&lengths)
+            .map_err(|err| settings_error(&[FieldError::new("availableWordLengths", err)]))?;
+        Ok(WasmSettings { settings })
     }
 
     #[wasm_bindgen(js_name = "withSeparators")]
@@ -68,44 +105,240 @@ impl WasmSettings {
     }
 
     #[wasm_bindgen(js_name = "withFixedPadding")]
-    pub fn with_fixed_padding(&self) -> WasmSettings {
+    pub fn with_fixed_padding(&self) -> Result<WasmSettings, JsValue> {
         let settings = self
             .settings
             .with_padding_strategy(PaddingStrategy::Fixed)
-            .expect(DEFAULT_SETTING_BUILDER_ERR);
-        WasmSettings { settings }
+            .map_err(|err| settings_error(&[FieldError::new("adaptivePadding", err)]))?;
+        Ok(WasmSettings { settings })
     }
 
     #[wasm_bindgen(js_name = "withAdaptivePadding")]
-    pub fn with_adaptive_padding(&self, length: usize) -> WasmSettings {
+    pub fn with_adaptive_padding(&self, length: usize) -> Result<WasmSettings, JsValue> {
         let settings = self
             .settings
             .with_padding_strategy(PaddingStrategy::Adaptive(length))
-            .expect(DEFAULT_SETTING_BUILDER_ERR);
-        WasmSettings { settings }
+            .map_err(|err| settings_error(&[FieldError::new("adaptivePadding", err)]))?;
+        Ok(WasmSettings { settings })
     }
 
     #[wasm_bindgen(js_name = "withWordTransforms")]
-    pub fn with_word_transforms(&self, transforms: u8) -> WasmSettings {
+    pub fn with_word_transforms(&self, transforms: u8) -> Result<WasmSettings, JsValue> {
         let settings = self
             .settings
             .with_word_transforms(transforms)
-            .expect(DEFAULT_SETTING_BUILDER_ERR);
+            .map_err(|err| settings_error(&[FieldError::new("wordTransforms", err)]))?;
+        Ok(WasmSettings { settings })
+    }
+
+    #[wasm_bindgen(js_name = "withExcludedWords")]
+    pub fn with_excluded_words(&self, words: Vec<String>) -> WasmSettings {
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let settings = self.settings.with_excluded_words(&words);
         WasmSettings { settings }
     }
 
+    #[wasm_bindgen(js_name = "withIncludedWords")]
+    pub fn with_included_words(&self, words: Vec<String>) -> WasmSettings {
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let settings = self.settings.with_included_words(&words);
+        WasmSettings { settings }
+    }
+
+    #[wasm_bindgen(js_name = "withReadable")]
+    pub fn with_readable(&self, readable: bool) -> WasmSettings {
+        let settings = self.settings.with_readable(readable);
+        WasmSettings { settings }
+    }
+
+    /// Only use separators & padding symbols typable with the keyboard, any of them if `undefined`.
+    #[wasm_bindgen(js_name = "withKeyboard")]
+    pub fn with_keyboard(&self, keyboard: Option<Keyboard>) -> WasmSettings {
+        let settings = self.settings.with_keyboard(keyboard);
+        WasmSettings { settings }
+    }
+
+    /// Settings from a `SettingsObject`, throwing a `SettingsError` with all invalid fields.
+    #[wasm_bindgen(js_name = "fromObject")]
+    pub fn from_object(
+        #[wasm_bindgen(unchecked_param_type = "SettingsObject")] value: JsValue,
+    ) -> Result<WasmSettings, JsValue> {
+        let config =
+            SettingsConfig::from_object(&value).map_err(|errors| settings_error(&errors))?;
+        let settings = config.build().map_err(|err| settings_error(&[err]))?;
+        Ok(WasmSettings { settings })
+    }
+
+    /// Settings from the contents of an `xkpasswd.toml` config file, as used by the CLI.
+    #[cfg(feature = "wasm_toml")]
+    #[wasm_bindgen(js_name = "fromToml")]
+    pub fn from_toml(data: &str) -> Result<WasmSettings, JsValue> {
+        let config = SettingsConfig::from_toml(data).map_err(|errors| settings_error(&errors))?;
+        let settings = config.build().map_err(|err| settings_error(&[err]))?;
+        Ok(WasmSettings { settings })
+    }
+
+    /// Settings from a compact code of `toCode()` or the CLI's `config dump --code`,
+    /// e.g. from a URL query parameter.
+    #[wasm_bindgen(js_name = "fromCode")]
+    pub fn from_code(code: &str) -> Result<WasmSettings, JsValue> {
+        let settings = Settings::from_code(code)
+            .map_err(|err| settings_error(&[FieldError::malformed(err)]))?;
+        Ok(WasmSettings { settings })
+    }
+
+    #[wasm_bindgen(js_name = "toCode")]
+    pub fn to_code(&self) -> String {
+        self.settings.to_code()
+    }
+
     #[wasm_bindgen(js_name = "fromPreset")]
     pub fn from_preset(preset: Preset) -> WasmSettings {
         WasmSettings {
             settings: Settings::from_preset(preset),
         }
     }
+
+    #[wasm_bindgen(getter, js_name = "wordsCount")]
+    pub fn words_count(&self) -> u8 {
+        self.settings.words_count()
+    }
+
+    #[wasm_bindgen(getter, js_name = "wordLengths")]
+    pub fn word_lengths(&self) -> Vec<u8> {
+        let (min, max) = word_length_bounds(&self.settings);
+        vec![min, max]
+    }
+
+    #[wasm_bindgen(getter, js_name = "wordTransforms")]
+    pub fn word_transforms(&self) -> u8 {
+        self.settings.word_transforms()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn separators(&self) -> String {
+        self.settings.separators().to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = "paddingDigits")]
+    pub fn padding_digits(&self) -> Vec<u8> {
+        let (prefix, suffix) = self.settings.padding_digits();
+        vec![prefix, suffix]
+    }
+
+    #[wasm_bindgen(getter, js_name = "paddingSymbols")]
+    pub fn padding_symbols(&self) -> String {
+        self.settings.padding_symbols().to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = "paddingSymbolLengths")]
+    pub fn padding_symbol_lengths(&self) -> Vec<u8> {
+        let (prefix, suffix) = self.settings.padding_symbol_lengths();
+        vec![prefix, suffix]
+    }
+
+    /// Length to pad/trim passwords to, `undefined` for fixed padding.
+    #[wasm_bindgen(getter, js_name = "adaptivePadding")]
+    pub fn adaptive_padding(&self) -> Option<usize> {
+        adaptive_padding(&self.settings)
+    }
+
+    #[wasm_bindgen(getter, js_name = "excludedWords")]
+    pub fn excluded_words(&self) -> Vec<String> {
+        self.settings.excluded_words().iter().cloned().collect()
+    }
+
+    #[wasm_bindgen(getter, js_name = "includedWords")]
+    pub fn included_words(&self) -> Vec<String> {
+        self.settings.included_words().iter().cloned().collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn readable(&self) -> bool {
+        self.settings.readable()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn keyboard(&self) -> Option<Keyboard> {
+        self.settings.keyboard()
+    }
+
+    #[wasm_bindgen(getter, js_name = "availableWordLengths")]
+    pub fn available_word_lengths(&self) -> Vec<u8> {
+        self.settings.available_word_lengths().to_vec()
+    }
+
+    /// Human readable summary, one ` - ` prefixed line per setting.
+    pub fn describe(&self) -> String {
+        self.settings.to_string()
+    }
+
+    /// Plain object with all settings, also used by `JSON.stringify`.
+    #[wasm_bindgen(js_name = "toJSON", unchecked_return_type = "SettingsJSON")]
+    pub fn to_json(&self) -> JsValue {
+        let settings = &self.settings;
+        let json = Object::new();
+
+        set_field(&json, "wordsCount", settings.words_count());
+        set_field(
+            &json,
+            "wordLengths",
+            pair_array(word_length_bounds(settings)),
+        );
+        set_field(&json, "wordTransforms", settings.word_transforms());
+        set_field(&json, "separators", settings.separators());
+        set_field(
+            &json,
+            "paddingDigits",
+            pair_array(settings.padding_digits()),
+        );
+        set_field(&json, "paddingSymbols", settings.padding_symbols());
+        set_field(
+            &json,
+            "paddingSymbolLengths",
+            pair_array(settings.padding_symbol_lengths()),
+        );
+        set_field(
+            &json,
+            "adaptivePadding",
+            adaptive_padding(settings).map_or(JsValue::NULL, JsValue::from),
+        );
+        set_field(
+            &json,
+            "excludedWords",
+            string_array(settings.excluded_words()),
+        );
+        set_field(
+            &json,
+            "includedWords",
+            string_array(settings.included_words()),
+        );
+        set_field(&json, "readable", settings.readable());
+        set_field(
+            &json,
+            "keyboard",
+            settings
+                .keyboard()
+                .map_or(JsValue::NULL, |keyboard| JsValue::from(keyboard.name())),
+        );
+        set_field(
+            &json,
+            "availableWordLengths",
+            settings
+                .available_word_lengths()
+                .iter()
+                .map(|&len| JsValue::from(len))
+                .collect::<Array>(),
+        );
+
+        json.into()
+    }
 }
 
 #[wasm_bindgen]
 #[derive(Clone, Debug)]
 pub struct PasswdResult {
-    passwd: String,
+    passwd: Passwd,
     pub entropy: Entropy,
 }
 
@@ -113,14 +346,29 @@ pub struct PasswdResult {
 impl PasswdResult {
     #[wasm_bindgen(getter)]
     pub fn passwd(&self) -> String {
-        self.passwd.clone()
+        self.passwd.to_string()
+    }
+}
+
+// bundled dictionaries are borrowed statics, loaded ones are owned by the generator
+#[derive(Debug)]
+enum PassGenerator {
+    Bundled(Xkpasswd),
+    Loaded(Xkpasswd<OwnedDictIndex>),
+}
+
+impl Default for PassGenerator {
+    fn default() -> Self {
+        PassGenerator::Bundled(Xkpasswd::default())
     }
 }
 
 #[wasm_bindgen(js_name = "Xkpasswd")]
 #[derive(Debug, Default)]
 pub struct WasmXkpasswd {
-    pass_generator: Xkpasswd,
+    pass_generator: PassGenerator,
+    // usual layout of the language, for settings without keyboard
+    keyboard: Option<Keyboard>,
 }
 
 #[wasm_bindgen(js_class = "Xkpasswd")]
@@ -131,13 +379,127 @@ impl WasmXkpasswd {
         WasmXkpasswd::default()
     }
 
+    /// Generator for a single language, which must be bundled into the Wasm build.
+    #[wasm_bindgen(js_name = "forLanguage")]
+    pub fn for_language(language: Language) -> Result<WasmXkpasswd, JsError> {
+        set_panic_hook();
+
+        if !language.is_bundled() {
+            return Err(JsError::new(&format!(
+                "{:?} dictionary is not bundled",
+                language
+            )));
+        }
+
+        Ok(WasmXkpasswd {
+            pass_generator: PassGenerator::Bundled(Xkpasswd::for_language(language)),
+            keyboard: Some(Keyboard::for_language(language)),
+        })
+    }
+
+    /// Generator for a dictionary loaded at runtime, in the same `length:word,word,...`
+    /// format as bundled ones, so that Wasm builds don't need any `lang_*` feature.
+    #[wasm_bindgen(js_name = "fromDictionary")]
+    pub fn from_dictionary(dict_bytes: Vec<u8>) -> Result<WasmXkpasswd, JsError> {
+        set_panic_hook();
+
+        let dict = OwnedDictIndex::from_bytes(dict_bytes).map_err(|err| JsError::new(&err))?;
+
+        Ok(WasmXkpasswd {
+            pass_generator: PassGenerator::Loaded(Xkpasswd::new(dict)),
+            keyboard: None,
+        })
+    }
+
+    #[wasm_bindgen(js_name = "wordLengths")]
+    pub fn word_lengths(&self) -> Vec<u8> {
+        match &self.pass_generator {
+            PassGenerator::Bundled(generator) => generator.source().word_lengths(),
+            PassGenerator::Loaded(generator) => generator.source().word_lengths(),
+        }
+    }
+
+    /// Password from the settings, preset symbols being restricted to the keyboard
+    /// of the language of `forLanguage` generators unless the settings have their own.
     #[wasm_bindgen(js_name = "genPass")]
-    pub fn gen_pass(&self, js_settings: &WasmSettings) -> PasswdResult {
-        let settings: Settings = js_settings.settings.clone();
+    pub fn gen_pass(&self, js_settings: &WasmSettings) -> Result<PasswdResult, JsError> {
+        let settings = match self.keyboard {
+            Some(keyboard) => js_settings.settings.with_language_keyboard(keyboard),
+            None => js_settings.settings.clone(),
+        };
 
-        let (passwd, entropy) = self.pass_generator.gen_pass(&settings);
+        let (passwd, entropy) = match &self.pass_generator {
+            PassGenerator::Bundled(generator) => generator.gen_pass(&settings),
+            PassGenerator::Loaded(generator) => generator.gen_pass(&settings),
+        }
+        .map_err(|err| JsError::new(&err))?;
         console_log!("{:?} {:?}", settings, entropy);
 
-        PasswdResult { passwd, entropy }
+        Ok(PasswdResult { passwd, entropy })
+    }
+}
+
+#[wasm_bindgen]
+impl Entropy {
+    /// Human readable breakdown of blind & seen entropies and guess time.
+    pub fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[wasm_bindgen(js_name = "listPresets")]
+pub fn list_presets() -> Vec<Preset> {
+    Preset::ALL.to_vec()
+}
+
+/// Languages bundled into the Wasm build.
+#[wasm_bindgen(js_name = "listLanguages")]
+pub fn list_languages() -> Vec<Language> {
+    Language::ALL
+        .into_iter()
+        .filter(Language::is_bundled)
+        .collect()
+}
+
+/// SVG image of a Wi-Fi QR code, e.g. to print guest network cards.
+#[cfg(feature = "wasm_qr")]
+#[wasm_bindgen(js_name = "wifiQrSvg")]
+pub fn wifi_qr_svg(ssid: &str, security: WifiSecurity, passwd: &str) -> Result<String, JsError> {
+    WifiQr::new(ssid, security, passwd)
+        .map(|wifi_qr| wifi_qr.to_svg())
+        .map_err(|err| JsError::new(&err))
+}
+
+/// Password spelled out for phone calls & screen readers, one spoken group per item.
+#[wasm_bindgen(js_name = "spellPasswd")]
+pub fn spell_passwd(passwd: &str, language: Language, nato: bool) -> Vec<String> {
+    Speller::new(language).with_nato(nato).spell(passwd)
+}
+
+fn word_length_bounds(settings: &Settings) -> (u8, u8) {
+    let lengths = settings.word_lengths();
+    (lengths.start, lengths.end - 1)
+}
+
+fn adaptive_padding(settings: &Settings) -> Option<usize> {
+    match settings.padding_strategy() {
+        PaddingStrategy::Fixed => None,
+        PaddingStrategy::Adaptive(length) => Some(length),
     }
 }
+
+fn set_field(object: &Object, key: &str, value: impl Into<JsValue>) {
+    // plain objects are always extensible
+    let _ = Reflect::set(object, &key.into(), &value.into());
+}
+
+fn pair_array((first, second): (u8, u8)) -> Array {
+    Array::of2(&first.into(), &second.into())
+}
+
+fn string_array<'a>(values: impl IntoIterator<Item = &'a String>) -> Array {
+    values
+        .into_iter()
+        .map(|value| JsValue::from_str(value))
+        .collect()
+}
@@ -0,0 +1,65 @@
+//! Builds the C harness in `tests/ffi` against the shared library & generated header, then runs it.
+
+use std::env;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+// the shared library is built along with the rlib, next to the test binary or one level up
+fn shared_library_dir() -> PathBuf {
+    let library = format!("{}xkpasswd{}", DLL_PREFIX, DLL_SUFFIX);
+    let test_binary = env::current_exe().unwrap();
+
+    test_binary
+        .ancestors()
+        .skip(1)
+        .take(2)
+        .find(|dir| dir.join(&library).exists())
+        .unwrap_or_else(|| panic!("{} not found near {:?}", library, test_binary))
+        .to_path_buf()
+}
+
+#[test]
+fn test_c_harness() {
+    let library_dir = shared_library_dir();
+    let out_dir = tempfile::tempdir().unwrap();
+    let harness = out_dir.path().join("xkpasswd_test");
+    let compiler = env::var("CC").unwrap_or("cc".to_string());
+
+    let compiled = Command::new(&compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-I"])
+        .arg(Path::new(MANIFEST_DIR).join("include"))
+        .arg(Path::new(MANIFEST_DIR).join("tests/ffi/xkpasswd_test.c"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .args(["-lxkpasswd", "-o"])
+        .arg(&harness)
+        .status()
+        .unwrap_or_else(|err| panic!("unable to run C compiler '{}': {}", compiler, err));
+    assert!(compiled.success(), "C harness failed to compile");
+
+    // cargo's library path may point at stale builds with other features, ahead of the rpath
+    let library_path_var = if cfg!(target_os = "macos") {
+        "DYLD_LIBRARY_PATH"
+    } else {
+        "LD_LIBRARY_PATH"
+    };
+    let library_path = env::var_os(library_path_var).unwrap_or_default();
+    let library_path = env::join_paths(
+        [library_dir.clone()]
+            .into_iter()
+            .chain(env::split_paths(&library_path)),
+    )
+    .unwrap();
+
+    let output = Command::new(&harness)
+        .env(library_path_var, library_path)
+        .output()
+        .unwrap();
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success(), "C harness failed");
+}
@@ -0,0 +1,134 @@
+// C harness for the FFI bindings, built & run by tests/ffi.rs.
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "xkpasswd.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                      \
+  do {                                                                        \
+    if (!(condition)) {                                                       \
+      const char *error = xkpasswd_last_error();                              \
+      fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n", __FILE__, \
+              __LINE__, #condition, error ? error : "none");                  \
+      failures++;                                                             \
+    }                                                                         \
+  } while (0)
+
+static size_t count_char(const char *str, char needle) {
+  size_t count = 0;
+  for (; *str; str++) {
+    count += *str == needle;
+  }
+  return count;
+}
+
+static void test_gen_pass(void) {
+  XkGenerator *generator = xkpasswd_generator_new(XK_LANGUAGE_ENGLISH);
+  CHECK(generator != NULL);
+
+  XkSettings *settings = xkpasswd_settings_new();
+  CHECK(xkpasswd_settings_set_words_count(settings, 4) == XK_STATUS_OK);
+  CHECK(xkpasswd_settings_set_word_lengths(settings, 4, 6) == XK_STATUS_OK);
+  CHECK(xkpasswd_settings_set_word_transforms(
+            settings, XK_TRANSFORM_LOWERCASE | XK_TRANSFORM_UPPERCASE) ==
+        XK_STATUS_OK);
+  CHECK(xkpasswd_settings_set_separators(settings, "+") == XK_STATUS_OK);
+  CHECK(xkpasswd_settings_set_padding_digits(settings, 0, 2) == XK_STATUS_OK);
+  CHECK(xkpasswd_settings_set_padding_symbols(settings, "!") == XK_STATUS_OK);
+  CHECK(xkpasswd_settings_set_padding_symbol_lengths(settings, 1, 1) ==
+        XK_STATUS_OK);
+
+  char *passwd = NULL;
+  XkEntropy entropy = {0};
+  CHECK(xkpasswd_gen_pass(generator, settings, &passwd, &entropy) ==
+        XK_STATUS_OK);
+  CHECK(passwd != NULL);
+  if (passwd != NULL) {
+    printf("generated: %s\n", passwd);
+    CHECK(count_char(passwd, '+') == 4);
+    CHECK(passwd[0] == '!');
+    CHECK(passwd[strlen(passwd) - 1] == '!');
+  }
+  CHECK(entropy.seen > 0);
+  CHECK(entropy.blind_min <= entropy.blind_max);
+  xkpasswd_string_free(passwd);
+
+  // entropy is optional
+  passwd = NULL;
+  CHECK(xkpasswd_gen_pass(generator, settings, &passwd, NULL) == XK_STATUS_OK);
+  xkpasswd_string_free(passwd);
+
+  CHECK(xkpasswd_settings_set_adaptive_padding(settings, 32) == XK_STATUS_OK);
+  passwd = NULL;
+  CHECK(xkpasswd_gen_pass(generator, settings, &passwd, NULL) == XK_STATUS_OK);
+  CHECK(passwd != NULL && strlen(passwd) == 32);
+  xkpasswd_string_free(passwd);
+
+  xkpasswd_settings_free(settings);
+  xkpasswd_generator_free(generator);
+}
+
+static void test_presets(void) {
+  XkGenerator *generator = xkpasswd_generator_new(XK_LANGUAGE_GERMAN);
+  XkSettings *settings = xkpasswd_settings_from_preset(XK_PRESET_XKCD);
+
+  char *passwd = NULL;
+  CHECK(xkpasswd_gen_pass(generator, settings, &passwd, NULL) == XK_STATUS_OK);
+  CHECK(passwd != NULL && count_char(passwd, '-') == 3);
+  xkpasswd_string_free(passwd);
+
+  xkpasswd_settings_free(settings);
+  xkpasswd_generator_free(generator);
+}
+
+static void test_errors(void) {
+  XkSettings *settings = xkpasswd_settings_new();
+
+  CHECK(xkpasswd_settings_set_words_count(settings, 0) ==
+        XK_STATUS_INVALID_SETTINGS);
+  CHECK(strcmp(xkpasswd_last_error(),
+               "only positive integer is allowed for words count") == 0);
+
+  CHECK(xkpasswd_settings_set_word_transforms(settings, 0) ==
+        XK_STATUS_INVALID_SETTINGS);
+  CHECK(xkpasswd_settings_set_separators(settings, "\xff") ==
+        XK_STATUS_INVALID_UTF8);
+  CHECK(xkpasswd_settings_set_separators(settings, NULL) ==
+        XK_STATUS_NULL_POINTER);
+  CHECK(xkpasswd_settings_set_words_count(NULL, 3) == XK_STATUS_NULL_POINTER);
+
+  // unknown enum values are rejected
+  CHECK(xkpasswd_settings_from_preset(42) == NULL);
+  CHECK(strcmp(xkpasswd_last_error(), "unknown preset 42") == 0);
+  CHECK(xkpasswd_generator_new(42) == NULL);
+
+  char *passwd = NULL;
+  CHECK(xkpasswd_gen_pass(NULL, settings, &passwd, NULL) ==
+        XK_STATUS_NULL_POINTER);
+  CHECK(passwd == NULL);
+
+  // freeing NULL is a no-op
+  xkpasswd_settings_free(NULL);
+  xkpasswd_generator_free(NULL);
+  xkpasswd_string_free(NULL);
+
+  xkpasswd_settings_free(settings);
+}
+
+int main(void) {
+  test_gen_pass();
+  test_presets();
+  test_errors();
+
+  if (failures > 0) {
+    fprintf(stderr, "%d check(s) failed\n", failures);
+    return EXIT_FAILURE;
+  }
+
+  printf("all checks passed\n");
+  return EXIT_SUCCESS;
+}
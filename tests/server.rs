@@ -0,0 +1,129 @@
+//! Runs `xkpasswd serve` on a free local port & a Unix socket, then talks plain HTTP to it.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+const BINARY: &str = env!("CARGO_BIN_EXE_xkpasswd");
+
+// killed on drop, even when assertions fail
+struct ServerProcess {
+    child: Child,
+    address: String,
+}
+
+impl ServerProcess {
+    fn start(args: &[&str]) -> Self {
+        // an empty config file, not to depend on the one of the user
+        let config_file = tempfile::NamedTempFile::new().unwrap();
+
+        let mut child = Command::new(BINARY)
+            .arg("--config")
+            .arg(config_file.path())
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut line = String::new();
+        BufReader::new(child.stderr.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+
+        let address = line
+            .trim()
+            .strip_prefix("Listening on ")
+            .unwrap_or_else(|| panic!("unexpected output: {}", line))
+            .to_string();
+
+        ServerProcess { child, address }
+    }
+}
+
+impl Drop for ServerProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn request<S: Read + Write>(mut stream: S, method: &str, path: &str, body: &str) -> (u16, String) {
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, body.to_string())
+}
+
+fn tcp_request(server: &ServerProcess, method: &str, path: &str, body: &str) -> (u16, String) {
+    let address = server.address.strip_prefix("http://").unwrap();
+    request(TcpStream::connect(address).unwrap(), method, path, body)
+}
+
+#[test]
+fn test_serve_on_port() {
+    let server = ServerProcess::start(&["-P", "xkcd", "serve", "--port", "0"]);
+    assert!(server.address.starts_with("http://127.0.0.1:"));
+
+    let (status, body) = tcp_request(&server, "POST", "/generate", "");
+    assert_eq!(200, status);
+    assert!(body.starts_with(r#"{"passwords":[{"entropy":{"#));
+
+    let (status, body) = tcp_request(
+        &server,
+        "POST",
+        "/generate",
+        r##"{"settings": {"words_count": 2, "separators": "#", "padding": "fixed"}, "language": "de", "count": 5}"##,
+    );
+    assert_eq!(200, status);
+    assert_eq!(5, body.matches(r#""passwd":"#).count());
+    // separators between both words & the padding digits
+    assert_eq!(10, body.matches('#').count());
+
+    let (status, body) = tcp_request(&server, "POST", "/generate", r#"{"count": 1000}"#);
+    assert_eq!(400, status);
+    assert_eq!(
+        r#"{"error":"count must be an integer from 1 to 100"}"#,
+        body
+    );
+
+    let (status, body) = tcp_request(&server, "GET", "/presets", "");
+    assert_eq!(200, status);
+    assert!(body.contains(r#""name":"wifi""#));
+
+    let (status, body) = tcp_request(&server, "GET", "/languages?all=1", "");
+    assert_eq!(200, status);
+    assert!(body.contains(r#"{"code":"de","name":"German"}"#));
+
+    assert_eq!(405, tcp_request(&server, "DELETE", "/presets", "").0);
+    assert_eq!(404, tcp_request(&server, "GET", "/", "").0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_serve_on_unix_socket() {
+    use std::os::unix::net::UnixStream;
+
+    let socket_dir = tempfile::tempdir().unwrap();
+    let socket_path = socket_dir.path().join("xkpasswd.sock");
+    let _server = ServerProcess::start(&["serve", "--socket", socket_path.to_str().unwrap()]);
+
+    let stream = UnixStream::connect(&socket_path).unwrap();
+    let (status, body) = request(stream, "POST", "/generate", r#"{"preset": "wifi"}"#);
+    assert_eq!(200, status);
+
+    let passwd = body.split(r#""passwd":""#).nth(1).unwrap();
+    assert_eq!(63, passwd.split('"').next().unwrap().chars().count());
+}
@@ -0,0 +1,119 @@
+//! Drives `xkpasswd --stdio` through pipes, one JSON-RPC request & response per line.
+
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio};
+
+const BINARY: &str = env!("CARGO_BIN_EXE_xkpasswd");
+
+// killed on drop, even when assertions fail
+struct RpcProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: Lines<BufReader<ChildStdout>>,
+    _config_file: tempfile::NamedTempFile,
+}
+
+impl RpcProcess {
+    fn start(args: &[&str]) -> Self {
+        // an empty config file, not to depend on the one of the user
+        let config_file = tempfile::NamedTempFile::new().unwrap();
+
+        let mut child = Command::new(BINARY)
+            .arg("--config")
+            .arg(config_file.path())
+            .args(args)
+            .arg("--stdio")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+
+        RpcProcess {
+            child,
+            stdin,
+            stdout,
+            _config_file: config_file,
+        }
+    }
+
+    fn send(&mut self, request: &str) {
+        let stdin = self.stdin.as_mut().unwrap();
+        writeln!(stdin, "{}", request).unwrap();
+        stdin.flush().unwrap();
+    }
+
+    fn call(&mut self, request: &str) -> String {
+        self.send(request);
+        self.stdout.next().unwrap().unwrap()
+    }
+
+    fn close(&mut self) -> ExitStatus {
+        drop(self.stdin.take());
+        self.child.wait().unwrap()
+    }
+}
+
+impl Drop for RpcProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_stdio_session() {
+    let mut process = RpcProcess::start(&["-P", "xkcd"]);
+
+    // several calls to the same process, with dictionaries loaded once
+    for id in 1..=3 {
+        let response = process.call(&format!(
+            r#"{{"jsonrpc": "2.0", "method": "generate", "params": {{"language": "de"}}, "id": {}}}"#,
+            id
+        ));
+        assert!(
+            response.contains(&format!(r#""id":{},"#, id)),
+            "{}",
+            response
+        );
+        assert_eq!(1, response.matches(r#""passwd":"#).count());
+    }
+
+    let response = process.call(
+        r#"{"jsonrpc": "2.0", "method": "entropy", "params": {"settings": {"words_count": 2}}, "id": "e"}"#,
+    );
+    assert!(response.contains(r#""id":"e""#));
+    assert!(response.contains(r#""seen":"#));
+    assert!(!response.contains("passwd"));
+
+    let response = process.call(r#"{"jsonrpc": "2.0", "method": "describe", "id": 4}"#);
+    assert!(response.contains(r#""code":"xk1:w4-"#), "{}", response);
+
+    let response = process.call(r#"{"jsonrpc": "2.0", "method": "presets", "id": 5}"#);
+    assert!(response.contains(r#""name":"wifi""#));
+
+    // notifications get no response, so the next line answers the next request
+    process.send(r#"{"jsonrpc": "2.0", "method": "generate"}"#);
+    let response = process.call(r#"{"jsonrpc": "2.0", "method": "foo", "id": 6}"#);
+    assert!(response.contains(r#""code":-32601"#), "{}", response);
+
+    let response = process.call("not json");
+    assert!(response.contains(r#""code":-32700"#));
+    assert!(response.contains(r#""id":null"#));
+}
+
+#[test]
+fn test_stdio_closed_input() {
+    let mut process = RpcProcess::start(&[]);
+    let response = process
+        .call(r#"{"jsonrpc": "2.0", "method": "generate", "params": {"count": 0}, "id": 1}"#);
+    assert!(response.contains(r#""code":-32602"#), "{}", response);
+    assert!(response.contains("count must be an integer from 1 to 100"));
+
+    // exits once stdin is closed
+    assert!(process.close().success());
+    assert!(process.stdout.next().is_none());
+}